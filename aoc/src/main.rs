@@ -0,0 +1,263 @@
+//! Runs any day from the top of the repository, e.g. `aoc run 3`, instead of
+//! from inside the day's own crate.
+
+use clap::Parser;
+use common::inputs::{personal_path_in, YEAR};
+use common::timings::{self, TIMINGS_ENV};
+use report::answers::{answers_path, check, describe, parse, Check};
+use report::client::{session_file, Client, SESSION_ENV};
+use report::puzzle::Verdict;
+use report::timeline::{record_now, solves_path};
+use report::{year, Day, Solver, YEARS};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+
+/// Runs any day from the top of the repository.
+#[derive(Parser)]
+#[command(name = "aoc")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+/// The year a command is for.
+#[derive(clap::Args)]
+struct Year {
+    /// The puzzle's year
+    #[arg(long, default_value_t = YEAR)]
+    year: u32,
+}
+
+#[derive(clap::Subcommand)]
+enum Command {
+    /// List the days there are solutions for
+    List,
+    /// Check every answer in answers.toml still comes out the same
+    Verify,
+    /// Solve both parts of a day
+    Run {
+        day: u32,
+        #[command(flatten)]
+        year: Year,
+        /// Read the puzzle input from this file instead of the personal input
+        #[arg(long, value_name = "PATH")]
+        input: Option<PathBuf>,
+        /// Also print how long parsing and each part took
+        #[arg(long)]
+        time: bool,
+    },
+    /// Download a day's personal input, unless it's already there
+    Fetch {
+        day: u32,
+        #[command(flatten)]
+        year: Year,
+    },
+    /// Solve a part on the personal input and submit the answer
+    Submit {
+        day: u32,
+        #[arg(value_parser = clap::value_parser!(u32).range(1..=2))]
+        part: u32,
+        #[command(flatten)]
+        year: Year,
+    },
+}
+
+/// Downloads the day's personal input to where the days read it from,
+/// exiting with an error if it can't.
+fn download(client: &Client, year: u32, day: u32) -> PathBuf {
+    let fail = |message: String| -> ! {
+        eprintln!("{}", message);
+        process::exit(1);
+    };
+
+    let path = personal_path_in(year, day);
+    let input = client
+        .input(year, day)
+        .unwrap_or_else(|err| fail(err.to_string()));
+    path.parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|_| fs::write(&path, input))
+        .unwrap_or_else(|err| fail(format!("can't write {}: {}", path.display(), err)));
+    eprintln!("wrote {}", path.display());
+    path
+}
+
+/// Downloads the day's personal input unless it's already there.
+fn fetch(year: u32, day: u32) {
+    let path = personal_path_in(year, day);
+    if path.exists() {
+        eprintln!("already have {}", path.display());
+        return;
+    }
+
+    download(&logged_in(), year, day);
+}
+
+/// Returns a client for the site, exiting with an error if there's no session
+/// cookie to log in with.
+fn logged_in() -> Client {
+    let client = Client::from_env();
+    if !client.is_logged_in() {
+        let file = session_file().map_or(String::new(), |file| {
+            format!(" or put it in {}", file.display())
+        });
+        eprintln!(
+            "this needs logging in: set {} to your session cookie{}",
+            SESSION_ENV, file
+        );
+        process::exit(1);
+    }
+    client
+}
+
+/// Returns the year's day, exiting with an error if there's no solution for it.
+fn find_day(year_number: u32, day: u32) -> &'static Day {
+    year(year_number)
+        .and_then(|year| year.day(day))
+        .unwrap_or_else(|| {
+            eprintln!("no solution for {} day {}", year_number, day);
+            process::exit(1);
+        })
+}
+
+/// Reads the input at `path`, or else the day's personal input, which is
+/// downloaded first if it's missing and there's a session cookie to download
+/// it with.
+fn read_input(year: u32, day: u32, path: Option<PathBuf>) -> String {
+    let path = path.unwrap_or_else(|| {
+        let path = personal_path_in(year, day);
+        let client = Client::from_env();
+        if path.exists() || !client.is_logged_in() {
+            return path;
+        }
+        download(&client, year, day)
+    });
+    fs::read_to_string(&path).unwrap_or_else(|err| {
+        eprintln!("can't read {}: {}", path.display(), err);
+        process::exit(1);
+    })
+}
+
+/// Solves both parts of the day and prints them, reading the input from
+/// `input` or else the day's personal input. With `time`, also prints how long
+/// parsing and each part took, parsing once for both parts, and appends the
+/// parts' times to the CSV file named by `AOC_TIMINGS` if it's set.
+fn run_day(year: u32, day: u32, input: Option<PathBuf>, time: bool) {
+    let day = find_day(year, day);
+    let input = read_input(year, day.number, input);
+
+    let timings = day.timed(&input);
+    for (part, solved) in timings.parts.iter().enumerate() {
+        println!("part {}: {}", part + 1, solved.answer);
+    }
+    if time {
+        let parse = match timings.parse {
+            Some(parse) => format!("{:.3?}", parse),
+            None => String::from("counted in each part"),
+        };
+        println!(
+            "time: parse {}, part 1 {:.3?}, part 2 {:.3?}",
+            parse, timings.parts[0].elapsed, timings.parts[1].elapsed
+        );
+
+        if let Some(path) = env::var_os(TIMINGS_ENV) {
+            let path = Path::new(&path);
+            for (part, solved) in (1..).zip(&timings.parts) {
+                if let Err(err) = timings::append(path, day.number, part, solved.elapsed) {
+                    eprintln!("can't append to {}: {}", path.display(), err);
+                }
+            }
+        }
+    }
+}
+
+/// Notes the part as solved now in the timeline, unless it already was.
+/// Failing to is only worth a warning.
+fn record_solve(year: u32, day: u32, part: u32) {
+    if let Err(err) = record_now(year, day, part) {
+        eprintln!(
+            "can't record the solve in {}: {}",
+            solves_path().display(),
+            err
+        );
+    }
+}
+
+/// Solves the part on the day's personal input and submits the answer,
+/// printing what the site made of it and recording the solve if it was right.
+/// Exits with 1 unless it was right.
+fn submit(year: u32, day: u32, part: u32) {
+    let day = find_day(year, day);
+    let client = logged_in();
+    let input = read_input(year, day.number, None);
+    let answer = day.part(part, &input).unwrap();
+    println!("part {}: {}", part, answer);
+
+    let page = client
+        .submit(year, day.number, part, &answer.to_string())
+        .unwrap_or_else(|err| {
+            eprintln!("{}", err);
+            process::exit(1);
+        });
+    let verdict = Verdict::from_page(&page);
+    println!("{}", verdict);
+    if !verdict.is_correct() {
+        process::exit(1);
+    }
+    record_solve(year, day.number, part);
+}
+
+/// Checks every answer in `answers.toml` against its solver, printing how each
+/// did and recording parts that match as solved, if they aren't yet. Exits
+/// with 1 if any changed.
+fn verify() {
+    let path = answers_path();
+    let answers = fs::read_to_string(&path)
+        .map_err(|err| err.to_string())
+        .and_then(|text| parse(&text).map_err(|err| err.to_string()))
+        .unwrap_or_else(|err| {
+            eprintln!("{}: {}", path.display(), err);
+            process::exit(1);
+        });
+
+    let mut failed = 0;
+    for expected in &answers {
+        let check = check(expected);
+        if check.failed() {
+            failed += 1;
+        }
+        if check == Check::Same {
+            record_solve(expected.year, expected.day, expected.part);
+        }
+        println!("{}", describe(expected, &check));
+    }
+    if failed > 0 {
+        eprintln!("{} of {} answers changed", failed, answers.len());
+        process::exit(1);
+    }
+}
+
+/// Prints the days there are solutions for, by year.
+fn list() {
+    for year in &YEARS {
+        let days: Vec<String> = year.implemented().iter().map(u32::to_string).collect();
+        println!("{}: {}", year.number, days.join(" "));
+    }
+}
+
+fn main() {
+    match Cli::parse().command {
+        Command::List => list(),
+        Command::Verify => verify(),
+        Command::Run {
+            day,
+            year,
+            input,
+            time,
+        } => run_day(year.year, day, input, time),
+        Command::Fetch { day, year } => fetch(year.year, day),
+        Command::Submit { day, part, year } => submit(year.year, day, part),
+    }
+}
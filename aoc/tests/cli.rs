@@ -0,0 +1,161 @@
+use common::inputs::INPUTS_ENV;
+use common::require_input;
+use common::testing::{run_with_input, scratch_dir, stderr, stdout};
+use common::timings::{HEADER, TIMINGS_ENV};
+use report::timeline::load;
+use std::fs;
+use std::process::Command;
+
+const EXE: &str = env!("CARGO_BIN_EXE_aoc");
+
+#[test]
+fn test_run() {
+    let output = run_with_input(EXE, 1, Some("12\n14\n1969\n100756\n"), &["run", "1"]);
+    assert_eq!(stdout(&output), "part 1: 34241\npart 2: 51316\n");
+}
+
+#[test]
+fn test_time() {
+    let output = run_with_input(EXE, 1, Some("12\n14\n"), &["run", "1", "--time"]);
+    let out = stdout(&output);
+    let lines: Vec<&str> = out.lines().collect();
+    assert_eq!(lines.len(), 3, "{}", out);
+    assert_eq!(lines[0], "part 1: 4");
+    assert!(lines[2].starts_with("time: parse "), "{}", out);
+    assert!(lines[2].contains(", part 1 "), "{}", out);
+    assert!(lines[2].contains(", part 2 "), "{}", out);
+}
+
+#[test]
+fn test_time_appends_timings() {
+    let dir = scratch_dir();
+    fs::write(dir.join("day1.txt"), "12\n14\n").unwrap();
+    let path = dir.join("timings.csv");
+
+    let output = Command::new(EXE)
+        .args(["run", "1", "--time"])
+        .env(INPUTS_ENV, &dir)
+        .env(TIMINGS_ENV, &path)
+        .output()
+        .unwrap();
+    stdout(&output);
+
+    let csv = fs::read_to_string(&path).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 3, "{}", csv);
+    assert_eq!(lines[0], HEADER);
+    assert!(lines[1].contains(",1,1,"), "{}", lines[1]);
+    assert!(lines[2].contains(",1,2,"), "{}", lines[2]);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_missing_input() {
+    let output = run_with_input(EXE, 2, None, &["run", "2"]);
+    let err = stderr(&output);
+    assert!(err.starts_with("can't read "), "{}", err);
+    assert!(err.contains("day2.txt: "), "{}", err);
+}
+
+#[test]
+fn test_input_path() {
+    // day 1's masses, saved where only --input finds them
+    let output = run_with_input(
+        EXE,
+        9,
+        Some("12\n14\n"),
+        &["run", "1", "--input", "day9.txt"],
+    );
+    assert_eq!(stdout(&output), "part 1: 4\npart 2: 4\n");
+
+    let output = run_with_input(EXE, 1, Some("12"), &["run", "1", "--input", "missing"]);
+    assert!(stderr(&output).starts_with("can't read missing: "));
+}
+
+#[test]
+fn test_fetch_cached() {
+    let output = run_with_input(EXE, 3, Some("R8,U5\nU7,R6\n"), &["fetch", "3"]);
+    assert!(output.status.success());
+    let err = String::from_utf8(output.stderr).unwrap();
+    assert!(err.starts_with("already have "), "{}", err);
+    assert!(err.trim_end().ends_with("day3.txt"), "{}", err);
+}
+
+#[test]
+fn test_submit_part() {
+    // rejected by the argument parser before logging in or solving anything
+    let output = run_with_input(EXE, 1, Some("12"), &["submit", "1", "3"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_verify() {
+    // day 1 is the only input in the scratch directory, so the rest are skipped
+    let output = run_with_input(EXE, 1, Some("12\n"), &["verify"]);
+    let err = stderr(&output);
+    assert_eq!(err, "2 of 18 answers changed\n");
+
+    let out = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = out.lines().collect();
+    assert_eq!(lines.len(), 18);
+    assert_eq!(
+        lines[0],
+        "2019 day 1 part 1: changed, expected 3339288 but got 2"
+    );
+    assert_eq!(lines[2], "2019 day 2 part 1: no personal input, skipped");
+}
+
+#[test]
+fn test_verify_records_solves() {
+    let input = require_input!(1);
+    let dir = scratch_dir();
+    fs::write(dir.join("day1.txt"), input).unwrap();
+
+    for _ in 0..2 {
+        let output = Command::new(EXE)
+            .arg("verify")
+            .env(INPUTS_ENV, &dir)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+    }
+
+    // the other days have no input and are skipped; day 1 is recorded once
+    // however often it's verified
+    let solves = load(&dir.join("solves.csv")).unwrap();
+    let parts: Vec<(u32, u32)> = solves.iter().map(|solve| (solve.day, solve.part)).collect();
+    assert_eq!(parts, vec![(1, 1), (1, 2)]);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_unknown_day() {
+    let output = run_with_input(EXE, 25, Some("1"), &["run", "25"]);
+    assert_eq!(stderr(&output), "no solution for 2019 day 25\n");
+
+    let output = run_with_input(EXE, 1, Some("1"), &["run", "one"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_help() {
+    let output = run_with_input(EXE, 1, None, &["--help"]);
+    let out = stdout(&output);
+    for command in &["list", "verify", "run", "fetch", "submit"] {
+        assert!(out.contains(&format!("  {}  ", command)), "{}", out);
+    }
+
+    let output = run_with_input(EXE, 1, None, &["run", "--help"]);
+    assert!(stdout(&output).contains("--time"));
+
+    let output = run_with_input(EXE, 1, None, &["run", "1", "--year"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_list() {
+    let output = run_with_input(EXE, 1, None, &["list"]);
+    assert_eq!(stdout(&output), "2019: 1 2 3 4 5 6 7 8 9\n");
+}
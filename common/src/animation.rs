@@ -0,0 +1,173 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use crate::color::Color;
+use crate::grid::Grid;
+
+/// Records frames of a colored grid and writes them out as a looping animated GIF.
+pub struct Animation {
+    frames: Vec<Grid<Color>>,
+    scale: usize,
+    delay: u16,
+}
+
+impl Animation {
+    /// Creates an empty animation where each cell is drawn as a `scale` x `scale`
+    /// block of pixels and each frame is shown for `delay_ms` milliseconds (GIFs
+    /// only have centisecond precision).
+    pub fn new(scale: usize, delay_ms: u32) -> Self {
+        assert!(scale > 0, "scale must be positive");
+
+        Animation {
+            frames: Vec::new(),
+            scale,
+            delay: (delay_ms / 10).min(u16::MAX as u32) as u16,
+        }
+    }
+
+    /// Adds a frame to the end of the animation. Every frame must have the same
+    /// dimensions as the first one.
+    pub fn push(&mut self, frame: Grid<Color>) {
+        if let Some(first) = self.frames.first() {
+            assert!(
+                first.width() == frame.width() && first.height() == frame.height(),
+                "frame is {}x{}, expected {}x{}",
+                frame.width(),
+                frame.height(),
+                first.width(),
+                first.height()
+            );
+        }
+
+        self.frames.push(frame);
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Encodes the recorded frames as a GIF. All frames share one palette, so
+    /// they can use at most 256 distinct colors between them.
+    pub fn encode_gif<W: Write>(&self, writer: W) -> io::Result<()> {
+        let first = match self.frames.first() {
+            Some(first) => first,
+            None => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "animation has no frames",
+                ))
+            }
+        };
+
+        let mut palette = Vec::new();
+        let mut indices: HashMap<Color, u8> = HashMap::new();
+        for frame in self.frames.iter() {
+            for (_, color) in frame.iter() {
+                if indices.contains_key(color) {
+                    continue;
+                }
+                if indices.len() == 256 {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "animation uses more than 256 colors",
+                    ));
+                }
+                indices.insert(*color, indices.len() as u8);
+                palette.extend_from_slice(&[color.r, color.g, color.b]);
+            }
+        }
+
+        let width = first.width() * self.scale;
+        let height = first.height() * self.scale;
+        if width > u16::MAX as usize || height > u16::MAX as usize {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "animation is too large for a GIF",
+            ));
+        }
+
+        let mut encoder = gif::Encoder::new(writer, width as u16, height as u16, &palette)
+            .map_err(io::Error::other)?;
+        encoder
+            .set_repeat(gif::Repeat::Infinite)
+            .map_err(io::Error::other)?;
+
+        for frame in self.frames.iter() {
+            let mut pixels = Vec::with_capacity(width * height);
+            for row in frame.rows() {
+                let mut line = Vec::with_capacity(width);
+                for color in row {
+                    line.extend(std::iter::repeat_n(indices[color], self.scale));
+                }
+                for _ in 0..self.scale {
+                    pixels.extend_from_slice(&line);
+                }
+            }
+
+            let mut frame =
+                gif::Frame::from_indexed_pixels(width as u16, height as u16, pixels, None);
+            frame.delay = self.delay;
+            encoder.write_frame(&frame).map_err(io::Error::other)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the animation to a GIF file at `path`. See `encode_gif`.
+    pub fn write_gif<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let file = File::create(path)?;
+        self.encode_gif(BufWriter::new(file))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::point::Point2;
+
+    #[test]
+    fn test_encode_gif() {
+        let mut animation = Animation::new(3, 100);
+        assert!(animation.encode_gif(Vec::new()).is_err());
+
+        for i in 0..4 {
+            let mut frame = Grid::new(4, 2, Color::BLACK);
+            frame[Point2::new(i, 1)] = Color::RED;
+            animation.push(frame);
+        }
+        assert_eq!(animation.len(), 4);
+
+        let mut encoded = Vec::new();
+        animation.encode_gif(&mut encoded).unwrap();
+
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::Indexed);
+        let mut decoder = options.read_info(&encoded[..]).unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (12, 6));
+        assert_eq!(decoder.global_palette().unwrap(), &[0, 0, 0, 255, 0, 0][..]);
+
+        let mut frames = 0;
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!(frame.delay, 10);
+            // the red cell moves one cell (three pixels) to the right each frame
+            let red = frame.buffer.iter().position(|&index| index == 1).unwrap();
+            assert_eq!(red, 3 * 12 + frames * 3);
+            frames += 1;
+        }
+        assert_eq!(frames, 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "frame is 1x1, expected 2x2")]
+    fn test_mismatched_frames() {
+        let mut animation = Animation::new(1, 10);
+        animation.push(Grid::new(2, 2, Color::BLACK));
+        animation.push(Grid::new(1, 1, Color::BLACK));
+    }
+}
@@ -0,0 +1,119 @@
+//! A puzzle answer, which is usually a number but sometimes text, or letters
+//! drawn in a grid of pixels.
+
+use crate::grid::Grid;
+use crate::ocr;
+use std::convert::TryFrom;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Int(i64),
+    Text(String),
+    /// Letters drawn with lit pixels, answered by reading them.
+    Grid(Grid<bool>),
+}
+
+impl Answer {
+    /// Returns the answer as a JSON value: a number for numbers, otherwise the
+    /// text as a string.
+    pub fn to_json(&self) -> String {
+        match self {
+            Answer::Int(value) => value.to_string(),
+            answer => json_string(&answer.to_string()),
+        }
+    }
+}
+
+/// Escapes text for a JSON string.
+pub fn json_string(text: &str) -> String {
+    let mut escaped = String::from("\"");
+    for ch in text.chars() {
+        match ch {
+            '"' => escaped += "\\\"",
+            '\\' => escaped += "\\\\",
+            '\n' => escaped += "\\n",
+            ch if (ch as u32) < 0x20 => escaped += &format!("\\u{:04x}", ch as u32),
+            ch => escaped.push(ch),
+        }
+    }
+    escaped + "\""
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(value) => write!(f, "{}", value),
+            Answer::Text(text) => write!(f, "{}", text),
+            Answer::Grid(pixels) => write!(f, "{}", ocr::read(pixels)),
+        }
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(value: i64) -> Self {
+        Answer::Int(value)
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(value: i32) -> Self {
+        Answer::Int(value.into())
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(value: u32) -> Self {
+        Answer::Int(value.into())
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(value: u64) -> Self {
+        i64::try_from(value).map_or_else(|_| Answer::Text(value.to_string()), Answer::Int)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Answer::from(value as u64)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::Text(text.to_string())
+    }
+}
+
+impl From<Grid<bool>> for Answer {
+    fn from(pixels: Grid<bool>) -> Self {
+        Answer::Grid(pixels)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(Answer::from(42usize), Answer::Int(42));
+        assert_eq!(Answer::from(u64::MAX).to_string(), "18446744073709551615");
+        assert_eq!(Answer::from(-7i64).to_json(), "-7");
+        assert_eq!(Answer::from("A\"B\n").to_json(), r#""A\"B\n""#);
+
+        let pixels = Grid::parse("#....\n#....\n#....\n#....\n#....\n####.", |ch| {
+            Some(ch == '#')
+        });
+        let answer = Answer::from(pixels.unwrap());
+        assert_eq!(answer.to_string(), "L");
+        assert_eq!(answer.to_json(), "\"L\"");
+    }
+}
@@ -0,0 +1,157 @@
+//! Command line flags shared by the days' binaries.
+
+use clap::Parser;
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+
+use crate::answer::Answer;
+use crate::inputs::personal_path;
+
+/// Flags every day's binary takes. Days with flags of their own flatten these
+/// into theirs and call `for_day`; the rest just call `parse`.
+#[derive(Debug, Clone, Default, clap::Args)]
+pub struct Args {
+    /// Read the puzzle input from this file instead of inputs/dayN.txt
+    #[arg(long, value_name = "PATH")]
+    pub input: Option<PathBuf>,
+
+    /// Only solve this part
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..=2))]
+    pub part: Option<u32>,
+
+    /// Also draw the day's picture, for days that have one
+    #[arg(long)]
+    pub render: bool,
+
+    /// Print each answer as a line of JSON
+    #[arg(long, conflicts_with = "quiet")]
+    pub json: bool,
+
+    /// Print just the answers, one per line, without labels or pictures
+    #[arg(long)]
+    pub quiet: bool,
+
+    #[arg(skip)]
+    day: u32,
+}
+
+#[derive(Parser)]
+struct Only {
+    #[command(flatten)]
+    args: Args,
+}
+
+impl Args {
+    /// Parses the flags of a day that has none of its own.
+    pub fn parse(day: u32) -> Self {
+        Only::parse().args.for_day(day)
+    }
+
+    /// Sets the day the flags are for.
+    pub fn for_day(mut self, day: u32) -> Self {
+        self.day = day;
+        self
+    }
+
+    /// Returns where the puzzle input is read from.
+    pub fn input_path(&self) -> PathBuf {
+        self.input
+            .clone()
+            .unwrap_or_else(|| personal_path(self.day))
+    }
+
+    /// Returns the puzzle input, exiting with an error if it can't be read.
+    pub fn input(&self) -> String {
+        let path = self.input_path();
+
+        match fs::read_to_string(&path) {
+            Ok(input) => input,
+            Err(err) => {
+                eprintln!("can't read {}: {}", path.display(), err);
+                process::exit(1);
+            }
+        }
+    }
+
+    /// Returns whether `part` should be solved.
+    pub fn wants(&self, part: u32) -> bool {
+        self.part.is_none_or(|only| only == part)
+    }
+
+    /// Returns whether there's room for more than the answers, like labels and
+    /// explanations.
+    pub fn verbose(&self) -> bool {
+        !self.quiet && !self.json
+    }
+
+    /// Formats the answer to `part` as it should be printed, with the label if
+    /// the output isn't for scripts.
+    pub fn format_answer(&self, part: u32, label: Option<&str>, answer: &Answer) -> String {
+        match label {
+            _ if self.json => format!(
+                "{{\"day\":{},\"part\":{},\"answer\":{}}}",
+                self.day,
+                part,
+                answer.to_json()
+            ),
+            Some(label) if !self.quiet => format!("{}: {}", label, answer),
+            _ => answer.to_string(),
+        }
+    }
+
+    /// Prints the answer to `part`.
+    pub fn answer(&self, part: u32, answer: impl Into<Answer>) {
+        println!("{}", self.format_answer(part, None, &answer.into()));
+    }
+
+    /// Prints the answer to `part` after a label, unless the output's for
+    /// scripts.
+    pub fn labelled(&self, part: u32, label: &str, answer: impl Into<Answer>) {
+        println!("{}", self.format_answer(part, Some(label), &answer.into()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Args, clap::Error> {
+        let args = Some("day3").into_iter().chain(args.iter().copied());
+        Only::try_parse_from(args).map(|only| only.args.for_day(3))
+    }
+
+    #[test]
+    fn test_parse() {
+        let args = parse(&["--part", "2", "--input", "in.txt"]).unwrap();
+        assert_eq!(args.input, Some(PathBuf::from("in.txt")));
+        assert!(!args.wants(1) && args.wants(2));
+        assert!(args.verbose());
+
+        assert!(parse(&["--part", "3"]).is_err());
+        assert!(parse(&["--json", "--quiet"]).is_err());
+        assert!(parse(&["--verbose"]).is_err());
+    }
+
+    #[test]
+    fn test_format_answer() {
+        let twelve = Answer::Int(12);
+        let plain = parse(&[]).unwrap();
+        assert_eq!(plain.format_answer(1, Some("Fuel"), &twelve), "Fuel: 12");
+        assert_eq!(plain.format_answer(1, None, &twelve), "12");
+
+        let quiet = parse(&["--quiet"]).unwrap();
+        assert_eq!(quiet.format_answer(1, Some("Fuel"), &twelve), "12");
+
+        let json = parse(&["--json"]).unwrap();
+        assert!(!json.verbose());
+        assert_eq!(
+            json.format_answer(2, Some("Fuel"), &Answer::from("A\"B\n")),
+            r#"{"day":3,"part":2,"answer":"A\"B\n"}"#
+        );
+        assert_eq!(
+            json.format_answer(1, None, &twelve),
+            r#"{"day":3,"part":1,"answer":12}"#
+        );
+    }
+}
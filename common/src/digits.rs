@@ -0,0 +1,91 @@
+/// Iterator over the decimal digits of a number, most significant first.
+#[derive(Debug, Clone)]
+pub struct Digits {
+    value: u64,
+    // number of digits left in `value`
+    len: u32,
+}
+
+/// Returns the decimal digits of `n`, most significant first. Zero has a single
+/// digit.
+pub fn digits(n: u64) -> Digits {
+    let mut len = 1;
+    while len < 20 && 10u64.pow(len) <= n {
+        len += 1;
+    }
+
+    Digits { value: n, len }
+}
+
+/// Reassembles digits, most significant first, into a number. Panics if a digit
+/// is larger than 9.
+pub fn from_digits<I: IntoIterator<Item = u8>>(digits: I) -> u64 {
+    digits.into_iter().fold(0, |n, digit| {
+        assert!(digit < 10, "{} is not a digit", digit);
+        n * 10 + u64::from(digit)
+    })
+}
+
+impl Iterator for Digits {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        if self.len == 0 {
+            return None;
+        }
+
+        self.len -= 1;
+        let place = 10u64.pow(self.len);
+        let digit = self.value / place;
+        self.value %= place;
+
+        Some(digit as u8)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len as usize, Some(self.len as usize))
+    }
+}
+
+impl DoubleEndedIterator for Digits {
+    fn next_back(&mut self) -> Option<u8> {
+        if self.len == 0 {
+            return None;
+        }
+
+        self.len -= 1;
+        let digit = self.value % 10;
+        self.value /= 10;
+
+        Some(digit as u8)
+    }
+}
+
+impl ExactSizeIterator for Digits {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_digits() {
+        assert_eq!(digits(0).collect::<Vec<_>>(), vec![0]);
+        assert_eq!(digits(138307).collect::<Vec<_>>(), vec![1, 3, 8, 3, 0, 7]);
+        assert_eq!(digits(1000).rev().collect::<Vec<_>>(), vec![0, 0, 0, 1]);
+        assert_eq!(digits(u64::MAX).len(), 20);
+
+        let mut both = digits(12345);
+        assert_eq!(both.next(), Some(1));
+        assert_eq!(both.next_back(), Some(5));
+        assert_eq!(both.collect::<Vec<_>>(), vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn test_from_digits() {
+        for &n in [0, 7, 10, 654504, u64::MAX].iter() {
+            assert_eq!(from_digits(digits(n)), n);
+        }
+        assert_eq!(from_digits(digits(1200).rev()), 21);
+        assert_eq!(from_digits(vec![]), 0);
+    }
+}
@@ -0,0 +1,110 @@
+use std::convert::TryFrom;
+
+use crate::point::Point2;
+
+/// One of the four compass directions.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    South,
+    East,
+    West,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// Returns the direction a quarter turn counter-clockwise from this one.
+    pub fn turn_left(self) -> Self {
+        match self {
+            Direction::North => Direction::West,
+            Direction::West => Direction::South,
+            Direction::South => Direction::East,
+            Direction::East => Direction::North,
+        }
+    }
+
+    /// Returns the direction a quarter turn clockwise from this one.
+    pub fn turn_right(self) -> Self {
+        match self {
+            Direction::North => Direction::East,
+            Direction::East => Direction::South,
+            Direction::South => Direction::West,
+            Direction::West => Direction::North,
+        }
+    }
+
+    /// Returns the opposite direction.
+    pub fn reverse(self) -> Self {
+        self.turn_left().turn_left()
+    }
+
+    /// Returns the unit step in this direction with y pointing up, like the wires in
+    /// day3 and `Point2`'s rotations.
+    pub fn delta(self) -> Point2 {
+        match self {
+            Direction::North => Point2::new(0, 1),
+            Direction::South => Point2::new(0, -1),
+            Direction::East => Point2::new(1, 0),
+            Direction::West => Point2::new(-1, 0),
+        }
+    }
+
+    /// Returns the unit step in this direction with y pointing down, like rows in a
+    /// `Grid` or on screen.
+    pub fn screen_delta(self) -> Point2 {
+        let delta = self.delta();
+        Point2::new(delta.x, -delta.y)
+    }
+}
+
+/// Parses the `U`, `D`, `R` and `L` used by wire paths, returning the character
+/// back if it isn't one of them.
+impl TryFrom<char> for Direction {
+    type Error = char;
+
+    fn try_from(ch: char) -> Result<Self, char> {
+        match ch {
+            'U' => Ok(Direction::North),
+            'D' => Ok(Direction::South),
+            'R' => Ok(Direction::East),
+            'L' => Ok(Direction::West),
+            _ => Err(ch),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turning() {
+        for &dir in Direction::ALL.iter() {
+            assert_eq!(dir.turn_left().turn_right(), dir);
+            assert_eq!(dir.turn_right().turn_right(), dir.reverse());
+            assert_eq!(dir.turn_left().delta(), dir.delta().rotate_left());
+            assert_eq!(dir.turn_right().delta(), dir.delta().rotate_right());
+            assert_eq!(dir.delta() + dir.reverse().delta(), Point2::ORIGIN);
+        }
+    }
+
+    #[test]
+    fn test_deltas() {
+        assert_eq!(Direction::North.delta(), Point2::new(0, 1));
+        assert_eq!(Direction::North.screen_delta(), Point2::new(0, -1));
+        assert_eq!(Direction::East.screen_delta(), Point2::new(1, 0));
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Direction::try_from('U'), Ok(Direction::North));
+        assert_eq!(Direction::try_from('L'), Ok(Direction::West));
+        assert_eq!(Direction::try_from('x'), Err('x'));
+    }
+}
@@ -0,0 +1,126 @@
+/// Disjoint set union (union-find) over the integers `0..len`, with path
+/// compression and union by rank.
+#[derive(Debug, Clone)]
+pub struct Dsu {
+    parents: Vec<usize>,
+    ranks: Vec<u8>,
+    sizes: Vec<usize>,
+    sets: usize,
+}
+
+impl Dsu {
+    /// Creates `len` singleton sets.
+    pub fn new(len: usize) -> Self {
+        Dsu {
+            parents: (0..len).collect(),
+            ranks: vec![0; len],
+            sizes: vec![1; len],
+            sets: len,
+        }
+    }
+
+    /// Returns the number of elements.
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    /// Returns the number of disjoint sets.
+    pub fn sets(&self) -> usize {
+        self.sets
+    }
+
+    /// Returns the representative of the set containing `x`.
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+
+        let mut x = x;
+        while self.parents[x] != root {
+            let next = self.parents[x];
+            self.parents[x] = root;
+            x = next;
+        }
+
+        root
+    }
+
+    /// Merges the sets containing `a` and `b`, returning false if they were
+    /// already the same set.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let a = self.find(a);
+        let b = self.find(b);
+        if a == b {
+            return false;
+        }
+
+        let (parent, child) = if self.ranks[a] < self.ranks[b] {
+            (b, a)
+        } else {
+            (a, b)
+        };
+
+        self.parents[child] = parent;
+        self.sizes[parent] += self.sizes[child];
+        if self.ranks[parent] == self.ranks[child] {
+            self.ranks[parent] += 1;
+        }
+        self.sets -= 1;
+
+        true
+    }
+
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// Returns the number of elements in the set containing `x`.
+    pub fn size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.sizes[root]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_union_find() {
+        let mut dsu = Dsu::new(6);
+        assert_eq!(dsu.sets(), 6);
+
+        assert!(dsu.union(0, 1));
+        assert!(dsu.union(2, 3));
+        assert!(dsu.union(1, 3));
+        assert!(!dsu.union(0, 2));
+
+        assert!(dsu.connected(0, 3));
+        assert!(!dsu.connected(0, 4));
+        assert_eq!(dsu.size(2), 4);
+        assert_eq!(dsu.size(5), 1);
+        assert_eq!(dsu.sets(), 3);
+    }
+
+    #[test]
+    fn test_long_chain() {
+        let n = 100_000;
+        let mut dsu = Dsu::new(n);
+        for i in 1..n {
+            dsu.union(i - 1, i);
+        }
+
+        assert_eq!(dsu.sets(), 1);
+        assert_eq!(dsu.size(0), n);
+        // union by rank keeps the tree shallow, and after a find every element on
+        // the path points straight at the root
+        let root = dsu.find(0);
+        assert!(dsu.ranks[root] <= 1);
+        assert!(dsu.parents.iter().all(|&parent| parent == root));
+    }
+}
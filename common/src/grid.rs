@@ -0,0 +1,324 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+use crate::point::Point2;
+
+/// A rectangular grid of cells addressed by `Point2`, with (0, 0) in the top left
+/// corner and y growing downwards.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum GridError {
+    /// A row had a different length than the first one.
+    Ragged {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// A character couldn't be turned into a cell.
+    Cell { row: usize, column: usize, ch: char },
+    /// The number of cells doesn't match the dimensions.
+    Size { expected: usize, found: usize },
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            GridError::Ragged {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "row {} has {} cells, expected {}",
+                row + 1,
+                found,
+                expected
+            ),
+            GridError::Cell { row, column, ch } => write!(
+                f,
+                "unexpected character {:?} at line {}, column {}",
+                ch,
+                row + 1,
+                column + 1
+            ),
+            GridError::Size { expected, found } => {
+                write!(f, "got {} cells, expected {}", found, expected)
+            }
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Creates a grid with every cell set to `fill`.
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from cells in row-major order.
+    pub fn from_vec(width: usize, height: usize, cells: Vec<T>) -> Result<Self, GridError> {
+        if cells.len() != width * height {
+            return Err(GridError::Size {
+                expected: width * height,
+                found: cells.len(),
+            });
+        }
+
+        Ok(Grid {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    /// Parses a character map with one row per line, converting each character
+    /// with `cell`. Blank lines at the start and end are ignored.
+    pub fn parse<F>(input: &str, mut cell: F) -> Result<Self, GridError>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+
+        for (row, line) in input.trim_matches('\n').lines().enumerate() {
+            let line = line.trim_end_matches('\r');
+
+            let mut found = 0;
+            for (column, ch) in line.chars().enumerate() {
+                match cell(ch) {
+                    Some(value) => cells.push(value),
+                    None => return Err(GridError::Cell { row, column, ch }),
+                }
+                found += 1;
+            }
+
+            let expected = *width.get_or_insert(found);
+            if found != expected {
+                return Err(GridError::Ragged {
+                    row,
+                    expected,
+                    found,
+                });
+            }
+
+            height += 1;
+        }
+
+        Ok(Grid {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns whether the point lies inside the grid.
+    pub fn contains(&self, point: Point2) -> bool {
+        point.x >= 0
+            && point.y >= 0
+            && (point.x as usize) < self.width
+            && (point.y as usize) < self.height
+    }
+
+    fn offset(&self, point: Point2) -> Option<usize> {
+        if self.contains(point) {
+            Some(point.y as usize * self.width + point.x as usize)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the cell at the point, or None if it's out of bounds.
+    pub fn get(&self, point: Point2) -> Option<&T> {
+        self.offset(point).map(|offset| &self.cells[offset])
+    }
+
+    /// Returns the cell at the point, or None if it's out of bounds.
+    pub fn get_mut(&mut self, point: Point2) -> Option<&mut T> {
+        match self.offset(point) {
+            Some(offset) => Some(&mut self.cells[offset]),
+            None => None,
+        }
+    }
+
+    /// Returns every point in the grid in row-major order.
+    pub fn points(&self) -> impl Iterator<Item = Point2> {
+        let width = self.width as i64;
+        let height = self.height as i64;
+        (0..height).flat_map(move |y| (0..width).map(move |x| Point2::new(x, y)))
+    }
+
+    /// Returns every cell along with its position, in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point2, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    /// Returns the rows of the grid from top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks panics on a zero chunk size, which an empty grid would give
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Returns the cells in column `x` from top to bottom.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {} out of bounds", x);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// Returns the columns of the grid from left to right.
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    /// Applies `f` to every cell, keeping the layout.
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<Point2> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point2) -> &T {
+        match self.get(point) {
+            Some(cell) => cell,
+            None => panic!("point {:?} is outside the grid", point),
+        }
+    }
+}
+
+impl<T> IndexMut<Point2> for Grid<T> {
+    fn index_mut(&mut self, point: Point2) -> &mut T {
+        match self.offset(point) {
+            Some(offset) => &mut self.cells[offset],
+            None => panic!("point {:?} is outside the grid", point),
+        }
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = GridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Grid::parse(s, Some)
+    }
+}
+
+/// Renders one row per line with no separators, so a `Grid<char>` prints back as
+/// the map it was parsed from.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAP: &str = "#.#\n..#\n";
+
+    #[test]
+    fn test_parse_and_display() {
+        let grid: Grid<char> = MAP.parse().unwrap();
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid[Point2::new(2, 1)], '#');
+        assert_eq!(grid[Point2::new(1, 1)], '.');
+        assert_eq!(grid.to_string(), MAP);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            "##\n#\n".parse::<Grid<char>>(),
+            Err(GridError::Ragged {
+                row: 1,
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(
+            Grid::parse("#.\n.x", |ch| match ch {
+                '#' => Some(true),
+                '.' => Some(false),
+                _ => None,
+            }),
+            Err(GridError::Cell {
+                row: 1,
+                column: 1,
+                ch: 'x'
+            })
+        );
+    }
+
+    #[test]
+    fn test_bounds() {
+        let mut grid = Grid::new(2, 2, 0);
+        assert!(grid.contains(Point2::new(1, 1)));
+        assert!(!grid.contains(Point2::new(2, 0)));
+        assert!(!grid.contains(Point2::new(0, -1)));
+        assert_eq!(grid.get(Point2::new(-1, 0)), None);
+
+        grid[Point2::new(1, 0)] = 5;
+        *grid.get_mut(Point2::new(0, 1)).unwrap() = 7;
+        assert_eq!(grid.get(Point2::new(1, 0)), Some(&5));
+        assert_eq!(grid.get_mut(Point2::new(2, 2)), None);
+        assert_eq!(
+            grid.iter().map(|(_, v)| *v).collect::<Vec<_>>(),
+            vec![0, 5, 7, 0]
+        );
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = Grid::from_vec(3, 2, vec![1, 2, 3, 4, 5, 6]).unwrap();
+        let rows: Vec<&[i32]> = grid.rows().collect();
+        assert_eq!(rows, vec![&[1, 2, 3][..], &[4, 5, 6][..]]);
+
+        let columns: Vec<Vec<i32>> = grid.columns().map(|c| c.cloned().collect()).collect();
+        assert_eq!(columns, vec![vec![1, 4], vec![2, 5], vec![3, 6]]);
+
+        assert_eq!(
+            Grid::from_vec(2, 2, vec![1]),
+            Err(GridError::Size {
+                expected: 4,
+                found: 1
+            })
+        );
+    }
+}
@@ -0,0 +1,130 @@
+//! Locates puzzle inputs. Personal inputs live in `inputs/dayN.txt` at the root
+//! of the repository for 2019, and in `inputs/<year>/dayN.txt` for later years,
+//! and aren't checked in; examples from the puzzle text are checked in under
+//! `inputs/examples`.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Environment variable that overrides the directory personal inputs are read
+/// from.
+pub const INPUTS_ENV: &str = "AOC_INPUTS";
+
+// the repository's path when this crate was built, so binaries moved
+// elsewhere need AOC_INPUTS
+fn repo_inputs() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("inputs")
+}
+
+/// Returns the directory personal inputs are read from.
+pub fn inputs_dir() -> PathBuf {
+    match env::var_os(INPUTS_ENV) {
+        Some(dir) => PathBuf::from(dir),
+        None => repo_inputs(),
+    }
+}
+
+/// The year the days' crates solve, whose inputs sit directly in the inputs
+/// directory since it came first.
+pub const YEAR: u32 = 2019;
+
+/// Returns where the personal input for `day` of `year` is expected to be.
+pub fn personal_path_in(year: u32, day: u32) -> PathBuf {
+    let dir = inputs_dir();
+    let dir = if year == YEAR {
+        dir
+    } else {
+        dir.join(year.to_string())
+    };
+    dir.join(format!("day{}.txt", day))
+}
+
+/// Returns the personal input for `day` of `year`, or None if it hasn't been
+/// downloaded.
+pub fn personal_in(year: u32, day: u32) -> Option<String> {
+    fs::read_to_string(personal_path_in(year, day)).ok()
+}
+
+/// Returns where the personal input for `day` is expected to be.
+pub fn personal_path(day: u32) -> PathBuf {
+    personal_path_in(YEAR, day)
+}
+
+/// Returns the personal input for `day`, or None if it hasn't been downloaded.
+pub fn personal(day: u32) -> Option<String> {
+    personal_in(YEAR, day)
+}
+
+/// Returns the directory the checked-in examples live in.
+pub fn examples_dir() -> PathBuf {
+    repo_inputs().join("examples")
+}
+
+/// Returns where the `index`th example from the puzzle text for `day` of
+/// `year` is kept, counting from 0: `dayN.txt` for the first and `dayN-2.txt`
+/// and so on for the rest, in a directory for the year like personal inputs.
+pub fn example_path_in(year: u32, day: u32, index: usize) -> PathBuf {
+    let dir = examples_dir();
+    let dir = if year == YEAR {
+        dir
+    } else {
+        dir.join(year.to_string())
+    };
+    match index {
+        0 => dir.join(format!("day{}.txt", day)),
+        index => dir.join(format!("day{}-{}.txt", day, index + 1)),
+    }
+}
+
+/// Returns the checked-in example `inputs/examples/<name>.txt`.
+pub fn example(name: &str) -> String {
+    let path = examples_dir().join(format!("{}.txt", name));
+
+    fs::read_to_string(&path)
+        .unwrap_or_else(|err| panic!("can't read example {}: {}", path.display(), err))
+}
+
+/// Evaluates to the personal input for a day, or returns early from the enclosing
+/// test if it isn't present.
+#[macro_export]
+macro_rules! require_input {
+    ($day:expr) => {
+        match $crate::inputs::personal($day) {
+            Some(input) => input,
+            None => {
+                eprintln!(
+                    "skipping, no personal input at {}",
+                    $crate::inputs::personal_path($day).display()
+                );
+                return;
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_personal_path_in() {
+        let dir = inputs_dir();
+        assert_eq!(personal_path(9), dir.join("day9.txt"));
+        assert_eq!(personal_path_in(2019, 9), dir.join("day9.txt"));
+        assert_eq!(personal_path_in(2020, 1), dir.join("2020").join("day1.txt"));
+    }
+
+    #[test]
+    fn test_example_path_in() {
+        let dir = examples_dir();
+        assert_eq!(example_path_in(2019, 6, 0), dir.join("day6.txt"));
+        assert_eq!(example_path_in(2019, 6, 1), dir.join("day6-2.txt"));
+        assert_eq!(
+            example_path_in(2020, 1, 0),
+            dir.join("2020").join("day1.txt")
+        );
+    }
+}
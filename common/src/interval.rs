@@ -0,0 +1,135 @@
+use std::ops::RangeInclusive;
+
+/// An inclusive range of integers, `start..=end`. Never empty.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval {
+    pub start: i64,
+    pub end: i64,
+}
+
+impl Interval {
+    pub fn new(start: i64, end: i64) -> Self {
+        assert!(start <= end, "interval {}..={} is empty", start, end);
+        Interval { start, end }
+    }
+
+    /// Returns the number of integers in the interval.
+    pub fn length(&self) -> u64 {
+        self.end.abs_diff(self.start) + 1
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        self.start <= value && value <= self.end
+    }
+
+    pub fn overlaps(&self, other: &Interval) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    /// Returns the values in both intervals, or None if they don't overlap.
+    pub fn intersection(&self, other: &Interval) -> Option<Interval> {
+        if self.overlaps(other) {
+            Some(Interval::new(
+                self.start.max(other.start),
+                self.end.min(other.end),
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the values in either interval, or None if there's a gap between
+    /// them so the union isn't a single interval. Touching intervals like `1..=2`
+    /// and `3..=4` are joined.
+    pub fn union(&self, other: &Interval) -> Option<Interval> {
+        let (first, second) = if self.start <= other.start {
+            (self, other)
+        } else {
+            (other, self)
+        };
+
+        if second.start > first.end.saturating_add(1) {
+            return None;
+        }
+
+        Some(Interval::new(first.start, first.end.max(second.end)))
+    }
+
+    pub fn iter(&self) -> RangeInclusive<i64> {
+        self.start..=self.end
+    }
+}
+
+impl IntoIterator for Interval {
+    type Item = i64;
+    type IntoIter = RangeInclusive<i64>;
+
+    fn into_iter(self) -> RangeInclusive<i64> {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn interval() -> impl Strategy<Value = Interval> {
+        (-20i64..20, 0i64..20).prop_map(|(start, len)| Interval::new(start, start + len))
+    }
+
+    #[test]
+    fn test_interval() {
+        let a = Interval::new(1, 5);
+        assert_eq!(a.length(), 5);
+        assert!(a.contains(1) && a.contains(5) && !a.contains(6));
+        assert_eq!(
+            a.intersection(&Interval::new(4, 9)),
+            Some(Interval::new(4, 5))
+        );
+        assert_eq!(a.intersection(&Interval::new(6, 9)), None);
+        assert_eq!(a.union(&Interval::new(6, 9)), Some(Interval::new(1, 9)));
+        assert_eq!(a.union(&Interval::new(7, 9)), None);
+        assert_eq!(Interval::new(i64::MIN, i64::MAX - 1).length(), u64::MAX);
+        assert_eq!(a.iter().sum::<i64>(), 15);
+    }
+
+    #[test]
+    #[should_panic(expected = "interval 2..=1 is empty")]
+    fn test_empty_interval() {
+        Interval::new(2, 1);
+    }
+
+    proptest! {
+        #[test]
+        fn test_length_counts_values(a in interval()) {
+            prop_assert_eq!(a.length(), a.iter().count() as u64);
+        }
+
+        #[test]
+        fn test_intersection(a in interval(), b in interval(), x in -25i64..45) {
+            let both = a.contains(x) && b.contains(x);
+            match a.intersection(&b) {
+                Some(i) => prop_assert_eq!(i.contains(x), both),
+                None => prop_assert!(!both),
+            }
+            prop_assert_eq!(a.intersection(&b), b.intersection(&a));
+            prop_assert_eq!(a.overlaps(&b), a.intersection(&b).is_some());
+        }
+
+        #[test]
+        fn test_union(a in interval(), b in interval(), x in -25i64..45) {
+            let either = a.contains(x) || b.contains(x);
+            match a.union(&b) {
+                Some(u) => {
+                    prop_assert_eq!(u.contains(x), either);
+                    let shared = a.intersection(&b).map_or(0, |i| i.length());
+                    prop_assert_eq!(u.length(), a.length() + b.length() - shared);
+                }
+                // there's a value between the two intervals that's in neither
+                None => prop_assert!(a.end + 1 < b.start || b.end + 1 < a.start),
+            }
+            prop_assert_eq!(a.union(&b), b.union(&a));
+        }
+    }
+}
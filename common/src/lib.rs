@@ -0,0 +1,23 @@
+//! Helpers shared between the days.
+
+pub mod animation;
+pub mod answer;
+pub mod args;
+pub mod color;
+pub mod digits;
+pub mod direction;
+pub mod dsu;
+pub mod grid;
+pub mod inputs;
+pub mod interval;
+pub mod math;
+pub mod ocr;
+pub mod parse;
+pub mod permutations;
+pub mod physics;
+pub mod point;
+pub mod raster;
+pub mod rng;
+pub mod terminal;
+pub mod testing;
+pub mod timings;
@@ -0,0 +1,160 @@
+//! Number theory helpers. Everything works on i128 so that products of two values
+//! below ~10^18 (like day22's deck sizes) can't overflow.
+
+/// Returns the greatest common divisor of `a` and `b`, which is always
+/// non-negative.
+pub fn gcd(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        let r = a % b;
+        a = b;
+        b = r;
+    }
+    a
+}
+
+/// Returns the least common multiple of `a` and `b`, or 0 if either is 0.
+pub fn lcm(a: i128, b: i128) -> i128 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    (a / gcd(a, b) * b).abs()
+}
+
+/// Returns `(g, x, y)` such that `a * x + b * y == g == gcd(a, b)`.
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_s, mut s) = (1, 0);
+    let (mut old_t, mut t) = (0, 1);
+
+    while r != 0 {
+        let q = old_r / r;
+        let next_r = old_r - q * r;
+        old_r = r;
+        r = next_r;
+        let next_s = old_s - q * s;
+        old_s = s;
+        s = next_s;
+        let next_t = old_t - q * t;
+        old_t = t;
+        t = next_t;
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_s, -old_t)
+    } else {
+        (old_r, old_s, old_t)
+    }
+}
+
+/// Returns `base` to the power `exp` modulo `modulus`, in the range
+/// `0..modulus`. `modulus` must be positive.
+pub fn mod_pow(base: i128, mut exp: u128, modulus: i128) -> i128 {
+    assert!(modulus > 0, "modulus must be positive");
+
+    let mut result = 1 % modulus;
+    let mut base = base.rem_euclid(modulus);
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+
+    result
+}
+
+/// Returns the multiplicative inverse of `a` modulo `modulus` in the range
+/// `0..modulus`, or None if `a` and `modulus` aren't coprime.
+pub fn mod_inverse(a: i128, modulus: i128) -> Option<i128> {
+    let (g, x, _) = extended_gcd(a.rem_euclid(modulus), modulus);
+    if g == 1 {
+        Some(x.rem_euclid(modulus))
+    } else {
+        None
+    }
+}
+
+/// Solves the system `x ≡ residue (mod modulus)` for each `(residue, modulus)`
+/// pair with the Chinese remainder theorem. Returns the smallest non-negative
+/// solution along with the combined modulus, or None if the congruences
+/// contradict each other. The moduli don't need to be coprime.
+pub fn crt(congruences: &[(i128, i128)]) -> Option<(i128, i128)> {
+    let mut x: i128 = 0;
+    let mut m: i128 = 1;
+
+    for &(residue, modulus) in congruences {
+        let (g, p, _) = extended_gcd(m, modulus);
+        let diff = residue - x;
+        if diff % g != 0 {
+            return None;
+        }
+
+        let step = modulus / g;
+        let k = (diff / g % step * p % step).rem_euclid(step);
+        x += m * k;
+        m *= step;
+        x = x.rem_euclid(m);
+    }
+
+    Some((x, m))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd(48, 18), 6);
+        assert_eq!(gcd(-48, 18), 6);
+        assert_eq!(gcd(0, 7), 7);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(0, 6), 0);
+
+        // the day12 example 2 cycle lengths combine to 4686774924 steps
+        assert_eq!(lcm(lcm(2028, 5898), 4702), 4686774924);
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        for &(a, b) in [(240, 46), (-7, 3), (0, 5), (17, 0)].iter() {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g, gcd(a, b));
+            assert_eq!(a * x + b * y, g);
+        }
+    }
+
+    #[test]
+    fn test_mod_pow() {
+        assert_eq!(mod_pow(2, 10, 1000), 24);
+        assert_eq!(mod_pow(-2, 3, 5), 2);
+        assert_eq!(mod_pow(5, 0, 1), 0);
+        assert_eq!(mod_pow(3, 200, 1_000_000_007), 136_318_165);
+
+        // day22's deck size is prime, so Fermat's little theorem applies
+        let deck = 119_315_717_514_047;
+        assert_eq!(mod_pow(123_456_789_012, deck as u128 - 1, deck), 1);
+    }
+
+    #[test]
+    fn test_mod_inverse() {
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(6, 9), None);
+
+        let deck = 119_315_717_514_047;
+        let inverse = mod_inverse(2020, deck).unwrap();
+        assert_eq!(2020 * inverse % deck, 1);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(0, 4), (2, 6)]), Some((8, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt(&[]), Some((0, 1)));
+    }
+}
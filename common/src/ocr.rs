@@ -0,0 +1,93 @@
+//! Reading the capital letters that puzzles like day 8 draw in pixels.
+
+use crate::grid::Grid;
+use crate::point::Point2;
+
+/// Height of a letter in pixels.
+pub const LETTER_HEIGHT: usize = 6;
+/// Width of a letter in pixels, including the column of space after it.
+pub const LETTER_WIDTH: usize = 5;
+
+/// The letters that have been seen so far, `#` for lit pixels. All but Y are
+/// four pixels wide and followed by a blank column.
+const GLYPHS: [(char, [&str; LETTER_HEIGHT]); 18] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// Returns whether the letter starting at column `left` looks like `glyph`.
+fn matches(grid: &Grid<bool>, left: usize, glyph: &[&str; LETTER_HEIGHT]) -> bool {
+    (0..LETTER_HEIGHT).all(|y| {
+        let row = glyph[y].as_bytes();
+        (0..LETTER_WIDTH).all(|x| {
+            let lit = grid
+                .get(Point2::new((left + x) as i64, y as i64))
+                .copied()
+                .unwrap_or(false);
+            lit == (row.get(x) == Some(&b'#'))
+        })
+    })
+}
+
+/// Reads the letters drawn in the grid, one every `LETTER_WIDTH` columns from
+/// the left. Letters it doesn't recognize, including any in a grid that isn't
+/// `LETTER_HEIGHT` pixels high, come out as `?`.
+pub fn read(grid: &Grid<bool>) -> String {
+    (0..grid.width())
+        .step_by(LETTER_WIDTH)
+        .map(|left| {
+            if grid.height() != LETTER_HEIGHT {
+                return '?';
+            }
+
+            GLYPHS
+                .iter()
+                .find(|(_, glyph)| matches(grid, left, glyph))
+                .map_or('?', |(letter, _)| *letter)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(picture: &str) -> Grid<bool> {
+        Grid::parse(picture, |ch| Some(ch == '#')).unwrap()
+    }
+
+    #[test]
+    fn test_read() {
+        // Y runs straight into the next letter
+        let picture = "
+.##..#...##..#..##..#..#.
+#..#.#...##..#.#..#.#..#.
+#.....#.#.#..#.#..#.####.
+#......#..#..#.####.#..#.
+#..#...#..#..#.#..#.#..#.
+.##....#...##..#..#.#..#.";
+        assert_eq!(read(&parse(picture)), "CYUAH");
+
+        let mut smudged = parse(picture);
+        smudged[Point2::new(0, 0)] = true;
+        assert_eq!(read(&smudged), "?YUAH");
+
+        assert_eq!(read(&parse("####.\n")), "?");
+    }
+}
@@ -0,0 +1,158 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// A value in the input that couldn't be parsed, along with where it was found.
+/// Lines and columns are 1-based, columns count characters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: can't parse {:?}: {}",
+            self.line, self.column, self.text, self.message
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+fn parse_field<T>(text: &str, line: usize, column: usize) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    text.parse().map_err(|err: T::Err| ParseError {
+        line,
+        column,
+        text: text.to_string(),
+        message: err.to_string(),
+    })
+}
+
+/// Parses each non-blank line of the input as a `T`, ignoring surrounding
+/// whitespace.
+pub fn parse_lines<T>(input: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            let column = line.chars().take_while(|c| c.is_whitespace()).count() + 1;
+            parse_field(line.trim(), index + 1, column)
+        })
+        .collect()
+}
+
+/// Parses comma separated values, like an Intcode program, ignoring whitespace
+/// around each value. Values on multiple lines are all returned in order.
+pub fn parse_csv<T>(input: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    let mut values = Vec::new();
+
+    for (index, line) in input.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let mut column = 1;
+        for field in line.split(',') {
+            let leading = field.chars().count() - field.trim_start().chars().count();
+            values.push(parse_field(field.trim(), index + 1, column + leading)?);
+            column += field.chars().count() + 1;
+        }
+    }
+
+    Ok(values)
+}
+
+/// Splits the input into blocks separated by one or more blank lines, with
+/// surrounding whitespace trimmed from each block.
+pub fn split_blocks(input: &str) -> Vec<&str> {
+    let mut blocks = Vec::new();
+    let mut start = None;
+    let mut end = 0;
+    let mut offset = 0;
+
+    for line in input.split_inclusive('\n') {
+        if line.trim().is_empty() {
+            if let Some(block_start) = start.take() {
+                blocks.push(input[block_start..end].trim());
+            }
+        } else {
+            start.get_or_insert(offset);
+            end = offset + line.len();
+        }
+        offset += line.len();
+    }
+
+    if let Some(block_start) = start {
+        blocks.push(input[block_start..end].trim());
+    }
+
+    blocks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_lines() {
+        assert_eq!(
+            parse_lines::<i64>("12\n 14\n\n1969\n"),
+            Ok(vec![12, 14, 1969])
+        );
+
+        let err = parse_lines::<i64>("12\n  1x4\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.text, "1x4");
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 3: can't parse \"1x4\": invalid digit found in string"
+        );
+        // a non-breaking space is one character but two bytes
+        assert_eq!(parse_lines::<i64>("\u{a0}x").unwrap_err().column, 2);
+    }
+
+    #[test]
+    fn test_parse_csv() {
+        assert_eq!(
+            parse_csv::<i64>("1,9,10,3,\n2,3,11,0\n"),
+            Err(ParseError {
+                line: 1,
+                column: 10,
+                text: String::new(),
+                message: String::from("cannot parse integer from empty string"),
+            })
+        );
+        assert_eq!(
+            parse_csv::<i64>("1,9, 10,-3\n99\n"),
+            Ok(vec![1, 9, 10, -3, 99])
+        );
+
+        let err = parse_csv::<i64>("1,2, x").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 6));
+    }
+
+    #[test]
+    fn test_split_blocks() {
+        assert_eq!(split_blocks("a\nb\n\n\nc\n"), vec!["a\nb", "c"]);
+        assert_eq!(split_blocks("\n\na\r\n\r\nb"), vec!["a", "b"]);
+        assert!(split_blocks("\n \n").is_empty());
+    }
+}
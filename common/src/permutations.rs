@@ -0,0 +1,171 @@
+/// Iterator over every ordering of a set of items, generated one at a time with
+/// Heap's algorithm so only the current ordering is kept in memory.
+pub struct Permutations<T> {
+    items: Vec<T>,
+    counters: Vec<usize>,
+    index: usize,
+    started: bool,
+}
+
+/// Returns an iterator over every permutation of `items`.
+pub fn permutations<T: Clone>(items: &[T]) -> Permutations<T> {
+    Permutations {
+        items: items.to_vec(),
+        counters: vec![0; items.len()],
+        index: 1,
+        started: false,
+    }
+}
+
+impl<T: Clone> Iterator for Permutations<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        if !self.started {
+            self.started = true;
+            return Some(self.items.clone());
+        }
+
+        while self.index < self.items.len() {
+            let i = self.index;
+
+            if self.counters[i] < i {
+                if i.is_multiple_of(2) {
+                    self.items.swap(0, i);
+                } else {
+                    self.items.swap(self.counters[i], i);
+                }
+
+                self.counters[i] += 1;
+                self.index = 1;
+                return Some(self.items.clone());
+            }
+
+            self.counters[i] = 0;
+            self.index += 1;
+        }
+
+        None
+    }
+}
+
+/// Iterator over every way of choosing `k` items in order from a set, i.e. every
+/// permutation of every `k`-sized subset.
+pub struct KPermutations<T> {
+    items: Vec<T>,
+    // indices of the subset currently being permuted, in increasing order
+    subset: Vec<usize>,
+    current: Option<Permutations<T>>,
+}
+
+/// Returns an iterator over every ordered selection of `k` items from `items`.
+/// Yields nothing if `k` is larger than the number of items.
+pub fn k_permutations<T: Clone>(items: &[T], k: usize) -> KPermutations<T> {
+    let mut iter = KPermutations {
+        items: items.to_vec(),
+        subset: (0..k).collect(),
+        current: None,
+    };
+
+    if k <= items.len() {
+        iter.current = Some(permutations(&iter.selected()));
+    }
+
+    iter
+}
+
+impl<T: Clone> KPermutations<T> {
+    fn selected(&self) -> Vec<T> {
+        self.subset.iter().map(|&i| self.items[i].clone()).collect()
+    }
+
+    /// Advances to the next subset in lexicographic order of indices, returning
+    /// false once every subset has been visited.
+    fn next_subset(&mut self) -> bool {
+        let n = self.items.len();
+        let k = self.subset.len();
+
+        let mut i = k;
+        while i > 0 {
+            i -= 1;
+            if self.subset[i] < n - k + i {
+                self.subset[i] += 1;
+                for j in i + 1..k {
+                    self.subset[j] = self.subset[j - 1] + 1;
+                }
+                return true;
+            }
+        }
+
+        false
+    }
+}
+
+impl<T: Clone> Iterator for KPermutations<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        loop {
+            let current = self.current.as_mut()?;
+
+            if let Some(permutation) = current.next() {
+                return Some(permutation);
+            }
+
+            if self.next_subset() {
+                self.current = Some(permutations(&self.selected()));
+            } else {
+                self.current = None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn factorial(n: usize) -> usize {
+        (1..=n).product()
+    }
+
+    #[test]
+    fn test_permutations() {
+        for n in 0..7 {
+            let items: Vec<usize> = (0..n).collect();
+            let all: Vec<Vec<usize>> = permutations(&items).collect();
+            let unique: HashSet<Vec<usize>> = all.iter().cloned().collect();
+
+            assert_eq!(all.len(), factorial(n));
+            assert_eq!(unique.len(), all.len());
+            assert!(all.iter().all(|p| {
+                let mut sorted = p.clone();
+                sorted.sort();
+                sorted == items
+            }));
+        }
+    }
+
+    #[test]
+    fn test_permutations_order() {
+        let all: Vec<Vec<char>> = permutations(&['a', 'b', 'c']).collect();
+        assert_eq!(all[0], vec!['a', 'b', 'c']);
+        assert_eq!(all[1], vec!['b', 'a', 'c']);
+    }
+
+    #[test]
+    fn test_k_permutations() {
+        let items = [1, 2, 3, 4, 5];
+        for k in 0..=5 {
+            let all: Vec<Vec<i32>> = k_permutations(&items, k).collect();
+            let unique: HashSet<Vec<i32>> = all.iter().cloned().collect();
+
+            assert_eq!(all.len(), factorial(5) / factorial(5 - k));
+            assert_eq!(unique.len(), all.len());
+            assert!(all.iter().all(|p| p.len() == k));
+        }
+
+        assert_eq!(k_permutations(&items, 6).count(), 0);
+    }
+}
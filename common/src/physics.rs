@@ -0,0 +1,104 @@
+//! Helpers for simulating bodies that pull on each other one unit at a time,
+//! like day12's moons.
+
+use crate::point::Point3;
+
+/// A body's position and velocity.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Body {
+    pub position: Point3,
+    pub velocity: Point3,
+}
+
+impl Body {
+    /// Returns a body at rest at `position`.
+    pub fn at(position: Point3) -> Self {
+        Body {
+            position,
+            velocity: Point3::ORIGIN,
+        }
+    }
+
+    /// Returns the body's potential energy times its kinetic energy.
+    pub fn energy(&self) -> i64 {
+        self.position.manhattan(Point3::ORIGIN) * self.velocity.manhattan(Point3::ORIGIN)
+    }
+}
+
+/// Changes every pair of bodies' velocities by one towards each other on each
+/// axis where they differ.
+pub fn attract(bodies: &mut [Body]) {
+    for i in 0..bodies.len() {
+        for j in i + 1..bodies.len() {
+            let pull = (bodies[j].position - bodies[i].position).signum();
+            bodies[i].velocity += pull;
+            bodies[j].velocity -= pull;
+        }
+    }
+}
+
+/// Moves every body by its velocity.
+pub fn integrate(bodies: &mut [Body]) {
+    for body in bodies {
+        body.position += body.velocity;
+    }
+}
+
+/// Advances the simulation by one time step.
+pub fn step(bodies: &mut [Body]) {
+    attract(bodies);
+    integrate(bodies);
+}
+
+/// Returns the total energy of all the bodies.
+pub fn total_energy(bodies: &[Body]) -> i64 {
+    bodies.iter().map(Body::energy).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bodies(positions: &[(i64, i64, i64)]) -> Vec<Body> {
+        positions
+            .iter()
+            .map(|&position| Body::at(position.into()))
+            .collect()
+    }
+
+    #[test]
+    fn test_step() {
+        let mut moons = bodies(&[(-1, 0, 2), (2, -10, -7), (4, -8, 8), (3, 5, -1)]);
+        step(&mut moons);
+        assert_eq!(
+            moons[0],
+            Body {
+                position: Point3::new(2, -1, 1),
+                velocity: Point3::new(3, -1, -1),
+            }
+        );
+        assert_eq!(
+            moons[3],
+            Body {
+                position: Point3::new(2, 2, 0),
+                velocity: Point3::new(-1, -3, 1),
+            }
+        );
+    }
+
+    #[test]
+    fn test_total_energy() {
+        let mut moons = bodies(&[(-1, 0, 2), (2, -10, -7), (4, -8, 8), (3, 5, -1)]);
+        for _ in 0..10 {
+            step(&mut moons);
+        }
+        assert_eq!(moons[0].energy(), 6 * 6);
+        assert_eq!(total_energy(&moons), 179);
+
+        let mut moons = bodies(&[(-8, -10, 0), (5, 5, 10), (2, -7, 3), (9, -8, -3)]);
+        for _ in 0..100 {
+            step(&mut moons);
+        }
+        assert_eq!(total_energy(&moons), 1940);
+    }
+}
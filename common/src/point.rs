@@ -0,0 +1,259 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A point (or vector) on a 2D grid. Rotations treat y as pointing up, like the
+/// wires in day3; on screen-style grids where y points down they turn the other way.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point2 {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point2 {
+    pub const ORIGIN: Point2 = Point2 { x: 0, y: 0 };
+
+    pub fn new(x: i64, y: i64) -> Self {
+        Point2 { x, y }
+    }
+
+    /// Returns the taxicab distance between the two points.
+    pub fn manhattan(&self, other: Point2) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// Returns the four orthogonally adjacent points.
+    pub fn neighbors4(self) -> impl Iterator<Item = Point2> {
+        [(0, 1), (1, 0), (0, -1), (-1, 0)]
+            .iter()
+            .map(move |&(dx, dy)| self + Point2::new(dx, dy))
+    }
+
+    /// Returns the eight orthogonally and diagonally adjacent points.
+    pub fn neighbors8(self) -> impl Iterator<Item = Point2> {
+        [
+            (0, 1),
+            (1, 1),
+            (1, 0),
+            (1, -1),
+            (0, -1),
+            (-1, -1),
+            (-1, 0),
+            (-1, 1),
+        ]
+        .iter()
+        .map(move |&(dx, dy)| self + Point2::new(dx, dy))
+    }
+
+    /// Rotates the point a quarter turn counter-clockwise around the origin.
+    pub fn rotate_left(self) -> Self {
+        Point2::new(-self.y, self.x)
+    }
+
+    /// Rotates the point a quarter turn clockwise around the origin.
+    pub fn rotate_right(self) -> Self {
+        Point2::new(self.y, -self.x)
+    }
+
+    /// Rotates the point a quarter turn counter-clockwise around `center`.
+    pub fn rotate_left_around(self, center: Point2) -> Self {
+        (self - center).rotate_left() + center
+    }
+
+    /// Rotates the point a quarter turn clockwise around `center`.
+    pub fn rotate_right_around(self, center: Point2) -> Self {
+        (self - center).rotate_right() + center
+    }
+}
+
+impl From<(i64, i64)> for Point2 {
+    fn from((x, y): (i64, i64)) -> Self {
+        Point2 { x, y }
+    }
+}
+
+impl Add for Point2 {
+    type Output = Point2;
+
+    fn add(self, other: Point2) -> Point2 {
+        Point2::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Point2 {
+    fn add_assign(&mut self, other: Point2) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Point2 {
+    type Output = Point2;
+
+    fn sub(self, other: Point2) -> Point2 {
+        Point2::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl SubAssign for Point2 {
+    fn sub_assign(&mut self, other: Point2) {
+        *self = *self - other;
+    }
+}
+
+impl Mul<i64> for Point2 {
+    type Output = Point2;
+
+    fn mul(self, scale: i64) -> Point2 {
+        Point2::new(self.x * scale, self.y * scale)
+    }
+}
+
+impl Neg for Point2 {
+    type Output = Point2;
+
+    fn neg(self) -> Point2 {
+        Point2::new(-self.x, -self.y)
+    }
+}
+
+/// A point (or vector) in 3D space.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Point3 {
+    pub const ORIGIN: Point3 = Point3 { x: 0, y: 0, z: 0 };
+
+    pub fn new(x: i64, y: i64, z: i64) -> Self {
+        Point3 { x, y, z }
+    }
+
+    /// Returns the taxicab distance between the two points.
+    pub fn manhattan(&self, other: Point3) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+
+    /// Returns the six orthogonally adjacent points.
+    pub fn neighbors6(self) -> impl Iterator<Item = Point3> {
+        [
+            (1, 0, 0),
+            (-1, 0, 0),
+            (0, 1, 0),
+            (0, -1, 0),
+            (0, 0, 1),
+            (0, 0, -1),
+        ]
+        .iter()
+        .map(move |&(dx, dy, dz)| self + Point3::new(dx, dy, dz))
+    }
+
+    /// Returns the component-wise sign of the point.
+    pub fn signum(self) -> Self {
+        Point3::new(self.x.signum(), self.y.signum(), self.z.signum())
+    }
+}
+
+impl From<(i64, i64, i64)> for Point3 {
+    fn from((x, y, z): (i64, i64, i64)) -> Self {
+        Point3 { x, y, z }
+    }
+}
+
+impl Add for Point3 {
+    type Output = Point3;
+
+    fn add(self, other: Point3) -> Point3 {
+        Point3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl AddAssign for Point3 {
+    fn add_assign(&mut self, other: Point3) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Point3 {
+    type Output = Point3;
+
+    fn sub(self, other: Point3) -> Point3 {
+        Point3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl SubAssign for Point3 {
+    fn sub_assign(&mut self, other: Point3) {
+        *self = *self - other;
+    }
+}
+
+impl Mul<i64> for Point3 {
+    type Output = Point3;
+
+    fn mul(self, scale: i64) -> Point3 {
+        Point3::new(self.x * scale, self.y * scale, self.z * scale)
+    }
+}
+
+impl Neg for Point3 {
+    type Output = Point3;
+
+    fn neg(self) -> Point3 {
+        Point3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic() {
+        let mut p = Point2::new(3, -4);
+        p += Point2::new(1, 1) * 2;
+        assert_eq!(p, Point2::new(5, -2));
+        assert_eq!(-p - Point2::new(1, 1), Point2::new(-6, 1));
+
+        let q = Point3::new(1, 2, 3) + Point3::new(-2, 0, 5);
+        assert_eq!(q, Point3::new(-1, 2, 8));
+        assert_eq!(q.signum(), Point3::new(-1, 1, 1));
+    }
+
+    #[test]
+    fn test_manhattan() {
+        assert_eq!(Point2::new(3, 3).manhattan(Point2::ORIGIN), 6);
+        assert_eq!(Point2::new(-2, 5).manhattan(Point2::new(1, 1)), 7);
+        assert_eq!(Point3::new(1, -2, 3).manhattan(Point3::ORIGIN), 6);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let p = Point2::new(1, 1);
+        assert_eq!(p.neighbors4().count(), 4);
+        assert!(p.neighbors4().all(|n| n.manhattan(p) == 1));
+        assert_eq!(p.neighbors8().count(), 8);
+        assert!(p
+            .neighbors8()
+            .all(|n| n != p && (n - p).x.abs() <= 1 && (n - p).y.abs() <= 1));
+        assert!(Point3::ORIGIN
+            .neighbors6()
+            .all(|n| n.manhattan(Point3::ORIGIN) == 1));
+    }
+
+    #[test]
+    fn test_rotation() {
+        let up = Point2::new(0, 1);
+        assert_eq!(up.rotate_left(), Point2::new(-1, 0));
+        assert_eq!(up.rotate_right(), Point2::new(1, 0));
+        assert_eq!(up.rotate_left().rotate_right(), up);
+        assert_eq!(
+            Point2::new(3, 2).rotate_right_around(Point2::new(2, 2)),
+            Point2::new(2, 1)
+        );
+        assert_eq!(
+            Point2::new(3, 2).rotate_left_around(Point2::new(2, 2)),
+            Point2::new(2, 3)
+        );
+    }
+}
@@ -0,0 +1,72 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use crate::color::Color;
+use crate::grid::Grid;
+
+/// Encodes the grid as an RGB PNG, drawing each cell as a `scale` x `scale` block
+/// of pixels so tiny images like day8's 25x6 message are actually visible.
+pub fn encode_png<W: Write>(grid: &Grid<Color>, scale: usize, writer: W) -> io::Result<()> {
+    assert!(scale > 0, "scale must be positive");
+
+    let width = grid.width() * scale;
+    let height = grid.height() * scale;
+
+    let mut data = Vec::with_capacity(width * height * 3);
+    for row in grid.rows() {
+        let mut line = Vec::with_capacity(width * 3);
+        for color in row {
+            for _ in 0..scale {
+                line.extend_from_slice(&[color.r, color.g, color.b]);
+            }
+        }
+        for _ in 0..scale {
+            data.extend_from_slice(&line);
+        }
+    }
+
+    let mut encoder = png::Encoder::new(writer, width as u32, height as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&data)?;
+    writer.finish()?;
+
+    Ok(())
+}
+
+/// Writes the grid to a PNG file at `path`. See `encode_png`.
+pub fn write_png<P: AsRef<Path>>(grid: &Grid<Color>, scale: usize, path: P) -> io::Result<()> {
+    let file = File::create(path)?;
+    encode_png(grid, scale, BufWriter::new(file))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::point::Point2;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_encode_png() {
+        let mut grid = Grid::new(2, 1, Color::BLACK);
+        grid[Point2::new(1, 0)] = Color::rgb(10, 20, 30);
+
+        let mut encoded = Vec::new();
+        encode_png(&grid, 2, &mut encoded).unwrap();
+
+        let decoder = png::Decoder::new(Cursor::new(encoded));
+        let mut reader = decoder.read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size().unwrap()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+
+        assert_eq!((info.width, info.height), (4, 2));
+        assert_eq!(info.color_type, png::ColorType::Rgb);
+
+        let row = [0, 0, 0, 0, 0, 0, 10, 20, 30, 10, 20, 30];
+        assert_eq!(&pixels[..12], &row);
+        assert_eq!(&pixels[12..24], &row);
+    }
+}
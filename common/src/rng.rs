@@ -0,0 +1,132 @@
+//! A small seeded random number generator, so searches that use randomness can
+//! be rerun exactly by reusing the seed they report.
+
+use std::env;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Environment variable that fixes the seed used by `Rng::from_env`.
+pub const SEED_ENV: &str = "AOC_SEED";
+
+/// SplitMix64 generator. Not cryptographically secure, but fast and good enough
+/// for shuffling and random restarts.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    seed: u64,
+    state: u64,
+}
+
+impl Rng {
+    pub fn seeded(seed: u64) -> Self {
+        Rng { seed, state: seed }
+    }
+
+    /// Uses the seed in `AOC_SEED` if it's set, otherwise picks one from the
+    /// clock. Solvers should print `seed()` so a failing run can be repeated.
+    pub fn from_env() -> Self {
+        let seed = match env::var(SEED_ENV) {
+            Ok(seed) => seed
+                .parse()
+                .unwrap_or_else(|_| panic!("{} must be a u64, got {:?}", SEED_ENV, seed)),
+            Err(_) => SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|time| time.as_nanos() as u64)
+                .unwrap_or(0),
+        };
+
+        Rng::seeded(seed)
+    }
+
+    /// Returns the seed the generator started from.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a value in `0..n`, without modulo bias. Panics if `n` is 0.
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "can't pick a value below 0");
+
+        // reject the top partial copy of 0..n so every value is equally likely
+        let zone = u64::MAX - u64::MAX % n;
+        loop {
+            let value = self.next_u64();
+            if value < zone {
+                return value % n;
+            }
+        }
+    }
+
+    /// Returns true with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+
+    /// Returns a random element of `items`, or None if it's empty.
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+        if items.is_empty() {
+            None
+        } else {
+            Some(&items[self.below(items.len() as u64) as usize])
+        }
+    }
+
+    /// Shuffles `items` in place with a Fisher-Yates shuffle.
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i as u64 + 1) as usize;
+            items.swap(i, j);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deterministic() {
+        let mut a = Rng::seeded(42);
+        let mut b = Rng::seeded(42);
+        let first: Vec<u64> = (0..10).map(|_| a.next_u64()).collect();
+        assert_eq!(first, (0..10).map(|_| b.next_u64()).collect::<Vec<_>>());
+        assert_ne!(Rng::seeded(43).next_u64(), first[0]);
+        assert_eq!(a.seed(), 42);
+
+        // reference output of SplitMix64 seeded with 0
+        assert_eq!(Rng::seeded(0).next_u64(), 0xe220_a839_7b1d_cdaf);
+    }
+
+    #[test]
+    fn test_below() {
+        let mut rng = Rng::seeded(7);
+        let mut counts = [0; 6];
+        for _ in 0..6000 {
+            counts[rng.below(6) as usize] += 1;
+        }
+
+        assert!(counts.iter().all(|&count| count > 800 && count < 1200));
+        assert_eq!(rng.below(1), 0);
+    }
+
+    #[test]
+    fn test_shuffle_and_choose() {
+        let mut rng = Rng::seeded(1);
+        let mut items: Vec<u32> = (0..50).collect();
+        rng.shuffle(&mut items);
+
+        assert_ne!(items, (0..50).collect::<Vec<_>>());
+        items.sort_unstable();
+        assert_eq!(items, (0..50).collect::<Vec<_>>());
+
+        assert!(items.contains(rng.choose(&items).unwrap()));
+        assert_eq!(rng.choose::<u32>(&[]), None);
+    }
+}
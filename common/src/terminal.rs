@@ -0,0 +1,199 @@
+use std::io::{self, Write};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crossterm::{cursor, queue, style, terminal};
+
+use crate::color::Color;
+use crate::grid::Grid;
+use crate::point::Point2;
+
+/// A character drawn in a color.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Cell {
+    pub ch: char,
+    pub color: Color,
+}
+
+impl Cell {
+    pub fn new(ch: char, color: Color) -> Self {
+        Cell { ch, color }
+    }
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Cell::new(' ', Color::WHITE)
+    }
+}
+
+/// Returns the points whose cells differ between the two frames, or None if the
+/// frames have different dimensions and need a full redraw.
+pub fn changed_cells(previous: &Grid<Cell>, next: &Grid<Cell>) -> Option<Vec<Point2>> {
+    if previous.width() != next.width() || previous.height() != next.height() {
+        return None;
+    }
+
+    Some(
+        next.iter()
+            .filter(|(point, cell)| previous[*point] != **cell)
+            .map(|(point, _)| point)
+            .collect(),
+    )
+}
+
+/// Draws grids of cells to a terminal without flicker: each frame only rewrites the
+/// cells that changed since the previous one, and frames are spaced out to stay
+/// under a maximum frame rate.
+pub struct Renderer<W: Write> {
+    out: W,
+    previous: Option<Grid<Cell>>,
+    interval: Option<Duration>,
+    last_frame: Option<Instant>,
+}
+
+impl Renderer<io::Stdout> {
+    /// Creates a renderer drawing to stdout.
+    pub fn stdout(max_fps: u32) -> Self {
+        Renderer::new(io::stdout(), max_fps)
+    }
+}
+
+impl<W: Write> Renderer<W> {
+    /// Creates a renderer drawing to `out` at no more than `max_fps` frames per
+    /// second, or as fast as possible if it's 0.
+    pub fn new(out: W, max_fps: u32) -> Self {
+        let interval = if max_fps == 0 {
+            None
+        } else {
+            Some(Duration::from_secs(1) / max_fps)
+        };
+
+        Renderer {
+            out,
+            previous: None,
+            interval,
+            last_frame: None,
+        }
+    }
+
+    /// Switches to the alternate screen and hides the cursor.
+    pub fn begin(&mut self) -> io::Result<()> {
+        queue!(self.out, terminal::EnterAlternateScreen, cursor::Hide)?;
+        self.previous = None;
+        self.out.flush()
+    }
+
+    /// Restores the screen and cursor hidden by `begin`.
+    pub fn end(&mut self) -> io::Result<()> {
+        queue!(
+            self.out,
+            style::ResetColor,
+            cursor::Show,
+            terminal::LeaveAlternateScreen
+        )?;
+        self.out.flush()
+    }
+
+    /// Draws a frame, waiting first if the previous frame was drawn too recently.
+    pub fn draw(&mut self, frame: &Grid<Cell>) -> io::Result<()> {
+        if let (Some(interval), Some(last_frame)) = (self.interval, self.last_frame) {
+            let elapsed = last_frame.elapsed();
+            if elapsed < interval {
+                thread::sleep(interval - elapsed);
+            }
+        }
+        self.last_frame = Some(Instant::now());
+
+        let changed = match self.previous {
+            Some(ref previous) => changed_cells(previous, frame),
+            None => None,
+        };
+
+        let points = match changed {
+            Some(points) => points,
+            None => {
+                queue!(self.out, terminal::Clear(terminal::ClearType::All))?;
+                frame.points().collect()
+            }
+        };
+
+        let mut color = None;
+        for point in points {
+            let cell = frame[point];
+            queue!(self.out, cursor::MoveTo(point.x as u16, point.y as u16))?;
+
+            if color != Some(cell.color) {
+                let Color { r, g, b } = cell.color;
+                queue!(
+                    self.out,
+                    style::SetForegroundColor(style::Color::Rgb { r, g, b })
+                )?;
+                color = Some(cell.color);
+            }
+
+            queue!(self.out, style::Print(cell.ch))?;
+        }
+
+        self.out.flush()?;
+        self.previous = Some(frame.clone());
+
+        Ok(())
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(text: &str) -> Grid<Cell> {
+        Grid::parse(text, |ch| Some(Cell::new(ch, Color::WHITE))).unwrap()
+    }
+
+    #[test]
+    fn test_changed_cells() {
+        assert_eq!(
+            changed_cells(&frame("ab\ncd"), &frame("ab\nxd")),
+            Some(vec![Point2::new(0, 1)])
+        );
+        assert_eq!(changed_cells(&frame("ab"), &frame("ab")), Some(vec![]));
+        assert_eq!(changed_cells(&frame("ab"), &frame("abc")), None);
+    }
+
+    #[test]
+    fn test_only_changes_are_redrawn() {
+        let mut renderer = Renderer::new(Vec::new(), 0);
+
+        renderer.draw(&frame("ab\ncd")).unwrap();
+        let full = renderer.out.len();
+        assert!(String::from_utf8_lossy(&renderer.out).contains('d'));
+
+        renderer.out.clear();
+        renderer.draw(&frame("ab\ncd")).unwrap();
+        assert!(renderer.out.is_empty());
+
+        renderer.draw(&frame("ab\ncz")).unwrap();
+        let partial = String::from_utf8(renderer.into_inner()).unwrap();
+        assert!(partial.contains('z'));
+        assert!(!partial.contains('a'));
+        assert!(partial.len() < full);
+    }
+
+    #[test]
+    fn test_frame_rate_cap() {
+        let mut renderer = Renderer::new(Vec::new(), 50);
+
+        let start = Instant::now();
+        for _ in 0..3 {
+            renderer.draw(&frame("a")).unwrap();
+        }
+
+        // the first frame is immediate, the other two wait 20ms each
+        assert!(start.elapsed() >= Duration::from_millis(40));
+    }
+}
@@ -0,0 +1,141 @@
+//! Helpers for the days' end-to-end tests, which run the built binaries.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::inputs::INPUTS_ENV;
+use crate::timings::{self, TIMINGS_ENV};
+
+/// Environment variable holding a factor every time budget is multiplied by, for
+/// running the budgets on slower machines or in debug builds.
+pub const BUDGET_SCALE_ENV: &str = "AOC_BUDGET_SCALE";
+
+static NEXT_DIR: AtomicUsize = AtomicUsize::new(0);
+
+/// Creates an empty directory under the system temp directory that no other test
+/// is using.
+pub fn scratch_dir() -> PathBuf {
+    let dir = env::temp_dir().join(format!(
+        "aoc-{}-{}",
+        std::process::id(),
+        NEXT_DIR.fetch_add(1, Ordering::SeqCst)
+    ));
+
+    if dir.exists() {
+        fs::remove_dir_all(&dir).unwrap();
+    }
+    fs::create_dir_all(&dir).unwrap();
+
+    dir
+}
+
+/// Runs the binary at `exe` with `args` from a fresh directory that it reads
+/// personal inputs from, writing `input` there as the input for `day` first
+/// unless it's None.
+pub fn run_with_input(exe: &str, day: u32, input: Option<&str>, args: &[&str]) -> Output {
+    let dir = scratch_dir();
+    if let Some(input) = input {
+        fs::write(dir.join(format!("day{}.txt", day)), input).unwrap();
+    }
+
+    let output = Command::new(exe)
+        .args(args)
+        .current_dir(&dir)
+        .env(INPUTS_ENV, &dir)
+        .output()
+        .unwrap();
+
+    fs::remove_dir_all(&dir).unwrap();
+
+    output
+}
+
+/// Returns the output's stdout, panicking with its stderr if the binary failed.
+pub fn stdout(output: &Output) -> String {
+    assert!(
+        output.status.success(),
+        "binary exited with {}: {}",
+        output.status,
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+/// Returns the output's stderr, panicking if the binary succeeded.
+pub fn stderr(output: &Output) -> String {
+    assert!(!output.status.success(), "binary unexpectedly succeeded");
+
+    String::from_utf8(output.stderr.clone()).unwrap()
+}
+
+/// Runs `f` and panics if it takes longer than `budget`, scaled by
+/// `AOC_BUDGET_SCALE` if it's set.
+pub fn assert_within<T>(budget: Duration, f: impl FnOnce() -> T) -> T {
+    timed_within(budget, f).0
+}
+
+/// Like `assert_within` for part `part` of `day`, also appending how long it took
+/// to the CSV file named by `AOC_TIMINGS` if it's set.
+pub fn assert_part_within<T>(day: u32, part: u32, budget: Duration, f: impl FnOnce() -> T) -> T {
+    let (result, elapsed) = timed_within(budget, f);
+
+    if let Some(path) = env::var_os(TIMINGS_ENV) {
+        let path = Path::new(&path);
+        timings::append(path, day, part, elapsed)
+            .unwrap_or_else(|err| panic!("can't append to {}: {}", path.display(), err));
+    }
+
+    result
+}
+
+fn timed_within<T>(budget: Duration, f: impl FnOnce() -> T) -> (T, Duration) {
+    let scale = match env::var(BUDGET_SCALE_ENV) {
+        Ok(scale) => scale
+            .parse::<f64>()
+            .unwrap_or_else(|_| panic!("{} must be a number, got {:?}", BUDGET_SCALE_ENV, scale)),
+        Err(_) => 1.0,
+    };
+    let budget = budget.mul_f64(scale);
+
+    let start = Instant::now();
+    let result = f();
+    let elapsed = start.elapsed();
+
+    assert!(
+        elapsed <= budget,
+        "took {:?}, budget is {:?}",
+        elapsed,
+        budget
+    );
+
+    (result, elapsed)
+}
+
+/// Defines a day's two budget tests, each checking a part solves the day's
+/// personal input within its budget in milliseconds. They're ignored, so run
+/// them with `cargo test --release -- --ignored`.
+#[macro_export]
+macro_rules! budget_tests {
+    ($day:expr, $part1:path => $budget1:expr, $part2:path => $budget2:expr $(,)?) => {
+        #[test]
+        #[ignore]
+        fn test_part1_budget() {
+            let input = $crate::require_input!($day);
+            let budget = ::std::time::Duration::from_millis($budget1);
+            $crate::testing::assert_part_within($day, 1, budget, || $part1(&input));
+        }
+
+        #[test]
+        #[ignore]
+        fn test_part2_budget() {
+            let input = $crate::require_input!($day);
+            let budget = ::std::time::Duration::from_millis($budget2);
+            $crate::testing::assert_part_within($day, 2, budget, || $part2(&input));
+        }
+    };
+}
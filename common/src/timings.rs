@@ -0,0 +1,129 @@
+//! Keeping a CSV log of how long each part takes, to chart performance across
+//! the season and across optimization work.
+
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::Command;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Environment variable naming the CSV file that timed runs append to.
+pub const TIMINGS_ENV: &str = "AOC_TIMINGS";
+
+/// The first line of a new timings file.
+pub const HEADER: &str = "date,commit,day,part,micros";
+
+// held while appending, so parts timed in parallel don't both write the header
+static APPENDING: Mutex<()> = Mutex::new(());
+
+/// Returns the UTC date `secs` seconds after the Unix epoch as `YYYY-MM-DD`.
+fn date(secs: u64) -> String {
+    // Howard Hinnant's civil_from_days
+    let days = (secs / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + (month <= 2) as i64;
+
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// Returns the UTC date and time `secs` seconds after the Unix epoch as
+/// `YYYY-MM-DD HH:MM:SS`.
+pub fn datetime(secs: u64) -> String {
+    let time = secs % 86_400;
+    format!(
+        "{} {:02}:{:02}:{:02}",
+        date(secs),
+        time / 3600,
+        time / 60 % 60,
+        time % 60
+    )
+}
+
+/// Returns today's UTC date as `YYYY-MM-DD`.
+pub fn today() -> String {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    date(now.as_secs())
+}
+
+/// Returns the short hash of the commit the repository is at, or `unknown`
+/// if git can't say.
+pub fn commit() -> String {
+    Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|hash| hash.trim().to_string())
+        .unwrap_or_else(|| String::from("unknown"))
+}
+
+/// Appends a row for part `part` of `day` to the CSV file at `path`, starting it
+/// with `HEADER` if it's new.
+pub fn append(path: &Path, day: u32, part: u32, elapsed: Duration) -> io::Result<()> {
+    let _appending = APPENDING.lock().unwrap_or_else(|err| err.into_inner());
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+
+    let mut row = String::new();
+    if file.metadata()?.len() == 0 {
+        row += HEADER;
+        row += "\n";
+    }
+    row += &format!(
+        "{},{},{},{},{}\n",
+        today(),
+        commit(),
+        day,
+        part,
+        elapsed.as_micros()
+    );
+
+    file.write_all(row.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::scratch_dir;
+    use std::fs;
+
+    #[test]
+    fn test_date() {
+        assert_eq!(date(0), "1970-01-01");
+        assert_eq!(date(951_782_400), "2000-02-29");
+        assert_eq!(date(1_575_158_400), "2019-12-01");
+        assert_eq!(date(1_577_836_799), "2019-12-31");
+        assert_eq!(datetime(1_575_176_653), "2019-12-01 05:04:13");
+    }
+
+    #[test]
+    fn test_append() {
+        let path = scratch_dir().join("timings.csv");
+        append(&path, 3, 1, Duration::from_micros(1500)).unwrap();
+        append(&path, 3, 2, Duration::from_millis(2)).unwrap();
+
+        let csv = fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], HEADER);
+        assert!(lines[1].ends_with(",3,1,1500"), "{}", lines[1]);
+        assert!(lines[2].ends_with(",3,2,2000"), "{}", lines[2]);
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
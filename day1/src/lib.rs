@@ -0,0 +1,156 @@
+//! Day 1: The Tyranny of the Rocket Equation.
+
+use common::parse::parse_lines;
+
+/// Parses the modules' masses, one per line.
+pub fn masses(input: &str) -> Vec<i64> {
+    parse_lines(input).unwrap_or_else(|err| panic!("{}", err))
+}
+
+fn fuel(mass: i64) -> i64 {
+    mass / 3 - 2
+}
+
+/// Returns the fuel needed to launch a module of the given mass, including the
+/// fuel needed to carry that fuel.
+pub fn fuel_requirement(mass: i64) -> i64 {
+    let mut total_fuel = 0;
+
+    let mut cur_mass = mass;
+    loop {
+        cur_mass = fuel(cur_mass);
+        if cur_mass <= 0 {
+            break;
+        }
+
+        total_fuel += cur_mass;
+    }
+
+    total_fuel
+}
+
+/// A module from the input and the fuel it needs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Module {
+    /// The line of the input the module's mass is on, from 1.
+    pub line: usize,
+    pub mass: i64,
+    /// The fuel for the module alone, as in part 1.
+    pub fuel: i64,
+    /// The fuel for the module and its fuel, as in part 2.
+    pub total_fuel: i64,
+}
+
+/// Returns every module in the input with the fuel it needs.
+pub fn breakdown(input: &str) -> Vec<Module> {
+    let lines = input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty());
+
+    lines
+        .zip(masses(input))
+        .map(|((index, _), mass)| Module {
+            line: index + 1,
+            mass,
+            fuel: fuel(mass),
+            total_fuel: fuel_requirement(mass),
+        })
+        .collect()
+}
+
+/// Writes the modules as CSV with a header row.
+pub fn breakdown_csv(modules: &[Module]) -> String {
+    let mut csv = String::from("line,mass,fuel,total_fuel\n");
+    for module in modules {
+        csv += &format!(
+            "{},{},{},{}\n",
+            module.line, module.mass, module.fuel, module.total_fuel
+        );
+    }
+    csv
+}
+
+/// Writes the modules as JSON, one object per line.
+pub fn breakdown_json(modules: &[Module]) -> String {
+    let mut json = String::new();
+    for module in modules {
+        json += &format!(
+            "{{\"line\":{},\"mass\":{},\"fuel\":{},\"total_fuel\":{}}}\n",
+            module.line, module.mass, module.fuel, module.total_fuel
+        );
+    }
+    json
+}
+
+/// Returns the fuel needed for the modules alone.
+pub fn part1(masses: &[i64]) -> i64 {
+    masses.iter().copied().map(fuel).sum()
+}
+
+/// Returns the fuel needed for the modules and their fuel.
+pub fn part2(masses: &[i64]) -> i64 {
+    masses.iter().copied().map(fuel_requirement).sum()
+}
+
+pub fn solve_part1(input: &str) -> i64 {
+    part1(&masses(input))
+}
+
+pub fn solve_part2(input: &str) -> i64 {
+    part2(&masses(input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::inputs::example;
+
+    #[test]
+    fn test_module_fuel() {
+        assert_eq!(solve_part1("12"), 2);
+        assert_eq!(solve_part1("14"), 2);
+        assert_eq!(solve_part1("1969"), 654);
+        assert_eq!(solve_part1("100756"), 33583);
+        assert_eq!(solve_part1(&example("day1")), 2 + 2 + 654 + 33583);
+    }
+
+    #[test]
+    fn test_total_fuel() {
+        assert_eq!(fuel_requirement(14), 2);
+        assert_eq!(fuel_requirement(1969), 966);
+        assert_eq!(fuel_requirement(100756), 50346);
+        assert_eq!(solve_part2(&example("day1")), 2 + 2 + 966 + 50346);
+    }
+
+    #[test]
+    fn test_breakdown() {
+        let modules = breakdown("14\n\n  1969\n");
+        assert_eq!(
+            modules,
+            vec![
+                Module {
+                    line: 1,
+                    mass: 14,
+                    fuel: 2,
+                    total_fuel: 2
+                },
+                Module {
+                    line: 3,
+                    mass: 1969,
+                    fuel: 654,
+                    total_fuel: 966
+                },
+            ]
+        );
+
+        assert_eq!(
+            breakdown_csv(&modules),
+            "line,mass,fuel,total_fuel\n1,14,2,2\n3,1969,654,966\n"
+        );
+        assert_eq!(
+            breakdown_json(&modules[..1]),
+            "{\"line\":1,\"mass\":14,\"fuel\":2,\"total_fuel\":2}\n"
+        );
+    }
+}
@@ -1,34 +1,39 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-
-fn fuel_requirement(mass: i64) -> i64 {
-    let mut total_fuel = 0;
+use clap::{Parser, ValueEnum};
+use common::args::Args;
+use day1::{breakdown, breakdown_csv, breakdown_json, solve_part1, solve_part2};
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Format {
+    Csv,
+    Json,
+}
 
-    let mut cur_mass = mass;
-    loop {
-        cur_mass = (cur_mass / 3) - 2;
-        if cur_mass <= 0 {
-            break;
-        }
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    args: Args,
 
-        total_fuel += cur_mass;
-    }
-
-    return total_fuel;
+    /// Print each module's mass and fuel before the totals, unless the output's
+    /// for scripts
+    #[arg(long, value_name = "FORMAT")]
+    breakdown: Option<Format>,
 }
 
 fn main() {
-    let path = "input";
-
-    let input = File::open(path).unwrap();
-    let buffered = BufReader::new(input);
-
-    let mut total_fuel = 0;
-
-    for line in buffered.lines() {
-        let mass: i64 = line.unwrap().parse().unwrap();
-        total_fuel += fuel_requirement(mass);
+    let cli = Cli::parse();
+    let args = cli.args.for_day(1);
+    let input = args.input();
+
+    match cli.breakdown.filter(|_| args.verbose()) {
+        Some(Format::Csv) => print!("{}", breakdown_csv(&breakdown(&input))),
+        Some(Format::Json) => print!("{}", breakdown_json(&breakdown(&input))),
+        None => {}
     }
 
-    println!("Total Fuel: {}", total_fuel);
+    if args.wants(1) {
+        args.labelled(1, "Module Fuel", solve_part1(&input));
+    }
+    if args.wants(2) {
+        args.labelled(2, "Total Fuel", solve_part2(&input));
+    }
 }
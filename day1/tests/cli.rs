@@ -0,0 +1,63 @@
+use common::inputs::example;
+use common::testing::{run_with_input, stderr, stdout};
+
+const EXE: &str = env!("CARGO_BIN_EXE_day1");
+
+#[test]
+fn test_example() {
+    let output = run_with_input(EXE, 1, Some(&example("day1")), &[]);
+    assert_eq!(stdout(&output), "Module Fuel: 34241\nTotal Fuel: 51316\n");
+}
+
+#[test]
+fn test_invalid_mass() {
+    let output = run_with_input(EXE, 1, Some("12\nfourteen\n"), &[]);
+    assert!(stderr(&output).contains("line 2, column 1: can't parse \"fourteen\""));
+}
+
+#[test]
+fn test_missing_input() {
+    let output = run_with_input(EXE, 1, None, &[]);
+    let err = stderr(&output);
+    assert!(err.starts_with("can't read "), "{}", err);
+    assert!(
+        err.contains("day1.txt: No such file or directory"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn test_quiet() {
+    let output = run_with_input(EXE, 1, Some(&example("day1")), &["--quiet"]);
+    assert_eq!(stdout(&output), "34241\n51316\n");
+}
+
+#[test]
+fn test_breakdown() {
+    let output = run_with_input(EXE, 1, Some("12\n1969\n"), &["--breakdown", "csv"]);
+    assert_eq!(
+        stdout(&output),
+        "line,mass,fuel,total_fuel\n1,12,2,2\n2,1969,654,966\n\
+         Module Fuel: 656\nTotal Fuel: 968\n"
+    );
+
+    let output = run_with_input(EXE, 1, Some("12\n"), &["--breakdown", "json"]);
+    assert!(stdout(&output)
+        .starts_with("{\"line\":1,\"mass\":12,\"fuel\":2,\"total_fuel\":2}\nModule Fuel: 2\n"));
+
+    // output for scripts is only the answers
+    let output = run_with_input(
+        EXE,
+        1,
+        Some("12\n1969\n"),
+        &["--breakdown", "csv", "--quiet"],
+    );
+    assert_eq!(stdout(&output), "656\n968\n");
+
+    let output = run_with_input(EXE, 1, Some("12\n"), &["--breakdown", "json", "--json"]);
+    assert_eq!(
+        stdout(&output),
+        "{\"day\":1,\"part\":1,\"answer\":2}\n{\"day\":1,\"part\":2,\"answer\":2}\n"
+    );
+}
@@ -0,0 +1,171 @@
+//! Day 2: 1202 Program Alarm.
+
+use common::parse::parse_csv;
+use std::ops::RangeInclusive;
+
+/// Parses the Intcode program.
+pub fn program(input: &str) -> Vec<i64> {
+    parse_csv(input).unwrap_or_else(|err| panic!("{}", err))
+}
+
+/// Runs the program in `memory` until it halts, leaving its final state there.
+pub fn execute(memory: &mut [i64]) {
+    let mut pc = 0;
+    while memory[pc] != 99 {
+        let a: i64 = memory[memory[pc + 1] as usize];
+        let b: i64 = memory[memory[pc + 2] as usize];
+        let position = memory[pc + 3] as usize;
+
+        match memory[pc] {
+            1 => memory[position] = a + b,
+            2 => memory[position] = a * b,
+            _ => panic!("unexpected op code"),
+        }
+
+        pc += 4;
+    }
+}
+
+/// Runs the program with the given noun and verb and returns what's left at
+/// address 0.
+pub fn run(program: &[i64], noun: i64, verb: i64) -> i64 {
+    let mut memory = program.to_vec();
+
+    memory[1] = noun;
+    memory[2] = verb;
+    execute(&mut memory);
+
+    memory[0]
+}
+
+/// Every value in `values` tried at `address` before the program runs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Patch {
+    pub address: usize,
+    pub values: RangeInclusive<i64>,
+}
+
+/// Runs the program with every combination of the patches' values, the last
+/// patch varying fastest, and yields the combinations whose final memory
+/// satisfies `target`. A single buffer is reused for every run.
+pub fn sweep<'a, F>(
+    program: &'a [i64],
+    patches: &'a [Patch],
+    target: F,
+) -> impl Iterator<Item = Vec<i64>> + 'a
+where
+    F: Fn(&[i64]) -> bool + 'a,
+{
+    let mut memory = program.to_vec();
+    let mut next = if patches.iter().any(|patch| patch.values.is_empty()) {
+        None
+    } else {
+        Some(
+            patches
+                .iter()
+                .map(|patch| *patch.values.start())
+                .collect::<Vec<_>>(),
+        )
+    };
+
+    std::iter::from_fn(move || {
+        while let Some(values) = next.take() {
+            // advance like an odometer before running, so `next` is ready
+            let mut following = values.clone();
+            next = patches.iter().enumerate().rev().find_map(|(index, patch)| {
+                if following[index] < *patch.values.end() {
+                    following[index] += 1;
+                    Some(following.clone())
+                } else {
+                    following[index] = *patch.values.start();
+                    None
+                }
+            });
+
+            memory.copy_from_slice(program);
+            for (patch, &value) in patches.iter().zip(&values) {
+                memory[patch.address] = value;
+            }
+            execute(&mut memory);
+
+            if target(&memory) {
+                return Some(values);
+            }
+        }
+        None
+    })
+}
+
+/// Returns the output after restoring the "1202 program alarm" state.
+pub fn part1(program: &[i64]) -> i64 {
+    run(program, 12, 2)
+}
+
+/// Returns `100 * noun + verb` for the inputs that produce 19690720.
+pub fn part2(program: &[i64]) -> i64 {
+    let patches = [
+        Patch {
+            address: 1,
+            values: 0..=99,
+        },
+        Patch {
+            address: 2,
+            values: 0..=99,
+        },
+    ];
+
+    let values = sweep(program, &patches, |memory| memory[0] == 19690720)
+        .next()
+        .expect("no noun and verb produce 19690720");
+
+    100 * values[0] + values[1]
+}
+
+pub fn solve_part1(input: &str) -> i64 {
+    part1(&program(input))
+}
+
+pub fn solve_part2(input: &str) -> i64 {
+    part2(&program(input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        // the noun and verb are chosen to leave each example unchanged
+        assert_eq!(run(&program("1,9,10,3,2,3,11,0,99,30,40,50"), 9, 10), 3500);
+        assert_eq!(run(&program("1,0,0,0,99"), 0, 0), 2);
+        assert_eq!(run(&program("2,3,0,3,99"), 3, 0), 2);
+        assert_eq!(run(&program("2,4,4,5,99,0"), 4, 4), 2);
+        assert_eq!(run(&program("1,1,1,4,99,5,6,0,99"), 1, 1), 30);
+    }
+
+    #[test]
+    fn test_sweep() {
+        // adds [5] and [6] into [0]
+        let program = program("1,5,6,0,99,0,0");
+        let patches = [
+            Patch {
+                address: 5,
+                values: 0..=3,
+            },
+            Patch {
+                address: 6,
+                values: 0..=3,
+            },
+        ];
+
+        let found: Vec<Vec<i64>> = sweep(&program, &patches, |memory| memory[0] == 4).collect();
+        assert_eq!(found, vec![vec![1, 3], vec![2, 2], vec![3, 1]]);
+
+        let none = [Patch {
+            address: 5,
+            values: RangeInclusive::new(1, 0),
+        }];
+        assert_eq!(sweep(&program, &none, |_| true).count(), 0);
+        assert_eq!(sweep(&program, &[], |memory| memory[0] == 0).count(), 1);
+    }
+}
@@ -0,0 +1,8 @@
+//! Time budgets for the personal input, run with
+//! `cargo test --release -- --ignored`.
+
+common::budget_tests!(
+    2,
+    day2::solve_part1 => 50,
+    day2::solve_part2 => 100,
+);
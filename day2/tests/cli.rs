@@ -0,0 +1,17 @@
+use common::require_input;
+use common::testing::{run_with_input, stderr, stdout};
+
+const EXE: &str = env!("CARGO_BIN_EXE_day2");
+
+#[test]
+fn test_input() {
+    let input = require_input!(2);
+    let output = run_with_input(EXE, 2, Some(&input), &[]);
+    assert_eq!(stdout(&output), "4138687\n6635\n");
+}
+
+#[test]
+fn test_invalid_program() {
+    let output = run_with_input(EXE, 2, Some("1,0,0,x,99"), &[]);
+    assert!(stderr(&output).contains("line 1, column 7: can't parse \"x\""));
+}
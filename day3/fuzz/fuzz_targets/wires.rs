@@ -0,0 +1,34 @@
+#![no_main]
+
+use day3::{input_to_vectors, wire_to_points, MAX_MAGNITUDE};
+use libfuzzer_sys::fuzz_target;
+
+// Keeps the number of traced points, and so memory, bounded.
+const MAX_SEGMENTS: usize = 64;
+
+fuzz_target!(|data: &[u8]| {
+    let input = match std::str::from_utf8(data) {
+        Ok(input) => input,
+        Err(_) => return,
+    };
+
+    let wires = match input_to_vectors(input) {
+        Ok(wires) => wires,
+        Err(_) => return,
+    };
+
+    for wire in wires.iter() {
+        if wire.len() > MAX_SEGMENTS {
+            continue;
+        }
+
+        let total: u64 = wire.iter().map(|vector| vector.magnitude).sum();
+        assert!(wire.iter().all(|vector| vector.magnitude <= MAX_MAGNITUDE));
+
+        let points = wire_to_points(wire);
+        assert_eq!(points.len() as u64, total);
+        for point in points {
+            assert!(point.x.unsigned_abs() + point.y.unsigned_abs() <= total);
+        }
+    }
+});
@@ -0,0 +1,428 @@
+//! Day 3: Crossed Wires.
+
+use common::direction::Direction;
+use common::point::Point2;
+use std::collections::{BTreeMap, HashMap};
+use std::convert::TryFrom;
+use std::fmt;
+
+/// Longest segment the parser accepts. Real wires stay under a thousand steps,
+/// and every point where two segments overlap becomes a point in memory.
+pub const MAX_MAGNITUDE: u64 = 100_000;
+
+pub struct Vector {
+    pub direction: Direction,
+    pub magnitude: u64,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum WireError {
+    Segment { wire: usize, segment: String },
+    Magnitude { wire: usize, magnitude: u64 },
+    WireCount(usize),
+}
+
+impl fmt::Display for WireError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WireError::Segment { wire, segment } => {
+                write!(f, "wire {}: invalid segment {:?}", wire, segment)
+            }
+            WireError::Magnitude { wire, magnitude } => write!(
+                f,
+                "wire {}: segment length {} is over the limit of {}",
+                wire, magnitude, MAX_MAGNITUDE
+            ),
+            WireError::WireCount(count) => write!(f, "expected 2 wires, found {}", count),
+        }
+    }
+}
+
+fn parse_segment(wire: usize, segment: &str) -> Result<Vector, WireError> {
+    let invalid = || WireError::Segment {
+        wire,
+        segment: segment.to_string(),
+    };
+
+    let first = segment.chars().next().ok_or_else(invalid)?;
+    let direction = Direction::try_from(first).map_err(|_| invalid())?;
+    let magnitude: u64 = segment[first.len_utf8()..].parse().map_err(|_| invalid())?;
+
+    if magnitude > MAX_MAGNITUDE {
+        return Err(WireError::Magnitude { wire, magnitude });
+    }
+
+    Ok(Vector {
+        direction,
+        magnitude,
+    })
+}
+
+/// Parses the two wires, one comma separated path per line.
+pub fn input_to_vectors(input: &str) -> Result<[Vec<Vector>; 2], WireError> {
+    let mut wires = input
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .enumerate()
+        .map(|(index, line)| {
+            line.split(',')
+                .map(|segment| parse_segment(index + 1, segment.trim()))
+                .collect::<Result<Vec<Vector>, WireError>>()
+        })
+        .collect::<Result<Vec<Vec<Vector>>, WireError>>()?;
+
+    if wires.len() != 2 {
+        return Err(WireError::WireCount(wires.len()));
+    }
+
+    let second = wires.pop().unwrap();
+    let first = wires.pop().unwrap();
+    Ok([first, second])
+}
+
+/// Returns every point the wire passes through after leaving the central port,
+/// in order.
+pub fn wire_to_points(wire: &[Vector]) -> Vec<Point2> {
+    let mut pos = Point2::ORIGIN;
+    let mut points = Vec::new();
+
+    for vector in wire {
+        let dir = vector.direction.delta();
+        for _ in 0..vector.magnitude {
+            pos += dir;
+            points.push(pos);
+        }
+    }
+
+    points
+}
+
+/// A straight run of a wire from `start` to `end`, reached after the wire has
+/// taken `steps` steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Segment {
+    pub start: Point2,
+    pub end: Point2,
+    pub steps: u64,
+}
+
+impl Segment {
+    /// Returns how many steps the wire has taken when it gets to `point` along
+    /// this segment.
+    pub fn steps_to(&self, point: Point2) -> u64 {
+        self.steps + point.manhattan(self.start) as u64
+    }
+}
+
+/// Returns the wire's segments in order.
+pub fn wire_to_segments(wire: &[Vector]) -> Vec<Segment> {
+    let mut start = Point2::ORIGIN;
+    let mut steps = 0;
+
+    wire.iter()
+        .map(|vector| {
+            let end = start + vector.direction.delta() * vector.magnitude as i64;
+            let segment = Segment { start, end, steps };
+            start = end;
+            steps += vector.magnitude;
+            segment
+        })
+        .collect()
+}
+
+/// Returns every point the two segments share, ends included. Both are axis
+/// aligned, so they share the overlap of their bounding boxes: nothing, a single
+/// point where they cross, or a run of points where they lie along each other.
+pub fn crossings(a: &Segment, b: &Segment) -> impl Iterator<Item = Point2> {
+    let span = |segment: &Segment| {
+        (
+            segment.start.x.min(segment.end.x),
+            segment.start.x.max(segment.end.x),
+            segment.start.y.min(segment.end.y),
+            segment.start.y.max(segment.end.y),
+        )
+    };
+    let (a_left, a_right, a_bottom, a_top) = span(a);
+    let (b_left, b_right, b_bottom, b_top) = span(b);
+
+    let (left, right) = (a_left.max(b_left), a_right.min(b_right));
+    let (bottom, top) = (a_bottom.max(b_bottom), a_top.min(b_top));
+
+    (left..=right).flat_map(move |x| (bottom..=top).map(move |y| Point2::new(x, y)))
+}
+
+/// A wire's segments indexed by where they lie, so finding the ones that might
+/// touch another segment doesn't mean checking every one: horizontal segments
+/// by their row and vertical ones by their column, each kept sorted.
+pub struct SegmentIndex {
+    horizontal: BTreeMap<i64, Vec<Segment>>,
+    vertical: BTreeMap<i64, Vec<Segment>>,
+}
+
+impl SegmentIndex {
+    /// Indexes the segments. A segment of no length counts as horizontal.
+    pub fn new(segments: &[Segment]) -> Self {
+        let mut index = SegmentIndex {
+            horizontal: BTreeMap::new(),
+            vertical: BTreeMap::new(),
+        };
+        for &segment in segments {
+            let (lines, at) = if segment.start.y == segment.end.y {
+                (&mut index.horizontal, segment.start.y)
+            } else {
+                (&mut index.vertical, segment.start.x)
+            };
+            lines.entry(at).or_insert_with(Vec::new).push(segment);
+        }
+        index
+    }
+
+    /// Returns the indexed segments that might share a point with `segment`:
+    /// those across it within its span, and those on the same line.
+    pub fn candidates<'a>(&'a self, segment: &Segment) -> impl Iterator<Item = &'a Segment> {
+        let (across, along, line, from, to) = if segment.start.y == segment.end.y {
+            let (from, to) = (segment.start.x, segment.end.x);
+            (&self.vertical, &self.horizontal, segment.start.y, from, to)
+        } else {
+            let (from, to) = (segment.start.y, segment.end.y);
+            (&self.horizontal, &self.vertical, segment.start.x, from, to)
+        };
+
+        let crossing = across
+            .range(from.min(to)..=from.max(to))
+            .flat_map(|(_, lines)| lines);
+        crossing.chain(along.get(&line).into_iter().flatten())
+    }
+}
+
+/// Returns each point both wires pass through other than the central port, with
+/// the fewest steps each wire takes to get there.
+fn intersections(wires: &[Vec<Vector>; 2]) -> HashMap<Point2, [u64; 2]> {
+    let first = wire_to_segments(&wires[0]);
+    let second = SegmentIndex::new(&wire_to_segments(&wires[1]));
+
+    let mut inter = HashMap::new();
+    for a in &first {
+        for b in second.candidates(a) {
+            for point in crossings(a, b).filter(|&point| point != Point2::ORIGIN) {
+                let steps = inter.entry(point).or_insert([u64::MAX; 2]);
+                steps[0] = steps[0].min(a.steps_to(point));
+                steps[1] = steps[1].min(b.steps_to(point));
+            }
+        }
+    }
+
+    inter
+}
+
+/// Parses both wires, panicking on a bad segment.
+pub fn wires(input: &str) -> [Vec<Vector>; 2] {
+    input_to_vectors(input).unwrap_or_else(|err| panic!("{}", err))
+}
+
+/// Returns the distance from the central port to the closest intersection.
+pub fn part1(wires: &[Vec<Vector>; 2]) -> i64 {
+    intersections(wires)
+        .keys()
+        .map(|v| v.manhattan(Point2::ORIGIN))
+        .min()
+        .expect("the wires never cross")
+}
+
+/// Returns the fewest combined steps the wires take to reach an intersection.
+pub fn part2(wires: &[Vec<Vector>; 2]) -> usize {
+    intersections(wires)
+        .values()
+        .map(|&[first, second]| (first + second) as usize)
+        .min()
+        .expect("the wires never cross")
+}
+
+pub fn solve_part1(input: &str) -> i64 {
+    part1(&wires(input))
+}
+
+pub fn solve_part2(input: &str) -> usize {
+    part2(&wires(input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::inputs::example;
+
+    const EXAMPLES: [(&str, i64, usize); 2] = [
+        (
+            "R75,D30,R83,U83,L12,D49,R71,U7,L72\nU62,R66,U55,R34,D71,R55,D58,R83",
+            159,
+            610,
+        ),
+        (
+            "R98,U47,R26,D63,R33,U87,L62,D20,R33,U53,R51\nU98,R91,D20,R16,D67,R40,U7,R15,U6,R7",
+            135,
+            410,
+        ),
+    ];
+
+    #[test]
+    fn test_examples() {
+        assert_eq!(solve_part1(&example("day3")), 6);
+        assert_eq!(solve_part2(&example("day3")), 30);
+
+        for &(wires, distance, steps) in EXAMPLES.iter() {
+            assert_eq!(solve_part1(wires), distance);
+            assert_eq!(solve_part2(wires), steps);
+        }
+    }
+
+    fn segment(start: (i64, i64), end: (i64, i64)) -> Segment {
+        Segment {
+            start: start.into(),
+            end: end.into(),
+            steps: 0,
+        }
+    }
+
+    #[test]
+    fn test_crossings() {
+        let crossing = |a, b| crossings(&a, &b).collect::<Vec<Point2>>();
+
+        // perpendicular, crossing and not
+        assert_eq!(
+            crossing(segment((0, 0), (10, 0)), segment((4, -3), (4, 3))),
+            vec![Point2::new(4, 0)]
+        );
+        assert_eq!(
+            crossing(segment((0, 0), (10, 0)), segment((4, 1), (4, 3))),
+            vec![]
+        );
+
+        // collinear, overlapping in either direction, touching and apart
+        let overlap: Vec<Point2> = (4..=10).map(|x| Point2::new(x, 0)).collect();
+        assert_eq!(
+            crossing(segment((0, 0), (10, 0)), segment((12, 0), (4, 0))),
+            overlap
+        );
+        assert_eq!(
+            crossing(segment((10, 0), (0, 0)), segment((4, 0), (12, 0))),
+            overlap
+        );
+        assert_eq!(
+            crossing(segment((0, 5), (0, 0)), segment((0, 5), (0, 9))),
+            vec![Point2::new(0, 5)]
+        );
+        assert_eq!(
+            crossing(segment((0, 0), (0, 5)), segment((0, 6), (0, 9))),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn test_segment_index() {
+        let index = SegmentIndex::new(&[
+            segment((0, 0), (10, 0)),
+            segment((10, 0), (10, 5)),
+            segment((10, 5), (3, 5)),
+            segment((3, 5), (3, 5)),
+            segment((3, 5), (3, 20)),
+        ]);
+        let candidates = |a, b| {
+            let mut found: Vec<(Point2, Point2)> = index
+                .candidates(&segment(a, b))
+                .map(|segment| (segment.start, segment.end))
+                .collect();
+            found.sort_by_key(|&(start, end)| (start.x, start.y, end.x, end.y));
+            found
+        };
+
+        // a column crosses rows 0 and 5, and runs along the column at x = 3
+        assert_eq!(
+            candidates((3, -1), (3, 6)),
+            vec![
+                ((0, 0).into(), (10, 0).into()),
+                ((3, 5).into(), (3, 5).into()),
+                ((3, 5).into(), (3, 20).into()),
+                ((10, 5).into(), (3, 5).into()),
+            ]
+        );
+        // a row too high for any of the rows, left of the column at x = 10
+        assert_eq!(
+            candidates((9, 30), (-4, 30)),
+            vec![((3, 5).into(), (3, 20).into())]
+        );
+    }
+
+    #[test]
+    fn test_collinear_wires() {
+        // the second wire comes back along the first, facing it
+        let facing = "R10\nU1,R12,D1,L5";
+        assert_eq!(solve_part1(facing), 7);
+        assert_eq!(solve_part2(facing), 26);
+
+        // and here runs along it the same way, after crossing it at (3, 0)
+        let along = "R10\nU2,R3,D4,L1,U2,R6";
+        assert_eq!(solve_part1(along), 2);
+        assert_eq!(solve_part2(along), 10);
+    }
+
+    #[test]
+    fn test_matches_points() {
+        // every step of both wires, the way intersections were first found
+        let points = |input: &str| {
+            let wires = input_to_vectors(input).unwrap();
+            let first = wire_to_points(&wires[0]);
+            let second = wire_to_points(&wires[1]);
+            let mut inter: Vec<Point2> = first
+                .iter()
+                .filter(|point| second.contains(point))
+                .cloned()
+                .collect();
+            inter.sort_by_key(|point| (point.x, point.y));
+            inter.dedup();
+            inter
+        };
+
+        let inputs = [
+            example("day3"),
+            String::from(EXAMPLES[0].0),
+            String::from("R10\nU1,R12,D1,L5"),
+            String::from("R10\nU2,R3,D4,L1,U2,R6"),
+            String::from("R4,U4,L4,D8\nD2,R6,U4,L8"),
+        ];
+        for input in &inputs {
+            let mut found: Vec<Point2> = intersections(&wires(input)).keys().cloned().collect();
+            found.sort_by_key(|point| (point.x, point.y));
+            assert_eq!(found, points(input), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_invalid_wires() {
+        assert_eq!(
+            input_to_vectors("R8,U5\nU7,X6\n").err(),
+            Some(WireError::Segment {
+                wire: 2,
+                segment: String::from("X6")
+            })
+        );
+        assert_eq!(
+            input_to_vectors("R8,U\nU7")
+                .err()
+                .map(|err| err.to_string()),
+            Some(String::from("wire 1: invalid segment \"U\""))
+        );
+        assert_eq!(
+            input_to_vectors("R8,U999999\nU7").err(),
+            Some(WireError::Magnitude {
+                wire: 1,
+                magnitude: 999999
+            })
+        );
+        assert_eq!(
+            input_to_vectors("R8\n").err(),
+            Some(WireError::WireCount(1))
+        );
+        assert!(input_to_vectors("R8,U5\r\nU7,L6\r\n").is_ok());
+    }
+}
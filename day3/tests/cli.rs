@@ -0,0 +1,19 @@
+use common::inputs::example;
+use common::testing::{run_with_input, stderr, stdout};
+
+const EXE: &str = env!("CARGO_BIN_EXE_day3");
+
+#[test]
+fn test_example() {
+    let output = run_with_input(EXE, 3, Some(&example("day3")), &[]);
+    assert_eq!(stdout(&output), "6\n30\n");
+}
+
+#[test]
+fn test_invalid_wires() {
+    let output = run_with_input(EXE, 3, Some("R8,U5,L5,D3\n"), &[]);
+    assert!(stderr(&output).contains("expected 2 wires, found 1"));
+
+    let output = run_with_input(EXE, 3, Some("R8,U5\nU7,Q6\n"), &[]);
+    assert!(stderr(&output).contains("wire 2: invalid segment \"Q6\""));
+}
@@ -0,0 +1,57 @@
+//! Checks finding intersections with the segment index gives the same answers
+//! as walking both wires through a map of every point, on synthetic wires.
+//! The speed of the two is compared by report's `day3_index` benchmark.
+
+use common::point::Point2;
+use common::rng::Rng;
+use day3::{input_to_vectors, solve_part1, solve_part2, wire_to_points};
+use std::collections::HashMap;
+
+const STEPS: u64 = 20_000;
+const LONGEST_SEGMENT: u64 = 200;
+const SEED: u64 = 2019;
+
+/// Builds a random walk of about `STEPS` steps.
+fn synthetic_wire(rng: &mut Rng) -> String {
+    let mut segments = Vec::new();
+    let mut steps = 0;
+    while steps < STEPS {
+        let direction = rng.choose(&['U', 'D', 'L', 'R']).unwrap();
+        let magnitude = 1 + rng.below(LONGEST_SEGMENT);
+        segments.push(format!("{}{}", direction, magnitude));
+        steps += magnitude;
+    }
+    segments.join(",")
+}
+
+/// The answers the way they were first found, with every point of the first
+/// wire in a map.
+fn by_points(input: &str) -> (i64, usize) {
+    let wires = input_to_vectors(input).unwrap();
+
+    let mut first: HashMap<Point2, usize> = HashMap::new();
+    for (steps, point) in wire_to_points(&wires[0]).into_iter().enumerate() {
+        first.entry(point).or_insert(steps + 1);
+    }
+
+    let (mut closest, mut fewest) = (i64::MAX, usize::MAX);
+    for (steps, point) in wire_to_points(&wires[1]).into_iter().enumerate() {
+        if let Some(&first_steps) = first.get(&point) {
+            closest = closest.min(point.manhattan(Point2::ORIGIN));
+            fewest = fewest.min(first_steps + steps + 1);
+        }
+    }
+
+    (closest, fewest)
+}
+
+#[test]
+fn test_index_matches_points() {
+    let mut rng = Rng::seeded(SEED);
+    let input = format!("{}\n{}", synthetic_wire(&mut rng), synthetic_wire(&mut rng));
+
+    assert_eq!(
+        (solve_part1(&input), solve_part2(&input)),
+        by_points(&input)
+    );
+}
@@ -0,0 +1,229 @@
+//! Day 4: Secure Container.
+
+use common::digits::digits;
+use common::interval::Interval;
+
+/// Parses a range like `138307-654504`, which must have its low bound first.
+pub fn parse_range(input: &str) -> Result<Interval, String> {
+    let bounds = input
+        .trim()
+        .split('-')
+        .map(|bound| {
+            bound
+                .parse()
+                .map_err(|_| format!("invalid range bound: {:?}", bound))
+        })
+        .collect::<Result<Vec<i64>, String>>()?;
+
+    match bounds[..] {
+        [lo, hi] if lo <= hi => Ok(Interval::new(lo, hi)),
+        [lo, hi] => Err(format!("range {}-{} is empty", lo, hi)),
+        _ => Err(format!(
+            "range must look like lo-hi, got {:?}",
+            input.trim()
+        )),
+    }
+}
+
+/// Like `parse_range`, panicking if the range is invalid.
+pub fn range(input: &str) -> Interval {
+    parse_range(input).unwrap_or_else(|err| panic!("{}", err))
+}
+
+/// Parses the ranges in the input, one per line.
+pub fn ranges(input: &str) -> Vec<Interval> {
+    let ranges: Vec<Interval> = input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(range)
+        .collect();
+
+    if ranges.is_empty() {
+        panic!("range must look like lo-hi, got {:?}", input.trim());
+    }
+    ranges
+}
+
+/// Returns the rule for which runs of equal digits a password needs in `part`.
+pub fn rule(part: u32) -> fn(u32) -> bool {
+    match part {
+        1 => |run| run >= 2,
+        _ => |run| run == 2,
+    }
+}
+
+/// Returns whether the digits of `n` never decrease and it has a run of equal
+/// adjacent digits whose length is accepted by `valid_run`.
+pub fn is_password(n: u64, valid_run: impl Fn(u32) -> bool) -> bool {
+    let mut found_run = false;
+    let mut run = 1;
+
+    let mut digits = digits(n);
+    let mut cur = match digits.next() {
+        Some(cur) => cur,
+        None => return false,
+    };
+
+    for next in digits {
+        if next < cur {
+            return false;
+        }
+
+        if next == cur {
+            run += 1;
+        } else {
+            found_run |= valid_run(run);
+            run = 1;
+        }
+
+        cur = next;
+    }
+
+    found_run || valid_run(run)
+}
+
+fn count_passwords(range: Interval, valid_run: impl Fn(u32) -> bool) -> usize {
+    range
+        .into_iter()
+        .filter(|&i| is_password(i as u64, &valid_run))
+        .count()
+}
+
+/// How many passwords each range has, and how many they have between them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Counts {
+    pub ranges: Vec<(Interval, usize)>,
+    /// Passwords in any of the ranges, counting those in more than one once.
+    pub combined: usize,
+}
+
+/// Counts the passwords in each range and in all of them together.
+pub fn count_ranges(ranges: &[Interval], valid_run: impl Fn(u32) -> bool) -> Counts {
+    let counts = ranges
+        .iter()
+        .map(|&range| (range, count_passwords(range, &valid_run)))
+        .collect();
+
+    // overlapping ranges are merged so nothing is counted twice
+    let mut sorted = ranges.to_vec();
+    sorted.sort();
+    let mut merged: Vec<Interval> = Vec::new();
+    for range in sorted {
+        match merged.last_mut().and_then(|last| last.union(&range)) {
+            Some(union) => *merged.last_mut().unwrap() = union,
+            None => merged.push(range),
+        }
+    }
+
+    Counts {
+        ranges: counts,
+        combined: merged
+            .into_iter()
+            .map(|range| count_passwords(range, &valid_run))
+            .sum(),
+    }
+}
+
+/// Returns how many passwords in the ranges have at least two adjacent matching
+/// digits.
+pub fn part1(ranges: &[Interval]) -> usize {
+    count_ranges(ranges, rule(1)).combined
+}
+
+/// Returns how many passwords in the ranges have a pair of matching digits that
+/// isn't part of a larger group.
+pub fn part2(ranges: &[Interval]) -> usize {
+    count_ranges(ranges, rule(2)).combined
+}
+
+pub fn solve_part1(input: &str) -> usize {
+    part1(&ranges(input))
+}
+
+pub fn solve_part2(input: &str) -> usize {
+    part2(&ranges(input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::digits::from_digits;
+    use proptest::prelude::*;
+
+    /// Straightforward version of the rules working on the decimal string.
+    fn reference(n: u64, exact_pair: bool) -> bool {
+        let s: Vec<char> = n.to_string().chars().collect();
+
+        if s.windows(2).any(|pair| pair[1] < pair[0]) {
+            return false;
+        }
+
+        // the digits never decrease, so equal digits are always adjacent
+        s.iter().any(|&ch| {
+            let count = s.iter().filter(|&&other| other == ch).count();
+            if exact_pair {
+                count == 2
+            } else {
+                count >= 2
+            }
+        })
+    }
+
+    /// Six-digit numbers, half of them with non-decreasing digits since those
+    /// are rare among uniformly random numbers.
+    fn six_digits() -> impl Strategy<Value = u64> {
+        let sorted = prop::collection::vec(1u8..10, 6).prop_map(|mut digits| {
+            digits.sort_unstable();
+            from_digits(digits)
+        });
+
+        prop_oneof![100_000u64..1_000_000, sorted]
+    }
+
+    #[test]
+    fn test_examples() {
+        assert!(is_password(111111, |run| run >= 2));
+        assert!(!is_password(223450, |run| run >= 2));
+        assert!(!is_password(123789, |run| run >= 2));
+
+        assert!(is_password(112233, |run| run == 2));
+        assert!(!is_password(123444, |run| run == 2));
+        assert!(is_password(111122, |run| run == 2));
+    }
+
+    #[test]
+    fn test_count_ranges() {
+        let ranges = ranges("111111-111122\n\n111120-111133\n222222-222222\n");
+        assert_eq!(ranges.len(), 3);
+
+        // 111111 through 111119, 111122 through 111129 and 111133, with
+        // 111122 in both of the first two ranges
+        let counts = count_ranges(&ranges, rule(1));
+        assert_eq!(
+            counts
+                .ranges
+                .iter()
+                .map(|&(_, count)| count)
+                .collect::<Vec<_>>(),
+            vec![10, 9, 1]
+        );
+        assert_eq!(counts.combined, 9 + 8 + 1 + 1);
+
+        // only 111122 and 111133 have a pair on its own
+        assert_eq!(count_ranges(&ranges, rule(2)).combined, 2);
+    }
+
+    proptest! {
+        #[test]
+        fn test_matches_reference(n in six_digits()) {
+            prop_assert_eq!(is_password(n, |run| run >= 2), reference(n, false));
+            prop_assert_eq!(is_password(n, |run| run == 2), reference(n, true));
+        }
+
+        #[test]
+        fn test_exact_pair_is_stricter(lo in 100_000i64..999_999, len in 0i64..5_000) {
+            let range = format!("{}-{}", lo, (lo + len).min(999_999));
+            prop_assert!(solve_part2(&range) <= solve_part1(&range));
+        }
+    }
+}
@@ -1,43 +1,41 @@
-fn main() {
-    let lo = 138307;
-    let hi = 654504;
-
-    let mut ok = 0;
-
-    'bruteforce: for i in lo..hi {
-        let s: Vec<char> = i.to_string().chars().collect();
-
-        let mut found_seq = false;
-        let mut seq_count = 0;
-
-        let mut i = 0;
-        while i < s.len() - 1 {
-            let cur = s[i];
-            let next = s[i + 1];
-
-            if next == cur {
-                seq_count += 1;
-            } else {
-                if seq_count == 1 {
-                    found_seq = true;
-                }
-
-                seq_count = 0;
-            }
-
-            if next < cur {
-                continue 'bruteforce;
-            }
-
-            i += 1;
-        }
+use clap::Parser;
+use common::args::Args;
+use common::interval::Interval;
+use day4::{count_ranges, parse_range, ranges, rule};
+
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    args: Args,
+
+    /// Count passwords in this range instead of the input's, can be repeated
+    #[arg(long = "range", value_name = "LO-HI", value_parser = parse_range)]
+    ranges: Vec<Interval>,
+}
 
-        if !found_seq && seq_count != 1 {
+fn main() {
+    let cli = Cli::parse();
+    let args = cli.args.for_day(4);
+    let ranges = if cli.ranges.is_empty() {
+        ranges(&args.input())
+    } else {
+        cli.ranges
+    };
+
+    for part in 1..=2 {
+        if !args.wants(part) {
             continue;
         }
 
-        ok += 1;
+        let counts = count_ranges(&ranges, rule(part));
+        if counts.ranges.len() > 1 && args.verbose() {
+            for (range, count) in &counts.ranges {
+                let label = format!("part {} {}-{}", part, range.start, range.end);
+                args.labelled(part, &label, *count);
+            }
+            args.labelled(part, &format!("part {} combined", part), counts.combined);
+        } else {
+            args.answer(part, counts.combined);
+        }
     }
-
-    println!("{}", ok);
 }
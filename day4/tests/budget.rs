@@ -0,0 +1,8 @@
+//! Time budgets for the personal input, run with
+//! `cargo test --release -- --ignored`.
+
+common::budget_tests!(
+    4,
+    day4::solve_part1 => 200,
+    day4::solve_part2 => 200,
+);
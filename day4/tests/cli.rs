@@ -0,0 +1,53 @@
+use common::testing::{run_with_input, stderr, stdout};
+
+const EXE: &str = env!("CARGO_BIN_EXE_day4");
+
+#[test]
+fn test_small_range() {
+    // 111111 through 111119 and 111122 never decrease, only 111122 has an
+    // isolated pair
+    let output = run_with_input(EXE, 4, Some("111111-111122\n"), &[]);
+    assert_eq!(stdout(&output), "10\n1\n");
+}
+
+#[test]
+fn test_invalid_range() {
+    let output = run_with_input(EXE, 4, Some("111111\n"), &[]);
+    assert!(stderr(&output).contains("range must look like lo-hi"));
+
+    // bad --range values are usage errors
+    let bad = [
+        ("abc", "invalid range bound: \"abc\""),
+        ("200-100", "range 200-100 is empty"),
+    ];
+    for (range, message) in bad {
+        let output = run_with_input(EXE, 4, None, &["--range", range]);
+        let err = stderr(&output);
+        assert_eq!(output.status.code(), Some(2), "{}", err);
+        assert!(err.contains(message), "{}", err);
+    }
+}
+
+#[test]
+fn test_multiple_ranges() {
+    let input = "111111-111122\n111120-111133\n";
+    let output = run_with_input(EXE, 4, Some(input), &["--part", "1"]);
+    assert_eq!(
+        stdout(&output),
+        "part 1 111111-111122: 10\npart 1 111120-111133: 9\npart 1 combined: 18\n"
+    );
+
+    let output = run_with_input(
+        EXE,
+        4,
+        None,
+        &[
+            "--range",
+            "111111-111122",
+            "--range",
+            "222222-222222",
+            "--quiet",
+        ],
+    );
+    assert_eq!(stdout(&output), "11\n1\n");
+}
@@ -0,0 +1,413 @@
+//! Day 5: Sunny with a Chance of Asteroids.
+
+use common::parse::parse_csv;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Copy, Clone)]
+enum Mode {
+    Position,
+    Immediate,
+}
+
+impl FromStr for Mode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        Ok(match s {
+            "1" => Self::Immediate,
+            _ => Self::Position,
+        })
+    }
+}
+
+#[derive(Debug)]
+enum Instruction {
+    Add(Mode, Mode, Mode),
+    Mul(Mode, Mode, Mode),
+    Input(Mode),
+    Output(Mode),
+    JumpTrue(Mode, Mode),
+    JumpFalse(Mode, Mode),
+    LessThan(Mode, Mode, Mode),
+    Equals(Mode, Mode, Mode),
+}
+
+impl Instruction {
+    pub fn parameters(&self) -> usize {
+        match *self {
+            Instruction::Add(_, _, _) | Instruction::Mul(_, _, _) => 3,
+            Instruction::Input(_) | Instruction::Output(_) => 1,
+            Instruction::JumpTrue(_, _) | Instruction::JumpFalse(_, _) => 2,
+            Instruction::LessThan(_, _, _) | Instruction::Equals(_, _, _) => 3,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct OpCodeError {
+    op_code: Option<char>,
+}
+
+impl fmt::Display for OpCodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.op_code {
+            Some(v) => write!(f, "unknown op code: {}", v),
+            None => write!(f, "no op code"),
+        }
+    }
+}
+
+impl FromStr for Instruction {
+    type Err = OpCodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let chars = &mut s.chars().rev();
+
+        let op_code = match chars.next() {
+            Some(op_code) => op_code,
+            None => return Err(OpCodeError { op_code: None }),
+        };
+
+        // skip the zero in the op code since they're two-padded and we only care about
+        // the first digit
+        chars.next();
+
+        // always take three modes, default to position
+        let mut modes = [Mode::Position; 3];
+
+        for (mode, ch) in modes.iter_mut().zip(chars) {
+            if let Ok(parsed) = Mode::from_str(&ch.to_string()) {
+                *mode = parsed;
+            }
+        }
+
+        match op_code {
+            '1' => Ok(Instruction::Add(modes[0], modes[1], modes[2])),
+            '2' => Ok(Instruction::Mul(modes[0], modes[1], modes[2])),
+            '3' => Ok(Instruction::Input(modes[0])),
+            '4' => Ok(Instruction::Output(modes[0])),
+            '5' => Ok(Instruction::JumpTrue(modes[0], modes[1])),
+            '6' => Ok(Instruction::JumpFalse(modes[0], modes[1])),
+            '7' => Ok(Instruction::LessThan(modes[0], modes[1], modes[2])),
+            '8' => Ok(Instruction::Equals(modes[0], modes[1], modes[2])),
+            _ => Err(OpCodeError {
+                op_code: Some(op_code),
+            }),
+        }
+    }
+}
+
+/// What one instruction did, as seen by the hook passed to `evaluate_with`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub pc: usize,
+    /// The instruction's value in memory, modes included.
+    pub op_code: i64,
+    /// Each parameter's value, with the address it came from in position mode.
+    pub operands: Vec<(i64, Option<usize>)>,
+    pub write: Option<(usize, i64)>,
+    pub output: Option<i64>,
+}
+
+impl Step {
+    fn name(&self) -> &'static str {
+        match self.op_code % 100 {
+            1 => "add",
+            2 => "mul",
+            3 => "in",
+            4 => "out",
+            5 => "jump-if-true",
+            6 => "jump-if-false",
+            7 => "less-than",
+            _ => "equals",
+        }
+    }
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let modes: Vec<&str> = self
+            .operands
+            .iter()
+            .map(|(_, address)| match address {
+                Some(_) => "position",
+                None => "immediate",
+            })
+            .collect();
+        write!(f, "{} ({}) at {}", self.name(), modes.join(", "), self.pc)
+    }
+}
+
+fn operand(memory: &[i64], address: usize, mode: Mode) -> (i64, Option<usize>) {
+    match mode {
+        Mode::Position => {
+            let address = memory[address] as usize;
+            (memory[address], Some(address))
+        }
+        Mode::Immediate => (memory[address], None),
+    }
+}
+
+fn destination(memory: &[i64], address: usize, mode: Mode) -> usize {
+    match mode {
+        Mode::Position => memory[address] as usize,
+        Mode::Immediate => address,
+    }
+}
+
+/// Like `evaluate`, calling `on_step` after every instruction.
+pub fn evaluate_with<F>(memory: &mut [i64], input: i64, mut on_step: F) -> Result<i64, OpCodeError>
+where
+    F: FnMut(&Step),
+{
+    let mut pc = 0;
+    let mut diagnostic_code = 0;
+
+    while memory[pc] != 99 {
+        let instr = Instruction::from_str(&memory[pc].to_string())?;
+        let params = instr.parameters();
+        let mut step = Step {
+            pc,
+            op_code: memory[pc],
+            operands: Vec::new(),
+            write: None,
+            output: None,
+        };
+
+        match instr {
+            Instruction::Add(a, b, location)
+            | Instruction::Mul(a, b, location)
+            | Instruction::LessThan(a, b, location)
+            | Instruction::Equals(a, b, location) => {
+                step.operands = vec![operand(memory, pc + 1, a), operand(memory, pc + 2, b)];
+                let (a, b) = (step.operands[0].0, step.operands[1].0);
+
+                let v = match instr {
+                    Instruction::Add(_, _, _) => a + b,
+                    Instruction::Mul(_, _, _) => a * b,
+                    Instruction::LessThan(_, _, _) => (a < b) as i64,
+                    _ => (a == b) as i64,
+                };
+
+                let location = destination(memory, pc + 3, location);
+                memory[location] = v;
+                step.write = Some((location, v));
+            }
+            Instruction::Input(location) => {
+                let location = destination(memory, pc + 1, location);
+                memory[location] = input;
+                step.write = Some((location, input));
+            }
+            Instruction::Output(location) => {
+                step.operands = vec![operand(memory, pc + 1, location)];
+                diagnostic_code = step.operands[0].0;
+                step.output = Some(diagnostic_code);
+            }
+            Instruction::JumpTrue(a, jmp) | Instruction::JumpFalse(a, jmp) => {
+                step.operands = vec![operand(memory, pc + 1, a), operand(memory, pc + 2, jmp)];
+                let (a, jmp) = (step.operands[0].0, step.operands[1].0);
+
+                let cond = match instr {
+                    Instruction::JumpTrue(_, _) => a != 0,
+                    _ => a == 0,
+                };
+
+                if cond {
+                    on_step(&step);
+                    pc = jmp as usize;
+                    continue;
+                }
+            }
+        }
+
+        on_step(&step);
+        pc += params + 1;
+    }
+
+    Ok(diagnostic_code)
+}
+
+fn evaluate(memory: &mut [i64], input: i64) -> Result<i64, OpCodeError> {
+    evaluate_with(memory, input, |_| {})
+}
+
+/// The instruction that last wrote a cell, and the ones that had last written
+/// each of its operands when it ran.
+struct Written {
+    step: Step,
+    sources: Vec<Option<Step>>,
+}
+
+/// Runs the diagnostic program and describes each self-test it performs: the
+/// instruction under test, and what it gave against what the program expected.
+///
+/// A test checks its result by adding the negated expected value to it and
+/// outputting the difference, so an output that comes from adding a constant
+/// to a computed value is taken as a test of whatever computed it. Other
+/// outputs are shown with the instruction that produced them, passing if
+/// they're 0, and the last output is the diagnostic code.
+pub fn comparison_trace(input: &str, system_id: i64) -> Vec<String> {
+    let mut memory: Vec<i64> = parse_csv(input).unwrap_or_else(|err| panic!("{}", err));
+    let mut writers: HashMap<usize, Written> = HashMap::new();
+    let mut lines = Vec::new();
+
+    let on_step = |step: &Step| {
+        if let Some(value) = step.output {
+            let number = lines.len() + 1;
+            let written = step.operands[0].1.and_then(|address| writers.get(&address));
+            lines.push(match written {
+                Some(written) => describe_test(number, written, value),
+                None => format!(
+                    "test {}: {} output {}: {}",
+                    number,
+                    step,
+                    value,
+                    verdict(value == 0)
+                ),
+            });
+        }
+
+        if let Some((address, _)) = step.write {
+            let sources = step
+                .operands
+                .iter()
+                .map(|(_, from)| {
+                    from.and_then(|from| writers.get(&from))
+                        .map(|w| w.step.clone())
+                })
+                .collect();
+            writers.insert(
+                address,
+                Written {
+                    step: step.clone(),
+                    sources,
+                },
+            );
+        }
+    };
+
+    let code =
+        evaluate_with(&mut memory, system_id, on_step).unwrap_or_else(|err| panic!("{}", err));
+    if let Some(last) = lines.last_mut() {
+        *last = format!("diagnostic code {}", code);
+    }
+
+    lines
+}
+
+fn verdict(passed: bool) -> &'static str {
+    if passed {
+        "ok"
+    } else {
+        "FAILED"
+    }
+}
+
+fn describe_test(number: usize, written: &Written, value: i64) -> String {
+    let step = &written.step;
+    let checked = match step.operands[..] {
+        [(constant, None), (actual, Some(_))] if step.op_code % 100 == 1 => {
+            Some((actual, -constant, &written.sources[1]))
+        }
+        [(actual, Some(_)), (constant, None)] if step.op_code % 100 == 1 => {
+            Some((actual, -constant, &written.sources[0]))
+        }
+        _ => None,
+    };
+
+    match checked {
+        Some((actual, expected, Some(tested))) => format!(
+            "test {}: {} gave {}, expected {}: {}",
+            number,
+            tested,
+            actual,
+            expected,
+            verdict(actual == expected)
+        ),
+        _ => format!(
+            "test {}: {} output {}: {}",
+            number,
+            step,
+            value,
+            verdict(value == 0)
+        ),
+    }
+}
+
+/// Runs the diagnostic program with `system_id` as its input and returns the
+/// diagnostic code it outputs last.
+fn diagnostic_code(program: &[i64], system_id: i64) -> i64 {
+    let mut memory = program.to_vec();
+    evaluate(&mut memory, system_id).unwrap_or_else(|err| panic!("{}", err))
+}
+
+/// Parses the diagnostic program.
+pub fn program(input: &str) -> Vec<i64> {
+    parse_csv(input).unwrap_or_else(|err| panic!("{}", err))
+}
+
+/// Returns the diagnostic code for the ship's air conditioner unit.
+pub fn part1(program: &[i64]) -> i64 {
+    diagnostic_code(program, 1)
+}
+
+/// Returns the diagnostic code for the ship's thermal radiator controller.
+pub fn part2(program: &[i64]) -> i64 {
+    diagnostic_code(program, 5)
+}
+
+pub fn solve_part1(input: &str) -> i64 {
+    part1(&program(input))
+}
+
+pub fn solve_part2(input: &str) -> i64 {
+    part2(&program(input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::require_input;
+
+    #[test]
+    fn test_part_1() {
+        let mut memory: Vec<i64> = parse_csv(&require_input!(5)).unwrap();
+        let diagnostic = evaluate(&mut memory, 1);
+        assert!(diagnostic.is_ok());
+        assert_eq!(diagnostic.unwrap(), 8332629);
+    }
+
+    #[test]
+    fn test_part_2() {
+        let mut memory: Vec<i64> = parse_csv(&require_input!(5)).unwrap();
+        let diagnostic = evaluate(&mut memory, 5);
+        assert!(diagnostic.is_ok());
+        assert_eq!(diagnostic.unwrap(), 8805067);
+    }
+
+    #[test]
+    fn test_comparison_trace() {
+        // multiplies 6 by 7 and checks for 42, then adds 2 and 2 and checks for 5
+        let program = "1102,6,7,30,101,-42,30,31,4,31,1101,2,2,30,101,-5,30,31,4,31,4,30,99,\
+                       0,0,0,0,0,0,0,0,0";
+        assert_eq!(
+            comparison_trace(program, 1),
+            vec![
+                "test 1: mul (immediate, immediate) at 0 gave 42, expected 42: ok",
+                "test 2: add (immediate, immediate) at 10 gave 4, expected 5: FAILED",
+                "diagnostic code 4",
+            ]
+        );
+
+        let lines = comparison_trace(&require_input!(5), 1);
+        let (code, tests) = lines.split_last().unwrap();
+        assert_eq!(code, "diagnostic code 8332629");
+        assert!(
+            tests.iter().all(|line| line.ends_with(": ok")),
+            "{:?}",
+            tests
+        );
+    }
+}
@@ -0,0 +1,32 @@
+use common::testing::{run_with_input, stderr, stdout};
+
+const EXE: &str = env!("CARGO_BIN_EXE_day5");
+
+#[test]
+fn test_less_than_eight() {
+    // outputs whether the input is less than 8, and both system ids are
+    let output = run_with_input(EXE, 5, Some("3,9,7,9,10,9,4,9,99,-1,8\n"), &[]);
+    assert_eq!(stdout(&output), "1\n1\n");
+}
+
+#[test]
+fn test_unknown_op_code() {
+    let output = run_with_input(EXE, 5, Some("9,0,99\n"), &[]);
+    assert!(stderr(&output).contains("unknown op code: 9"));
+}
+
+#[test]
+fn test_trace() {
+    let output = run_with_input(
+        EXE,
+        5,
+        Some("1102,6,7,13,101,-42,13,14,4,14,104,8,99,0,0\n"),
+        &["--trace", "--part", "1"],
+    );
+    assert_eq!(
+        stdout(&output),
+        "test 1: mul (immediate, immediate) at 0 gave 42, expected 42: ok\n\
+         diagnostic code 8\n\
+         8\n"
+    );
+}
@@ -0,0 +1,148 @@
+//! Day 6: Universal Orbit Map.
+
+use std::collections::HashMap;
+use std::iter::successors;
+
+/// Stands in for the parent of the object nothing else is orbited by.
+const NONE: u32 = u32::MAX;
+
+/// The orbit map, with each object's name interned to a dense id up front so
+/// the counting works on arrays rather than hashing names.
+pub struct OrbitMap<'a> {
+    ids: HashMap<&'a str, u32>,
+    /// The object each object directly orbits, indexed by id.
+    parents: Vec<u32>,
+}
+
+impl<'a> OrbitMap<'a> {
+    pub fn parse(input: &'a str) -> Self {
+        let mut map = OrbitMap {
+            ids: HashMap::new(),
+            parents: Vec::new(),
+        };
+
+        for line in input.lines().filter(|line| !line.trim().is_empty()) {
+            let mut parts = line.trim().split(')');
+
+            let parent = parts.next().unwrap();
+            let child = parts
+                .next()
+                .unwrap_or_else(|| panic!("orbit must look like A)B, got {:?}", line));
+
+            let parent = map.intern(parent);
+            let child = map.intern(child);
+            map.parents[child as usize] = parent;
+        }
+
+        map
+    }
+
+    fn intern(&mut self, name: &'a str) -> u32 {
+        let parents = &mut self.parents;
+        *self.ids.entry(name).or_insert_with(|| {
+            parents.push(NONE);
+            parents.len() as u32 - 1
+        })
+    }
+
+    /// Returns how many objects each object orbits, directly and indirectly.
+    fn depths(&self) -> Vec<u32> {
+        let mut depths = vec![NONE; self.parents.len()];
+        let mut unknown = Vec::new();
+
+        for id in 0..self.parents.len() as u32 {
+            // climb to an object whose depth is known, then fill in on the way
+            // back down
+            let mut object = id;
+            while object != NONE && depths[object as usize] == NONE {
+                unknown.push(object);
+                object = self.parents[object as usize];
+            }
+
+            let mut depth = match object {
+                NONE => 0,
+                object => depths[object as usize] + 1,
+            };
+            while let Some(object) = unknown.pop() {
+                depths[object as usize] = depth;
+                depth += 1;
+            }
+        }
+
+        depths
+    }
+
+    /// Returns the objects `name` orbits, nearest first.
+    fn ancestors(&self, name: &str) -> impl Iterator<Item = u32> + '_ {
+        let start = self
+            .ids
+            .get(name)
+            .map_or(NONE, |&id| self.parents[id as usize]);
+        successors(Some(start).filter(|&id| id != NONE), move |&id| {
+            Some(self.parents[id as usize]).filter(|&parent| parent != NONE)
+        })
+    }
+}
+
+/// Returns the total number of direct and indirect orbits.
+pub fn part1(map: &OrbitMap) -> usize {
+    map.depths().iter().map(|&depth| depth as usize).sum()
+}
+
+/// Returns the number of orbital transfers needed to get from the object YOU
+/// orbit to the object SAN orbits.
+pub fn part2(map: &OrbitMap) -> usize {
+    let mut your_distance = vec![NONE; map.parents.len()];
+    for (i, object) in map.ancestors("YOU").enumerate() {
+        your_distance[object as usize] = i as u32;
+    }
+
+    let transfers = map
+        .ancestors("SAN")
+        .enumerate()
+        .find_map(|(j, object)| match your_distance[object as usize] {
+            NONE => None,
+            i => Some(i as usize + j),
+        })
+        .expect("YOU and SAN don't orbit a common object");
+
+    transfers
+}
+
+pub fn solve_part1(input: &str) -> usize {
+    part1(&OrbitMap::parse(input))
+}
+
+pub fn solve_part2(input: &str) -> usize {
+    part2(&OrbitMap::parse(input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::inputs::example;
+
+    #[test]
+    fn test_orbit_count() {
+        let map = "COM)B\nB)C\nC)D\nD)E\nE)F\nB)G\nG)H\nD)I\nE)J\nJ)K\nK)L\n";
+        assert_eq!(solve_part1(map), 42);
+    }
+
+    #[test]
+    fn test_transfers() {
+        assert_eq!(solve_part2(&example("day6")), 4);
+    }
+
+    #[test]
+    fn test_interning() {
+        // listed child first, so depths are filled in from the bottom
+        let map = OrbitMap::parse("B)C\nCOM)B\nC)D\n");
+        assert_eq!(map.ids.len(), 4);
+        assert_eq!(map.depths(), vec![1, 2, 0, 3]);
+
+        let ancestors: Vec<u32> = map.ancestors("D").collect();
+        assert_eq!(ancestors, vec![1, 0, 2]);
+        assert_eq!(map.ancestors("COM").count(), 0);
+        assert_eq!(map.ancestors("nobody").count(), 0);
+    }
+}
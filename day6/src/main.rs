@@ -1,36 +1,14 @@
-use std::collections::{HashMap};
-use std::iter::successors;
-use std::fs;
+use common::args::Args;
+use day6::{solve_part1, solve_part2};
 
 fn main() {
-    let input = fs::read_to_string("input").unwrap();
+    let args = Args::parse(6);
+    let input = args.input();
 
-    let graph: HashMap<&str, &str> = input
-        .split("\n")
-        .map(|line| {
-            let mut parts = line.split(")");
-
-            let parent = parts.next().unwrap();
-            let child = parts.next().unwrap();
-
-            (child, parent)
-        })
-        .collect();
-
-    let count = graph.values()
-        .fold(0, |acc, n| acc + successors(graph.get(*n), |n| graph.get(*n)).count()+1);
-
-    println!("{}", count);
-
-    let your_ancestors: Vec<&&str> = successors(graph.get("YOU"), |n| graph.get(*n)).collect();
-    let santas_ancestors: Vec<&&str> = successors(graph.get("SAN"), |n| graph.get(*n)).collect();
-
-    for (i, v) in your_ancestors.iter().enumerate() {
-        for (j, w) in santas_ancestors.iter().enumerate() {
-            if v == w {
-                println!("{}", i+j);
-                return
-            }
-        }
+    if args.wants(1) {
+        args.answer(1, solve_part1(&input));
+    }
+    if args.wants(2) {
+        args.answer(2, solve_part2(&input));
     }
 }
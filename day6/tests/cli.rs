@@ -0,0 +1,16 @@
+use common::inputs::example;
+use common::testing::{run_with_input, stderr, stdout};
+
+const EXE: &str = env!("CARGO_BIN_EXE_day6");
+
+#[test]
+fn test_example() {
+    let output = run_with_input(EXE, 6, Some(&example("day6")), &[]);
+    assert_eq!(stdout(&output), "54\n4\n");
+}
+
+#[test]
+fn test_invalid_orbit() {
+    let output = run_with_input(EXE, 6, Some("COM)B\nB-C\n"), &[]);
+    assert!(stderr(&output).contains("orbit must look like A)B, got \"B-C\""));
+}
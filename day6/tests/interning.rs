@@ -0,0 +1,63 @@
+//! Checks counting orbits on interned ids gives the same answers as counting
+//! them on a map of names, on a synthetic map larger than a real one. The speed
+//! of the two is compared by report's `day6_interning` benchmark.
+
+use common::rng::Rng;
+use day6::{solve_part1, solve_part2};
+use std::collections::HashMap;
+use std::iter::successors;
+
+const OBJECTS: u64 = 20_000;
+const SEED: u64 = 2019;
+
+/// Builds a random tree of orbits around COM, with YOU and SAN somewhere in it.
+fn synthetic_map(rng: &mut Rng) -> String {
+    let name = |id: u64| match id {
+        0 => String::from("COM"),
+        id => format!("O{}", id),
+    };
+
+    let mut orbits: Vec<String> = (1..OBJECTS)
+        .map(|id| format!("{}){}", name(rng.below(id)), name(id)))
+        .collect();
+    orbits.push(format!("{})YOU", name(rng.below(OBJECTS))));
+    orbits.push(format!("{})SAN", name(rng.below(OBJECTS))));
+    rng.shuffle(&mut orbits);
+
+    orbits.join("\n")
+}
+
+/// The answers the way they were found before interning, by hashing names.
+fn by_name(input: &str) -> (usize, usize) {
+    let graph: HashMap<&str, &str> = input
+        .lines()
+        .map(|line| {
+            let mut parts = line.split(')');
+            let parent = parts.next().unwrap();
+            (parts.next().unwrap(), parent)
+        })
+        .collect();
+    let ancestors = |object: &str| -> Vec<&str> {
+        successors(graph.get(object), |n| graph.get(*n))
+            .cloned()
+            .collect()
+    };
+
+    let orbits = graph.values().map(|n| ancestors(n).len() + 1).sum();
+
+    let (yours, santas) = (ancestors("YOU"), ancestors("SAN"));
+    let transfers = yours
+        .iter()
+        .enumerate()
+        .find_map(|(i, v)| santas.iter().position(|w| v == w).map(|j| i + j))
+        .unwrap();
+
+    (orbits, transfers)
+}
+
+#[test]
+fn test_interning_matches_names() {
+    let input = synthetic_map(&mut Rng::seeded(SEED));
+
+    assert_eq!((solve_part1(&input), solve_part2(&input)), by_name(&input));
+}
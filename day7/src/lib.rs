@@ -0,0 +1,719 @@
+//! Day 7: Amplification Circuit.
+
+use common::parse::parse_csv;
+use common::permutations::k_permutations;
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicIsize, Ordering};
+use std::sync::mpsc::{channel, Receiver, RecvError, Sender};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Copy, Clone)]
+enum Mode {
+    Position,
+    Immediate,
+}
+
+impl FromStr for Mode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        Ok(match s {
+            "1" => Self::Immediate,
+            _ => Self::Position,
+        })
+    }
+}
+
+#[derive(Debug)]
+enum Instruction {
+    Add(Mode, Mode, Mode),
+    Mul(Mode, Mode, Mode),
+    Input(Mode),
+    Output(Mode),
+    JumpTrue(Mode, Mode),
+    JumpFalse(Mode, Mode),
+    LessThan(Mode, Mode, Mode),
+    Equals(Mode, Mode, Mode),
+}
+
+impl Instruction {
+    pub fn parameters(&self) -> usize {
+        match *self {
+            Instruction::Add(_, _, _) | Instruction::Mul(_, _, _) => 3,
+            Instruction::Input(_) | Instruction::Output(_) => 1,
+            Instruction::JumpTrue(_, _) | Instruction::JumpFalse(_, _) => 2,
+            Instruction::LessThan(_, _, _) | Instruction::Equals(_, _, _) => 3,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum IntcodeError {
+    OpCode(Option<char>),
+    Input(RecvError),
+}
+
+impl From<RecvError> for IntcodeError {
+    fn from(error: RecvError) -> Self {
+        IntcodeError::Input(error)
+    }
+}
+
+impl fmt::Display for IntcodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            IntcodeError::OpCode(code) => match code {
+                Some(code) => write!(f, "unknown op code: {}", code),
+                None => write!(f, "empty op code"),
+            },
+            IntcodeError::Input(recv_err) => write!(f, "unable to get input: {}", recv_err),
+        }
+    }
+}
+
+impl FromStr for Instruction {
+    type Err = IntcodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let chars = &mut s.chars().rev();
+
+        let op_code = match chars.next() {
+            Some(op_code) => op_code,
+            None => return Err(IntcodeError::OpCode(None)),
+        };
+
+        // skip the zero in the op code since they're two-padded and we only care about
+        // the first digit
+        chars.next();
+
+        // always take three modes, default to position
+        let mut modes = [Mode::Position; 3];
+
+        for mode in modes.iter_mut() {
+            if let Some(ch) = chars.next() {
+                if let Ok(parsed) = Mode::from_str(&ch.to_string()) {
+                    *mode = parsed;
+                }
+            }
+        }
+
+        match op_code {
+            '1' => Ok(Instruction::Add(modes[0], modes[1], modes[2])),
+            '2' => Ok(Instruction::Mul(modes[0], modes[1], modes[2])),
+            '3' => Ok(Instruction::Input(modes[0])),
+            '4' => Ok(Instruction::Output(modes[0])),
+            '5' => Ok(Instruction::JumpTrue(modes[0], modes[1])),
+            '6' => Ok(Instruction::JumpFalse(modes[0], modes[1])),
+            '7' => Ok(Instruction::LessThan(modes[0], modes[1], modes[2])),
+            '8' => Ok(Instruction::Equals(modes[0], modes[1], modes[2])),
+            _ => Err(IntcodeError::OpCode(Some(op_code))),
+        }
+    }
+}
+
+pub fn evaluate(
+    memory: &mut [i64],
+    input: Receiver<i64>,
+    output: Sender<i64>,
+) -> Result<Option<i64>, IntcodeError> {
+    run(memory, || input.recv(), |value| output.send(value).is_ok())
+}
+
+/// Runs the program, getting input from `recv` and giving output to `send`,
+/// which returns false once nobody's listening.
+fn run(
+    memory: &mut [i64],
+    mut recv: impl FnMut() -> Result<i64, RecvError>,
+    mut send: impl FnMut(i64) -> bool,
+) -> Result<Option<i64>, IntcodeError> {
+    let mut pc = 0;
+    let mut last_output = None;
+
+    while memory[pc] != 99 {
+        let instr = Instruction::from_str(&memory[pc].to_string())?;
+        let params = instr.parameters();
+
+        match instr {
+            Instruction::Add(a, b, location)
+            | Instruction::Mul(a, b, location)
+            | Instruction::LessThan(a, b, location)
+            | Instruction::Equals(a, b, location) => {
+                let a = match a {
+                    Mode::Position => memory[memory[pc + 1] as usize],
+                    Mode::Immediate => memory[pc + 1],
+                };
+                let b = match b {
+                    Mode::Position => memory[memory[pc + 2] as usize],
+                    Mode::Immediate => memory[pc + 2],
+                };
+
+                let v = match instr {
+                    Instruction::Add(_, _, _) => a + b,
+                    Instruction::Mul(_, _, _) => a * b,
+                    Instruction::LessThan(_, _, _) => (a < b) as i64,
+                    _ => (a == b) as i64,
+                };
+
+                let location = match location {
+                    Mode::Position => memory[pc + 3] as usize,
+                    Mode::Immediate => pc + 3,
+                };
+
+                memory[location] = v;
+            }
+            Instruction::Input(location) | Instruction::Output(location) => {
+                let location = match location {
+                    Mode::Position => memory[pc + 1] as usize,
+                    Mode::Immediate => pc + 1,
+                };
+
+                if let Instruction::Input(_) = instr {
+                    let v = recv()?;
+                    memory[location] = v;
+                } else {
+                    let out = memory[location];
+                    last_output = Some(out);
+
+                    // any error means amplification is done since nobody's listening,
+                    // we should return the last output
+                    if !send(out) {
+                        return Ok(Some(out));
+                    }
+                }
+            }
+            Instruction::JumpTrue(a, jmp) | Instruction::JumpFalse(a, jmp) => {
+                let a = match a {
+                    Mode::Position => memory[memory[pc + 1] as usize],
+                    Mode::Immediate => memory[pc + 1],
+                };
+                let jmp = match jmp {
+                    Mode::Position => memory[memory[pc + 2] as usize],
+                    Mode::Immediate => memory[pc + 2],
+                };
+
+                let cond = match instr {
+                    Instruction::JumpTrue(_, _) => a != 0,
+                    _ => a == 0,
+                };
+
+                if cond {
+                    pc = jmp as usize;
+                    continue;
+                }
+            }
+        }
+
+        pc += params + 1;
+    }
+
+    Ok(last_output)
+}
+
+#[derive(Debug)]
+pub enum AmplifierError {
+    Stage(usize, IntcodeError),
+    Panicked(usize),
+    NoSignal,
+}
+
+/// Returns the conventional letter name (A, B, C, ...) of the amplifier at the
+/// given position in the chain.
+pub fn stage_name(stage: usize) -> String {
+    if stage < 26 {
+        ((b'A' + stage as u8) as char).to_string()
+    } else {
+        stage.to_string()
+    }
+}
+
+impl fmt::Display for AmplifierError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AmplifierError::Stage(stage, err) => {
+                write!(f, "amplifier {} failed: {}", stage_name(*stage), err)
+            }
+            AmplifierError::Panicked(stage) => {
+                write!(f, "amplifier {} panicked", stage_name(*stage))
+            }
+            AmplifierError::NoSignal => write!(f, "no signal reached the thrusters"),
+        }
+    }
+}
+
+/// How one amplifier in a chain spent its run.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StageMetrics {
+    /// Signals read, including the phase setting.
+    pub received: usize,
+    /// Signals written to the next amplifier.
+    pub sent: usize,
+    /// Time spent waiting for input.
+    pub blocked: Duration,
+    /// Time from starting to halting.
+    pub elapsed: Duration,
+    /// Most signals waiting in the amplifier's input channel at once.
+    pub max_queue: usize,
+}
+
+/// Counts the signals waiting in a channel, which mpsc doesn't expose. A signal
+/// is counted once it's been sent, so it can be received first and the depth
+/// can briefly go negative.
+#[derive(Default)]
+struct Queue {
+    depth: AtomicIsize,
+    max: AtomicIsize,
+}
+
+impl Queue {
+    fn push(&self) {
+        let depth = self.depth.fetch_add(1, Ordering::SeqCst) + 1;
+        self.max.fetch_max(depth, Ordering::SeqCst);
+    }
+
+    fn pop(&self) {
+        self.depth.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Runs an amplifier like `evaluate`, keeping track of its metrics.
+fn evaluate_metered(
+    memory: &mut [i64],
+    (input, queue): (Receiver<i64>, &Queue),
+    (output, next): (Sender<i64>, &Queue),
+) -> (Result<Option<i64>, IntcodeError>, StageMetrics) {
+    let start = Instant::now();
+    let (mut received, mut blocked, mut sent) = (0, Duration::default(), 0);
+
+    let result = run(
+        memory,
+        || {
+            let waiting = Instant::now();
+            let value = input.recv()?;
+            blocked += waiting.elapsed();
+            received += 1;
+            queue.pop();
+            Ok(value)
+        },
+        |value| {
+            if output.send(value).is_err() {
+                return false;
+            }
+            next.push();
+            sent += 1;
+            true
+        },
+    );
+
+    let metrics = StageMetrics {
+        received,
+        sent,
+        blocked,
+        elapsed: start.elapsed(),
+        // filled in once every amplifier has stopped sending
+        max_queue: 0,
+    };
+    (result, metrics)
+}
+
+/// Runs one amplifier per phase setting in a feedback loop and returns the signal
+/// that ends up being sent to the thrusters. Without a feedback loop (phase
+/// settings 0-4) the programs simply halt after their first output, so this also
+/// covers the serial chain.
+pub fn thruster_signal(memory: &[i64], settings: &[i64]) -> Result<i64, AmplifierError> {
+    thruster_signal_metered(memory, settings).map(|(signal, _)| signal)
+}
+
+/// Like `thruster_signal`, but also returns how each amplifier spent its run, to
+/// see which one holds up the rest.
+pub fn thruster_signal_metered(
+    memory: &[i64],
+    settings: &[i64],
+) -> Result<(i64, Vec<StageMetrics>), AmplifierError> {
+    if settings.is_empty() {
+        return Err(AmplifierError::NoSignal);
+    }
+
+    let (senders, receivers): (Vec<Sender<i64>>, Vec<Receiver<i64>>) =
+        settings.iter().map(|_| channel::<i64>()).unzip();
+    let queues: Vec<Arc<Queue>> = settings.iter().map(|_| Arc::default()).collect();
+
+    for ((sender, queue), setting) in senders.iter().zip(&queues).zip(settings) {
+        queue.push();
+        sender.send(*setting).unwrap();
+    }
+    queues[0].push();
+    senders[0].send(0).unwrap();
+
+    // each amplifier reads from its own channel and writes to the next one, with
+    // the last amplifier feeding back into the first
+    let mut outputs = senders.into_iter().zip(queues.clone()).cycle().skip(1);
+
+    let handles: Vec<thread::JoinHandle<_>> = receivers
+        .into_iter()
+        .zip(queues.clone())
+        .map(|(input, queue)| {
+            let (output, next) = outputs.next().unwrap();
+            let mut memory = memory.to_vec();
+            thread::spawn(move || evaluate_metered(&mut memory, (input, &queue), (output, &next)))
+        })
+        .collect();
+    drop(outputs);
+
+    let mut results = Vec::new();
+    let mut metrics = Vec::new();
+    for (stage, handle) in handles.into_iter().enumerate() {
+        match handle.join() {
+            Ok((result, stage_metrics)) => {
+                results.push((stage, result));
+                metrics.push(StageMetrics {
+                    max_queue: queues[stage].max.load(Ordering::SeqCst) as usize,
+                    ..stage_metrics
+                });
+            }
+            Err(_) => return Err(AmplifierError::Panicked(stage)),
+        }
+    }
+
+    // a failing amplifier hangs up its channels, which makes every amplifier after
+    // it fail to get input, so blame the first one that failed for another reason
+    let first_failure = results
+        .iter()
+        .position(
+            |(_, result)| matches!(result, Err(err) if !matches!(err, IntcodeError::Input(_))),
+        )
+        .or_else(|| results.iter().position(|(_, result)| result.is_err()));
+
+    if let Some(index) = first_failure {
+        let (stage, result) = results.swap_remove(index);
+        return Err(AmplifierError::Stage(stage, result.unwrap_err()));
+    }
+
+    match results.pop() {
+        Some((_, Ok(Some(signal)))) => Ok((signal, metrics)),
+        _ => Err(AmplifierError::NoSignal),
+    }
+}
+
+#[derive(Debug)]
+pub enum SearchError {
+    /// A chain of amplifiers failed with these phase settings.
+    Run {
+        settings: Vec<i64>,
+        error: AmplifierError,
+    },
+    /// There are no amplifiers, or more than there are phases to give them.
+    Amplifiers { amplifiers: usize, phases: usize },
+}
+
+impl fmt::Display for SearchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SearchError::Run { settings, error } => {
+                write!(f, "phase settings {:?}: {}", settings, error)
+            }
+            SearchError::Amplifiers { amplifiers, phases } => write!(
+                f,
+                "can't give {} amplifiers distinct phase settings from {} phases",
+                amplifiers, phases
+            ),
+        }
+    }
+}
+
+/// Checks there's at least one amplifier and a distinct phase for each.
+fn check_chain(amplifiers: usize, phases: &[i64]) -> Result<(), SearchError> {
+    if amplifiers == 0 || amplifiers > phases.len() {
+        return Err(SearchError::Amplifiers {
+            amplifiers,
+            phases: phases.len(),
+        });
+    }
+    Ok(())
+}
+
+/// Tries every way of assigning distinct phase settings from `phases` to a chain of
+/// `amplifiers` amplifiers, and returns the highest thruster signal along with the
+/// phase settings that produced it.
+pub fn max_thruster_signal(
+    memory: &[i64],
+    amplifiers: usize,
+    phases: &[i64],
+) -> Result<(i64, Vec<i64>), SearchError> {
+    check_chain(amplifiers, phases)?;
+    let permutations: Vec<Vec<i64>> = k_permutations(phases, amplifiers).collect();
+
+    let signals: Result<Vec<(i64, Vec<i64>)>, SearchError> = permutations
+        .into_par_iter()
+        .map(|settings| match thruster_signal(memory, &settings) {
+            Ok(signal) => Ok((signal, settings)),
+            Err(error) => Err(SearchError::Run { settings, error }),
+        })
+        .collect();
+
+    signals?
+        .into_iter()
+        .max_by_key(|(signal, _)| *signal)
+        .ok_or(SearchError::Run {
+            settings: Vec::new(),
+            error: AmplifierError::NoSignal,
+        })
+}
+
+/// Runs one amplifier on its own with its phase setting and `signal` as input,
+/// and returns the first signal it sends on.
+fn amplify(memory: &[i64], phase: i64, signal: i64) -> Result<Option<i64>, IntcodeError> {
+    let mut memory = memory.to_vec();
+    let mut inputs = vec![phase, signal].into_iter();
+
+    // stop at the first output, since in series nothing is sent back
+    run(&mut memory, || inputs.next().ok_or(RecvError), |_| false)
+}
+
+/// The outcome of `max_serial_signal`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SerialSearch {
+    pub signal: i64,
+    pub settings: Vec<i64>,
+    /// How many times an amplifier's program was run.
+    pub runs: usize,
+}
+
+/// Like `max_thruster_signal` for amplifiers wired in series. Each amplifier's
+/// output depends only on the phase settings up to and including its own, so
+/// outputs are cached by that prefix and every distinct prefix is run once:
+/// 325 runs rather than 600 for five amplifiers and five phases.
+pub fn max_serial_signal(
+    memory: &[i64],
+    amplifiers: usize,
+    phases: &[i64],
+) -> Result<SerialSearch, SearchError> {
+    check_chain(amplifiers, phases)?;
+    let mut outputs: HashMap<Vec<i64>, i64> = HashMap::new();
+    let mut runs = 0;
+    let mut best: Option<(i64, Vec<i64>)> = None;
+
+    for settings in k_permutations(phases, amplifiers) {
+        let mut signal = 0;
+        for stage in 0..settings.len() {
+            let prefix = &settings[..=stage];
+            signal = match outputs.get(prefix) {
+                Some(&output) => output,
+                None => {
+                    runs += 1;
+                    let fail = |error| SearchError::Run {
+                        settings: prefix.to_vec(),
+                        error,
+                    };
+                    let output = amplify(memory, settings[stage], signal)
+                        .map_err(|err| fail(AmplifierError::Stage(stage, err)))?
+                        .ok_or_else(|| fail(AmplifierError::NoSignal))?;
+                    outputs.insert(prefix.to_vec(), output);
+                    output
+                }
+            };
+        }
+
+        // ties go to the later settings, like `max_thruster_signal`
+        if best.as_ref().is_none_or(|(max, _)| signal >= *max) {
+            best = Some((signal, settings));
+        }
+    }
+
+    let (signal, settings) = best.ok_or(SearchError::Run {
+        settings: Vec::new(),
+        error: AmplifierError::NoSignal,
+    })?;
+    Ok(SerialSearch {
+        signal,
+        settings,
+        runs,
+    })
+}
+
+/// Phase settings for amplifiers wired in series.
+pub const SERIAL_PHASES: [i64; 5] = [0, 1, 2, 3, 4];
+/// Phase settings for amplifiers wired in a feedback loop.
+pub const FEEDBACK_PHASES: [i64; 5] = [5, 6, 7, 8, 9];
+
+/// Parses the amplifier controller software.
+pub fn program(input: &str) -> Vec<i64> {
+    parse_csv(input).unwrap_or_else(|err| panic!("{}", err))
+}
+
+/// Returns the highest signal that can be sent to the thrusters by amplifiers
+/// wired in series.
+pub fn part1(memory: &[i64]) -> i64 {
+    match max_serial_signal(memory, SERIAL_PHASES.len(), &SERIAL_PHASES) {
+        Ok(search) => search.signal,
+        Err(err) => panic!("{}", err),
+    }
+}
+
+/// Returns the highest signal that can be sent to the thrusters by amplifiers
+/// wired in a feedback loop.
+pub fn part2(memory: &[i64]) -> i64 {
+    match max_thruster_signal(memory, FEEDBACK_PHASES.len(), &FEEDBACK_PHASES) {
+        Ok((signal, _)) => signal,
+        Err(err) => panic!("{}", err),
+    }
+}
+
+pub fn solve_part1(input: &str) -> i64 {
+    part1(&program(input))
+}
+
+pub fn solve_part2(input: &str) -> i64 {
+    part2(&program(input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(program: &str) -> Vec<i64> {
+        parse_csv(program).unwrap()
+    }
+
+    #[test]
+    fn test_serial_examples() {
+        let examples = [
+            ("3,15,3,16,1002,16,10,16,1,16,15,15,4,15,99,0,0", 43210, vec![4, 3, 2, 1, 0]),
+            (
+                "3,23,3,24,1002,24,10,24,1002,23,-1,23,101,5,23,23,1,24,23,23,4,23,99,0,0",
+                54321,
+                vec![0, 1, 2, 3, 4],
+            ),
+            (
+                "3,31,3,32,1002,32,10,32,1001,31,-2,31,1007,31,0,33,1002,33,7,33,1,33,31,31,1,32,31,31,4,31,99,0,0,0",
+                65210,
+                vec![1, 0, 4, 3, 2],
+            ),
+        ];
+
+        for (program, signal, settings) in examples.iter() {
+            let result = max_thruster_signal(&parse(program), 5, &SERIAL_PHASES).unwrap();
+            assert_eq!(result, (*signal, settings.clone()));
+        }
+    }
+
+    #[test]
+    fn test_feedback_examples() {
+        let examples = [
+            (
+                "3,26,1001,26,-4,26,3,27,1002,27,2,27,1,27,26,27,4,27,1001,28,-1,28,1005,28,6,99,0,0,5",
+                139629729,
+                vec![9, 8, 7, 6, 5],
+            ),
+            (
+                "3,52,1001,52,-5,52,3,53,1,52,56,54,1007,54,5,55,1005,55,26,1001,54,-5,54,1105,1,12,1,53,54,53,1008,54,0,55,1001,55,1,55,2,53,55,53,4,53,1001,56,-1,56,1005,56,6,99,0,0,0,0,10",
+                18216,
+                vec![9, 7, 8, 5, 6],
+            ),
+        ];
+
+        for (program, signal, settings) in examples.iter() {
+            let result = max_thruster_signal(&parse(program), 5, &FEEDBACK_PHASES).unwrap();
+            assert_eq!(result, (*signal, settings.clone()));
+        }
+    }
+
+    #[test]
+    fn test_serial_search() {
+        let examples = [
+            "3,15,3,16,1002,16,10,16,1,16,15,15,4,15,99,0,0",
+            "3,23,3,24,1002,24,10,24,1002,23,-1,23,101,5,23,23,1,24,23,23,4,23,99,0,0",
+        ];
+
+        for program in examples.iter() {
+            let program = parse(program);
+            let search = max_serial_signal(&program, 5, &SERIAL_PHASES).unwrap();
+            let (signal, _) = max_thruster_signal(&program, 5, &SERIAL_PHASES).unwrap();
+            assert_eq!(search.signal, signal);
+            assert_eq!(thruster_signal(&program, &search.settings).unwrap(), signal);
+
+            // 5 + 5*4 + 5*4*3 + 5*4*3*2 + 5*4*3*2*1 prefixes, instead of 5 runs
+            // for each of the 120 orders
+            assert_eq!(search.runs, 325);
+        }
+
+        let err = max_serial_signal(&parse("99"), 2, &SERIAL_PHASES).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "phase settings [0]: no signal reached the thrusters"
+        );
+    }
+
+    #[test]
+    fn test_solve() {
+        assert_eq!(
+            solve_part1("3,15,3,16,1002,16,10,16,1,16,15,15,4,15,99,0,0"),
+            43210
+        );
+        assert_eq!(
+            solve_part2(
+                "3,26,1001,26,-4,26,3,27,1002,27,2,27,1,27,26,27,4,27,1001,28,-1,28,1005,28,6,99,0,0,5"
+            ),
+            139629729
+        );
+    }
+
+    #[test]
+    fn test_metrics() {
+        let program = parse(
+            "3,26,1001,26,-4,26,3,27,1002,27,2,27,1,27,26,27,4,27,1001,28,-1,28,1005,28,6,99,0,0,5",
+        );
+        let (signal, metrics) = thruster_signal_metered(&program, &[9, 8, 7, 6, 5]).unwrap();
+        assert_eq!(signal, 139629729);
+
+        // five rounds of the loop, with the last signal going to the thrusters.
+        // E's last send only fails if A has already hung up, which is a race.
+        let counts: Vec<(usize, usize)> = metrics.iter().map(|m| (m.received, m.sent)).collect();
+        assert_eq!(counts[..4], [(6, 5), (6, 5), (6, 5), (6, 5)]);
+        assert!(
+            counts[4] == (6, 4) || counts[4] == (6, 5),
+            "{:?}",
+            counts[4]
+        );
+
+        // A starts with its phase setting and the first signal waiting
+        assert_eq!(metrics[0].max_queue, 2);
+        for stage in &metrics {
+            assert!(stage.blocked <= stage.elapsed);
+            assert!((1..=2).contains(&stage.max_queue));
+        }
+    }
+
+    #[test]
+    fn test_failing_amplifier() {
+        // opcode 0 is invalid, so the first amplifier to run it fails
+        let err = thruster_signal(&parse("3,9,3,10,4,10,0,99,0,0,0"), &[1, 2]).unwrap_err();
+        assert!(matches!(
+            err,
+            AmplifierError::Stage(0, IntcodeError::OpCode(Some('0')))
+        ));
+    }
+
+    #[test]
+    fn test_chain_sizes() {
+        let memory = parse("3,15,3,16,1002,16,10,16,1,16,15,15,4,15,99,0,0");
+        for amplifiers in [0, 6] {
+            let err = max_thruster_signal(&memory, amplifiers, &SERIAL_PHASES).unwrap_err();
+            assert!(matches!(err, SearchError::Amplifiers { phases: 5, .. }));
+            let err = max_serial_signal(&memory, amplifiers, &SERIAL_PHASES).unwrap_err();
+            assert!(matches!(err, SearchError::Amplifiers { phases: 5, .. }));
+        }
+        assert_eq!(
+            max_serial_signal(&memory, 0, &[]).unwrap_err().to_string(),
+            "can't give 0 amplifiers distinct phase settings from 0 phases"
+        );
+        assert!(matches!(
+            thruster_signal(&memory, &[]),
+            Err(AmplifierError::NoSignal)
+        ));
+    }
+}
@@ -0,0 +1,8 @@
+//! Time budgets for the personal input, run with
+//! `cargo test --release -- --ignored`.
+
+common::budget_tests!(
+    7,
+    day7::solve_part1 => 250,
+    day7::solve_part2 => 250,
+);
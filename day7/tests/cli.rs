@@ -0,0 +1,72 @@
+use common::require_input;
+use common::testing::{run_with_input, stderr, stdout};
+
+const EXE: &str = env!("CARGO_BIN_EXE_day7");
+
+#[test]
+fn test_input() {
+    let input = require_input!(7);
+    let output = run_with_input(EXE, 7, Some(&input), &[]);
+    assert_eq!(
+        stdout(&output),
+        "262086\nphase settings: [2, 1, 4, 0, 3]\n\
+         5371621\nphase settings: [5, 7, 6, 8, 9]\n"
+    );
+}
+
+#[test]
+fn test_silent_amplifiers() {
+    // halts without ever reading its phase setting or producing a signal
+    let output = run_with_input(EXE, 7, Some("99\n"), &[]);
+    let err = stderr(&output);
+    assert!(err.starts_with("phase settings "), "{}", err);
+    assert!(err.contains("no signal reached the thrusters"), "{}", err);
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn test_invalid_program() {
+    let output = run_with_input(EXE, 7, Some("3,0,x"), &[]);
+    let err = stderr(&output);
+    assert!(
+        err.contains("day7.txt: line 1, column 5: can't parse"),
+        "{}",
+        err
+    );
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn test_metrics() {
+    let program =
+        "3,26,1001,26,-4,26,3,27,1002,27,2,27,1,27,26,27,4,27,1001,28,-1,28,1005,28,6,99,0,0,5\n";
+    let output = run_with_input(EXE, 7, Some(program), &["--metrics"]);
+    let out = stdout(&output);
+    let lines: Vec<&str> = out.lines().collect();
+
+    // each search prints a table, the feedback loop's last
+    let table = lines
+        .iter()
+        .rposition(|line| line.starts_with("stage "))
+        .unwrap();
+    assert_eq!(lines[table - 2], "139629729");
+    assert_eq!(lines.len(), table + 6);
+    assert!(lines[table + 1].starts_with("A             6     5          2  "));
+    // whether E's last send fails depends on how soon A hangs up
+    let last = lines[table + 5];
+    assert!(
+        last.starts_with("E             6     4  ") || last.starts_with("E             6     5  "),
+        "{}",
+        last
+    );
+
+    // the serial search reports how many amplifier runs sharing prefixes saved
+    assert!(lines.contains(&"amplifier runs: 325 (600 without sharing prefixes)"));
+}
+
+#[test]
+fn test_quiet() {
+    let input = require_input!(7);
+    let output = run_with_input(EXE, 7, Some(&input), &["--quiet"]);
+    assert_eq!(stdout(&output), "262086\n5371621\n");
+}
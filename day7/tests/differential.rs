@@ -0,0 +1,69 @@
+use common::parse::parse_csv;
+use common::require_input;
+use day7::evaluate;
+use intcode::diff::{compare, Engine, Interpreter, Run};
+use std::sync::mpsc::channel;
+
+/// The amplifier's own interpreter, from before relative mode existed.
+struct Amplifier;
+
+impl Engine for Amplifier {
+    fn name(&self) -> &str {
+        "amplifier"
+    }
+
+    fn run(&self, program: &[i64], input: &[i64]) -> Run {
+        let (send_in, recv_in) = channel();
+        for value in input {
+            send_in.send(*value).unwrap();
+        }
+        drop(send_in);
+        let (send_out, recv_out) = channel();
+
+        let mut memory = program.to_vec();
+        let error = evaluate(&mut memory, recv_in, send_out)
+            .err()
+            .map(|err| err.to_string());
+
+        Run::new(recv_out.try_iter().collect(), (0..).zip(memory), error)
+    }
+}
+
+/// Checks that both interpreters agree on every phase setting with a few input
+/// signals. Feedback phases ask for more input than they're given, so those
+/// runs check that both fail the same way.
+fn assert_agree(program: &[i64]) {
+    for phase in 0..10 {
+        for signal in &[0, 1, 12345] {
+            if let Err(divergence) = compare(&Interpreter, &Amplifier, program, &[phase, *signal]) {
+                panic!(
+                    "{} and {} diverge with phase {} and signal {}: {}",
+                    Interpreter.name(),
+                    Amplifier.name(),
+                    phase,
+                    signal,
+                    divergence
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_examples() {
+    let examples = [
+        "3,15,3,16,1002,16,10,16,1,16,15,15,4,15,99,0,0",
+        "3,31,3,32,1002,32,10,32,1001,31,-2,31,1007,31,0,33,1002,33,7,33,1,33,31,31,1,32,31,31,4,31,99,0,0,0",
+        "3,26,1001,26,-4,26,3,27,1002,27,2,27,1,27,26,27,4,27,1001,28,-1,28,1005,28,6,99,0,0,5",
+    ];
+
+    for program in examples.iter() {
+        assert_agree(&parse_csv::<i64>(program).unwrap());
+    }
+}
+
+#[test]
+fn test_input() {
+    let input = require_input!(7);
+    assert_agree(&parse_csv::<i64>(&input).unwrap());
+}
@@ -0,0 +1,111 @@
+//! Day 8: Space Image Format.
+
+use common::grid::Grid;
+use common::ocr;
+
+pub const WIDTH: usize = 25;
+pub const HEIGHT: usize = 6;
+
+/// Splits an image of the given size into its layers of digits.
+pub fn layers(input: &str, width: usize, height: usize) -> Vec<Vec<u32>> {
+    let digits: Vec<u32> = input
+        .trim()
+        .chars()
+        .map(|pixel| {
+            pixel
+                .to_digit(10)
+                .unwrap_or_else(|| panic!("invalid pixel: {:?}", pixel))
+        })
+        .collect();
+
+    digits
+        .chunks(width * height)
+        .map(|layer| layer.to_vec())
+        .collect()
+}
+
+/// Returns the number of 1 digits multiplied by the number of 2 digits in the
+/// layer with the fewest 0 digits.
+pub fn part1(layers: &[Vec<u32>]) -> usize {
+    let count = |layer: &[u32], digit| layer.iter().filter(|&&d| d == digit).count();
+
+    let layer = layers
+        .iter()
+        .min_by_key(|layer| count(layer, 0))
+        .expect("image has no layers");
+
+    count(layer, 1) * count(layer, 2)
+}
+
+/// Stacks the layers of an image of the given size and returns whether each
+/// visible pixel is lit, i.e. not black.
+pub fn stack(layers: &[Vec<u32>], width: usize, height: usize) -> Grid<bool> {
+    let mut final_image = vec![2; width * height];
+
+    for layer in layers {
+        for (position, &digit) in layer.iter().enumerate() {
+            if final_image[position] == 2 && digit != 2 {
+                final_image[position] = digit;
+            }
+        }
+    }
+
+    let pixels = final_image.into_iter().map(|pixel| pixel != 0).collect();
+    Grid::from_vec(width, height, pixels).unwrap()
+}
+
+/// Like `stack`, on the image's digits.
+pub fn image(input: &str, width: usize, height: usize) -> Grid<bool> {
+    stack(&layers(input, width, height), width, height)
+}
+
+/// Draws the image one line per row, with lit pixels as `lit` and the rest as
+/// spaces.
+pub fn render(image: &Grid<bool>, lit: char) -> String {
+    image
+        .rows()
+        .map(|row| {
+            row.iter()
+                .map(|&pixel| if pixel { lit } else { ' ' })
+                .collect::<String>()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Stacks the layers of an image of the given size and returns the visible
+/// pixels, one line per row, with white pixels drawn as `0`.
+pub fn decode(input: &str, width: usize, height: usize) -> String {
+    render(&image(input, width, height), '0')
+}
+
+/// Returns the password the decoded image spells out.
+pub fn part2(layers: &[Vec<u32>]) -> String {
+    ocr::read(&stack(layers, WIDTH, HEIGHT))
+}
+
+pub fn solve_part1(input: &str) -> usize {
+    part1(&layers(input, WIDTH, HEIGHT))
+}
+
+pub fn solve_part2(input: &str) -> String {
+    part2(&layers(input, WIDTH, HEIGHT))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::require_input;
+
+    #[test]
+    fn test_decode_example() {
+        insta::assert_snapshot!(decode("0222112222120000", 2, 2));
+    }
+
+    #[test]
+    fn test_decode_input() {
+        let input = require_input!(8);
+        insta::assert_snapshot!(decode(&input, WIDTH, HEIGHT));
+        assert_eq!(solve_part2(&input), "CYUAH");
+    }
+}
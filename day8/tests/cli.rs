@@ -0,0 +1,61 @@
+use common::require_input;
+use common::testing::{run_with_input, stderr, stdout};
+
+const EXE: &str = env!("CARGO_BIN_EXE_day8");
+
+#[test]
+fn test_input() {
+    let input = require_input!(8);
+    let output = stdout(&run_with_input(EXE, 8, Some(&input), &[]));
+    assert_eq!(output, "2500\nCYUAH\n");
+}
+
+#[test]
+fn test_render() {
+    let input = require_input!(8);
+    let output = stdout(&run_with_input(EXE, 8, Some(&input), &["--render"]));
+
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines[0], "2500");
+    assert_eq!(lines[1], "CYUAH");
+    assert_eq!(lines[2], "");
+    assert_eq!(lines.len(), 9);
+    assert!(lines[3..].iter().all(|line| line.chars().count() == 25));
+    assert!(lines[3].starts_with(" ██  █   ██"));
+}
+
+#[test]
+fn test_png() {
+    let input = require_input!(8);
+    let path = std::env::temp_dir().join(format!("day8-{}.png", std::process::id()));
+    let output = run_with_input(EXE, 8, Some(&input), &["--png", path.to_str().unwrap()]);
+    stdout(&output);
+
+    let png = std::fs::read(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert!(png.starts_with(b"\x89PNG"));
+}
+
+#[test]
+fn test_invalid_pixel() {
+    let output = run_with_input(EXE, 8, Some("012x"), &[]);
+    assert!(stderr(&output).contains("invalid pixel: 'x'"));
+}
+
+#[test]
+fn test_quiet() {
+    let input = require_input!(8);
+    let output = run_with_input(EXE, 8, Some(&input), &["--quiet"]);
+    assert_eq!(stdout(&output), "2500\nCYUAH\n");
+}
+
+#[test]
+fn test_json() {
+    // the count is a number and the letters read off the image are text
+    let input = require_input!(8);
+    let output = run_with_input(EXE, 8, Some(&input), &["--json"]);
+    assert_eq!(
+        stdout(&output),
+        "{\"day\":8,\"part\":1,\"answer\":2500}\n{\"day\":8,\"part\":2,\"answer\":\"CYUAH\"}\n"
+    );
+}
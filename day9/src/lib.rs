@@ -0,0 +1,147 @@
+//! Day 9: Sensor Boost.
+
+use common::parse::parse_csv;
+use intcode::{Intcode, IntcodeError};
+use std::fmt;
+
+/// BOOST input value that runs the self-test.
+pub const TEST_MODE: i64 = 1;
+/// BOOST input value that runs the program in sensor boost mode.
+pub const SENSOR_BOOST_MODE: i64 = 2;
+
+/// Why the BOOST program didn't produce an answer.
+#[derive(Debug, PartialEq, Eq)]
+pub enum BoostError {
+    Program(IntcodeError),
+    NoOutput,
+}
+
+impl From<IntcodeError> for BoostError {
+    fn from(error: IntcodeError) -> Self {
+        BoostError::Program(error)
+    }
+}
+
+impl fmt::Display for BoostError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BoostError::Program(err) => write!(f, "BOOST program failed: {}", err),
+            BoostError::NoOutput => write!(f, "BOOST program produced no output"),
+        }
+    }
+}
+
+/// What the BOOST program output: its answer last, after anything it reported
+/// along the way.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Boost {
+    /// In test mode, every opcode the self-test thinks is malfunctioning.
+    pub reported: Vec<i64>,
+    pub answer: i64,
+}
+
+/// Runs the BOOST program with the given mode as its only input and returns
+/// everything it outputs.
+pub fn run(program: &[i64], mode: i64) -> Result<Vec<i64>, IntcodeError> {
+    Intcode::run_with_inputs(program, &[mode])
+}
+
+/// Runs the BOOST program in the given mode and splits off its answer.
+pub fn boost(program: &[i64], mode: i64) -> Result<Boost, BoostError> {
+    let mut reported = run(program, mode)?;
+    let answer = reported.pop().ok_or(BoostError::NoOutput)?;
+    Ok(Boost { reported, answer })
+}
+
+fn solve(program: &[i64], mode: i64) -> i64 {
+    boost(program, mode)
+        .unwrap_or_else(|err| panic!("{}", err))
+        .answer
+}
+
+/// Parses the BOOST program.
+pub fn program(input: &str) -> Vec<i64> {
+    parse_csv(input).unwrap_or_else(|err| panic!("{}", err))
+}
+
+/// Returns the BOOST keycode the program outputs in test mode.
+pub fn part1(program: &[i64]) -> i64 {
+    solve(program, TEST_MODE)
+}
+
+/// Returns the coordinates of the distress signal.
+pub fn part2(program: &[i64]) -> i64 {
+    solve(program, SENSOR_BOOST_MODE)
+}
+
+pub fn solve_part1(input: &str) -> i64 {
+    part1(&program(input))
+}
+
+pub fn solve_part2(input: &str) -> i64 {
+    part2(&program(input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::require_input;
+
+    fn parse(program: &str) -> Vec<i64> {
+        parse_csv(program).unwrap()
+    }
+
+    #[test]
+    fn test_quine() {
+        let program = parse("109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99");
+        assert_eq!(run(&program, 0).unwrap(), program);
+    }
+
+    #[test]
+    fn test_large_multiplication() {
+        let program = parse("1102,34915192,34915192,7,4,7,99,0");
+        assert_eq!(run(&program, 0).unwrap(), vec![1219070632396864]);
+    }
+
+    #[test]
+    fn test_large_number() {
+        let program = parse("104,1125899906842624,99");
+        assert_eq!(run(&program, 0).unwrap(), vec![1125899906842624]);
+    }
+
+    #[test]
+    fn test_solve() {
+        // outputs the large number whatever the mode
+        assert_eq!(solve_part1("104,1125899906842624,99"), 1125899906842624);
+        assert_eq!(solve_part2("104,1125899906842624,99"), 1125899906842624);
+    }
+
+    #[test]
+    fn test_boost() {
+        // outputs 5 and then 6 times the mode
+        let program = parse("3,11,4,12,102,6,11,13,4,13,99,0,5,0");
+        assert_eq!(
+            boost(&program, TEST_MODE),
+            Ok(Boost {
+                reported: vec![5],
+                answer: 6,
+            })
+        );
+
+        assert_eq!(boost(&parse("99"), TEST_MODE), Err(BoostError::NoOutput));
+        // reads a second input that never comes
+        let err = boost(&parse("3,0,3,0,99"), TEST_MODE).unwrap_err();
+        assert!(matches!(err, BoostError::Program(IntcodeError::Input(_))));
+        assert_eq!(
+            err.to_string(),
+            "BOOST program failed: unable to get input: receiving on a closed channel"
+        );
+    }
+
+    #[test]
+    fn test_sensor_boost() {
+        // outputs only the coordinates, without reporting anything
+        let program = parse(&require_input!(9));
+        assert_eq!(run(&program, SENSOR_BOOST_MODE).unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,8 @@
+//! Time budgets for the personal input, run with
+//! `cargo test --release -- --ignored`.
+
+common::budget_tests!(
+    9,
+    day9::solve_part1 => 50,
+    day9::solve_part2 => 250,
+);
@@ -0,0 +1,96 @@
+use common::testing::{run_with_input, stderr, stdout};
+
+const EXE: &str = env!("CARGO_BIN_EXE_day9");
+
+// reads the mode and outputs 5 and then 6 times the mode
+const PROGRAM: &str = "3,11,4,12,102,6,11,13,4,13,99,0,5,0";
+
+#[test]
+fn test_both_modes() {
+    let output = run_with_input(EXE, 9, Some(PROGRAM), &[]);
+    assert_eq!(
+        stdout(&output),
+        "malfunctioning opcode: 5\nBOOST keycode: 6\ncoordinates: 12\n"
+    );
+}
+
+#[test]
+fn test_part_selection() {
+    let output = run_with_input(EXE, 9, Some(PROGRAM), &["--part", "2"]);
+    assert_eq!(stdout(&output), "coordinates: 12\n");
+}
+
+#[test]
+fn test_input_path() {
+    let output = run_with_input(EXE, 9, None, &["--input", "missing", "--part", "1"]);
+    assert!(stderr(&output).starts_with("can't read missing: No such file or directory"));
+}
+
+#[test]
+fn test_invalid_arguments() {
+    let output = run_with_input(EXE, 9, Some(PROGRAM), &["--part", "3"]);
+    let err = stderr(&output);
+    assert!(
+        err.contains("invalid value '3' for '--part <PART>'"),
+        "{}",
+        err
+    );
+
+    let output = run_with_input(EXE, 9, Some(PROGRAM), &["--verbose"]);
+    let err = stderr(&output);
+    assert!(err.contains("unexpected argument '--verbose'"), "{}", err);
+}
+
+#[test]
+fn test_invalid_program() {
+    let output = run_with_input(EXE, 9, Some("104,1,9 9"), &[]);
+    let err = stderr(&output);
+    assert!(
+        err.ends_with(
+            "day9.txt: line 1, column 7: can't parse \"9 9\": invalid digit found in string\n"
+        ),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn test_failing_program() {
+    let output = run_with_input(EXE, 9, Some("99"), &["--part", "1"]);
+    assert_eq!(stderr(&output), "BOOST program produced no output\n");
+    assert_eq!(output.status.code(), Some(1));
+
+    let output = run_with_input(EXE, 9, Some("3,0,3,0,99"), &["--part", "2"]);
+    assert_eq!(
+        stderr(&output),
+        "BOOST program failed: unable to get input: receiving on a closed channel\n"
+    );
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn test_disassemble() {
+    let output = run_with_input(EXE, 9, Some("104,1125899906842624,99"), &["--disassemble"]);
+    let listing = stdout(&output);
+    assert!(listing.contains("out 1125899906842624"), "{}", listing);
+    assert!(listing.contains("hlt"), "{}", listing);
+}
+
+#[test]
+fn test_quiet() {
+    let output = run_with_input(EXE, 9, Some(PROGRAM), &["--quiet"]);
+    assert_eq!(stdout(&output), "6\n12\n");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "malfunctioning opcode: 5\n"
+    );
+}
+
+#[test]
+fn test_json() {
+    let output = run_with_input(EXE, 9, Some(PROGRAM), &["--json"]);
+    assert_eq!(
+        stdout(&output),
+        "{\"day\":9,\"part\":1,\"answer\":6}\n{\"day\":9,\"part\":2,\"answer\":12}\n"
+    );
+}
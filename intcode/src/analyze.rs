@@ -0,0 +1,352 @@
+//! Looks over a program without running it for things that are probably
+//! mistakes, or at least worth knowing about when reading one: code nothing
+//! reaches, jumps into the middle of instructions, writes to immediate-mode
+//! destinations and reads of memory nothing ever writes.
+//!
+//! Reachability follows jumps with immediate targets. A jump to a computed
+//! address could go anywhere, so unreachable code isn't reported for programs
+//! that have one.
+
+use crate::disasm::modes;
+use crate::{Instruction, Mode};
+use std::collections::{BTreeMap, HashSet};
+use std::convert::TryFrom;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Finding {
+    /// Instructions from `start` up to but not including `end` that nothing
+    /// jumps or falls through to.
+    Unreachable {
+        start: usize,
+        end: usize,
+    },
+    JumpIntoInstruction {
+        jump: usize,
+        target: usize,
+        instruction: usize,
+    },
+    /// Writing to an immediate-mode parameter overwrites the parameter itself.
+    ImmediateWrite {
+        address: usize,
+    },
+    /// A read of a cell past the program that nothing writes, which is always 0.
+    UninitializedRead {
+        address: usize,
+        cell: i64,
+    },
+    /// A reachable cell that isn't an instruction, unless the program writes
+    /// one there before running it.
+    InvalidInstruction {
+        address: usize,
+        value: i64,
+        written: bool,
+    },
+    ComputedJump {
+        address: usize,
+    },
+}
+
+impl Finding {
+    /// The address the finding is about.
+    pub fn address(&self) -> usize {
+        match *self {
+            Finding::Unreachable { start, .. } => start,
+            Finding::JumpIntoInstruction { jump, .. } => jump,
+            Finding::ImmediateWrite { address }
+            | Finding::UninitializedRead { address, .. }
+            | Finding::InvalidInstruction { address, .. }
+            | Finding::ComputedJump { address } => address,
+        }
+    }
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: ", self.address())?;
+        match *self {
+            Finding::Unreachable { end, .. } => write!(f, "unreachable code up to {}", end - 1),
+            Finding::JumpIntoInstruction {
+                target,
+                instruction,
+                ..
+            } => write!(
+                f,
+                "jumps to {}, inside the instruction at {}",
+                target, instruction
+            ),
+            Finding::ImmediateWrite { .. } => {
+                write!(f, "writes to an immediate-mode destination, i.e. to itself")
+            }
+            Finding::UninitializedRead { cell, .. } => {
+                write!(f, "reads [{}], which is never written and always 0", cell)
+            }
+            Finding::InvalidInstruction { value, written, .. } => {
+                write!(f, "{} isn't an instruction", value)?;
+                if written {
+                    write!(f, ", though the program writes there")?;
+                }
+                Ok(())
+            }
+            Finding::ComputedJump { .. } => {
+                write!(f, "jumps to a computed address, so reachability is a guess")
+            }
+        }
+    }
+}
+
+/// Decodes the instruction at `address`, or None for a halt.
+fn decode(program: &[i64], address: usize) -> Result<Option<Instruction>, i64> {
+    match program.get(address).copied().unwrap_or(0) {
+        99 => Ok(None),
+        value => Instruction::decode(value).map(Some).map_err(|_| value),
+    }
+}
+
+fn param(program: &[i64], address: usize, index: usize) -> i64 {
+    program.get(address + 1 + index).copied().unwrap_or(0)
+}
+
+/// The index of the parameter the instruction writes to, if any.
+fn written(instr: &Instruction) -> Option<usize> {
+    match instr {
+        Instruction::Add(_, _, _)
+        | Instruction::Mul(_, _, _)
+        | Instruction::LessThan(_, _, _)
+        | Instruction::Equals(_, _, _) => Some(2),
+        Instruction::Input(_) => Some(0),
+        _ => None,
+    }
+}
+
+/// Returns everything found in the program, by address.
+pub fn analyze(program: &[i64]) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let mut ops: BTreeMap<usize, Option<Instruction>> = BTreeMap::new();
+    let mut jumps = Vec::new();
+    let mut computed = false;
+    let mut invalid = Vec::new();
+
+    let mut pending = vec![0];
+    while let Some(address) = pending.pop() {
+        if ops.contains_key(&address) {
+            continue;
+        }
+        let instr = match decode(program, address) {
+            Ok(instr) => instr,
+            Err(value) => {
+                invalid.push((address, value));
+                continue;
+            }
+        };
+
+        let mut falls_through = instr.is_some();
+        let jump = match &instr {
+            Some(Instruction::JumpTrue(cond, target)) => Some((true, *cond, *target)),
+            Some(Instruction::JumpFalse(cond, target)) => Some((false, *cond, *target)),
+            _ => None,
+        };
+        if let Some((if_true, cond, target)) = jump {
+            let always = cond == Mode::Immediate && (param(program, address, 0) != 0) == if_true;
+            falls_through = !always;
+
+            match (target, usize::try_from(param(program, address, 1))) {
+                (Mode::Immediate, Ok(target)) => {
+                    jumps.push((address, target));
+                    pending.push(target);
+                }
+                (Mode::Immediate, Err(_)) => {}
+                _ => {
+                    findings.push(Finding::ComputedJump { address });
+                    computed = true;
+                }
+            }
+        }
+
+        if let Some(instr) = &instr {
+            if falls_through {
+                pending.push(address + instr.parameters() + 1);
+            }
+        }
+        ops.insert(address, instr);
+    }
+
+    let len =
+        |instr: &Option<Instruction>| instr.as_ref().map_or(0, |instr| instr.parameters()) + 1;
+
+    for &(jump, target) in &jumps {
+        if let Some((&start, instr)) = ops.range(..target).next_back() {
+            if start + len(instr) > target {
+                findings.push(Finding::JumpIntoInstruction {
+                    jump,
+                    target,
+                    instruction: start,
+                });
+            }
+        }
+    }
+
+    let mut referenced = HashSet::new();
+    let mut writes = HashSet::new();
+    let mut relative_writes = false;
+    let mut reads = Vec::new();
+    for (&address, instr) in &ops {
+        let instr = match instr {
+            Some(instr) => instr,
+            None => continue,
+        };
+        for (index, mode) in modes(&Some(instr.clone())).into_iter().enumerate() {
+            let value = param(program, address, index);
+            let write = written(instr) == Some(index);
+            match (mode, write) {
+                (Mode::Immediate, true) => findings.push(Finding::ImmediateWrite { address }),
+                (Mode::Relative, true) => relative_writes = true,
+                (Mode::Position, true) => {
+                    writes.insert(value);
+                }
+                (Mode::Position, false) => reads.push((address, value)),
+                _ => {}
+            }
+            if mode == Mode::Position {
+                referenced.insert(value);
+            }
+        }
+    }
+
+    for (address, value) in invalid {
+        findings.push(Finding::InvalidInstruction {
+            address,
+            value,
+            written: writes.contains(&(address as i64)),
+        });
+    }
+
+    // anything could have been written by a relative-mode write
+    if !relative_writes {
+        for (address, cell) in reads {
+            let outside = usize::try_from(cell).map_or(true, |cell| cell >= program.len());
+            if outside && !writes.contains(&cell) {
+                findings.push(Finding::UninitializedRead { address, cell });
+            }
+        }
+    }
+
+    if !computed {
+        findings.extend(unreachable(program, &ops, &referenced));
+    }
+
+    findings.sort_by_key(Finding::address);
+    findings.dedup();
+    findings
+}
+
+/// Finds runs of cells that no reachable instruction covers or refers to and
+/// that decode as whole instructions.
+fn unreachable(
+    program: &[i64],
+    ops: &BTreeMap<usize, Option<Instruction>>,
+    referenced: &HashSet<i64>,
+) -> Vec<Finding> {
+    let mut covered = vec![false; program.len()];
+    for (&address, instr) in ops {
+        let len = instr.as_ref().map_or(0, |instr| instr.parameters()) + 1;
+        for cell in covered.iter_mut().skip(address).take(len) {
+            *cell = true;
+        }
+    }
+
+    let mut findings = Vec::new();
+    let mut address = 0;
+    while address < program.len() {
+        let free = |address: usize| !covered[address] && !referenced.contains(&(address as i64));
+        if !free(address) {
+            address += 1;
+            continue;
+        }
+
+        let start = address;
+        let mut end = start;
+        while end < program.len() && free(end) {
+            end += 1;
+        }
+
+        // only report the run if it's all instructions, so data isn't mistaken for code
+        let mut pc = start;
+        while pc < end {
+            match decode(program, pc) {
+                Ok(instr) => pc += instr.map_or(0, |instr| instr.parameters()) + 1,
+                Err(_) => break,
+            }
+        }
+        if pc == end {
+            findings.push(Finding::Unreachable { start, end });
+        }
+        address = end;
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_analyze() {
+        #[rustfmt::skip]
+        let program = [
+            1105, 1, 7,      // jt 1, 7
+            4, 20, 104, 1,   // out [20]; out 1, never reached
+            1101, 99, 0, 24, // add 99, 0, [24]
+            1005, 24, 8,     // jt [24], 8, into the add above, where 99 is a halt
+            1, 30, 31, 32,   // add [30], [31], [32], where nothing writes [30] or [31]
+            11101, 1, 1, 0,  // add 1, 1 into itself
+            99,
+            0, 0,
+        ];
+
+        let findings: Vec<String> = analyze(&program).iter().map(ToString::to_string).collect();
+        assert_eq!(
+            findings,
+            vec![
+                "3: unreachable code up to 6",
+                "11: jumps to 8, inside the instruction at 7",
+                "14: reads [30], which is never written and always 0",
+                "14: reads [31], which is never written and always 0",
+                "18: writes to an immediate-mode destination, i.e. to itself",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_computed_jumps() {
+        // jt 1, [9] could go anywhere, so nothing is unreachable
+        let program = [105, 1, 9, 104, 7, 99, 104, 8, 99, 6];
+        assert_eq!(
+            analyze(&program),
+            vec![Finding::ComputedJump { address: 0 }]
+        );
+
+        // the same jump with an immediate target skips the first output
+        let program = [1105, 1, 6, 104, 7, 99, 104, 8, 99];
+        assert_eq!(
+            analyze(&program),
+            vec![Finding::Unreachable { start: 3, end: 6 }]
+        );
+
+        assert_eq!(
+            analyze(&[1105, 1, 3, 77]),
+            vec![Finding::InvalidInstruction {
+                address: 3,
+                value: 77,
+                written: false,
+            }]
+        );
+
+        // add 1, 1, [4] writes a 2 over the 0 that comes next
+        assert_eq!(
+            analyze(&[1101, 1, 1, 4, 0])[0].to_string(),
+            "4: 0 isn't an instruction, though the program writes there"
+        );
+    }
+}
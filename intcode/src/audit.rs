@@ -0,0 +1,259 @@
+//! Hashing a computer's state every so many steps, so two runs of a program,
+//! e.g. on different machines or by different backends, can be checked for
+//! identical execution by comparing a few lines instead of whole traces. Where
+//! two audits first disagree narrows down which steps to trace.
+
+use crate::{Intcode, State};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::mpsc::channel;
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// FNV-1a, which unlike the standard library's hasher is the same everywhere
+/// and in every Rust version.
+fn fnv(hash: u64, value: i64) -> u64 {
+    value.to_le_bytes().iter().fold(hash, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+impl Intcode {
+    /// Returns a hash of the registers and memory. Cells are combined in no
+    /// particular order, so it doesn't depend on how memory is stored, and
+    /// cells holding 0 are left out, so writing a 0 is the same as never
+    /// writing. Input that hasn't been read yet isn't included.
+    pub fn state_hash(&self) -> u64 {
+        let cells = self
+            .memory
+            .iter()
+            .filter(|(_, value)| *value != 0)
+            .map(|(address, value)| fnv(fnv(FNV_OFFSET, address), value))
+            .fold(0, u64::wrapping_add);
+
+        [self.pc, self.relative_base, cells as i64]
+            .iter()
+            .fold(FNV_OFFSET, |hash, value| fnv(hash, *value))
+    }
+}
+
+/// The state's hash after some number of steps.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Checkpoint {
+    pub step: usize,
+    pub pc: i64,
+    pub hash: u64,
+}
+
+/// Formats as a single line, e.g. `1000 pc=27 5e2b8a4f0c9d1e36`, which
+/// `from_str` reads back.
+impl fmt::Display for Checkpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} pc={} {:016x}", self.step, self.pc, self.hash)
+    }
+}
+
+impl FromStr for Checkpoint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let fields: Vec<&str> = s.split_whitespace().collect();
+        match fields.as_slice() {
+            [step, pc, hash] => Ok(Checkpoint {
+                step: step.parse().map_err(|_| format!("bad step {:?}", step))?,
+                pc: pc
+                    .strip_prefix("pc=")
+                    .and_then(|pc| pc.parse().ok())
+                    .ok_or_else(|| format!("bad pc {:?}", pc))?,
+                hash: u64::from_str_radix(hash, 16).map_err(|_| format!("bad hash {:?}", hash))?,
+            }),
+            _ => Err(String::from("expected a step, pc and hash")),
+        }
+    }
+}
+
+/// Runs the program with the given input until it halts, fails, runs out of
+/// input or has run `limit` instructions, taking a checkpoint before the first
+/// step, after every `every` steps and once it stops.
+pub fn record(program: &[i64], input: &[i64], every: usize, limit: usize) -> Vec<Checkpoint> {
+    assert!(every > 0, "checkpoints must be at least a step apart");
+
+    let (_, recv_in) = channel();
+    let (send_out, _recv_out) = channel();
+    let mut computer = Intcode::new(program, recv_in, send_out);
+    for value in input {
+        computer.push_input(*value);
+    }
+
+    let checkpoint = |computer: &Intcode, step| Checkpoint {
+        step,
+        pc: computer.pc(),
+        hash: computer.state_hash(),
+    };
+
+    let mut checkpoints = vec![checkpoint(&computer, 0)];
+    let mut step = 0;
+    while step < limit && computer.status().state == State::Ready {
+        if computer.step().is_err() {
+            break;
+        }
+        step += 1;
+        if step % every == 0 {
+            checkpoints.push(checkpoint(&computer, step));
+        }
+    }
+    if step % every != 0 {
+        checkpoints.push(checkpoint(&computer, step));
+    }
+
+    checkpoints
+}
+
+/// Reads an audit written one checkpoint per line, skipping blank lines.
+pub fn parse(audit: &str) -> Result<Vec<Checkpoint>, String> {
+    audit
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            line.parse()
+                .map_err(|err| format!("line {}: {}", index + 1, err))
+        })
+        .collect()
+}
+
+/// Where two audits stop agreeing: the last step they matched at, if any, and
+/// the first checkpoint on each side that doesn't match, or None where an
+/// audit ended early.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Divergence {
+    pub after: Option<usize>,
+    pub left: Option<Checkpoint>,
+    pub right: Option<Checkpoint>,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.after {
+            Some(step) => write!(f, "runs diverge after step {}", step)?,
+            None => write!(f, "runs differ from the start")?,
+        }
+        for (sign, checkpoint) in [("-", self.left), ("+", self.right)].iter() {
+            match checkpoint {
+                Some(checkpoint) => write!(f, "\n{} {}", sign, checkpoint)?,
+                None => write!(f, "\n{} (audit ended)", sign)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Finds the first checkpoint where the audits differ, or None if they're the
+/// same.
+pub fn compare(left: &[Checkpoint], right: &[Checkpoint]) -> Option<Divergence> {
+    let matching = left.iter().zip(right).take_while(|(l, r)| l == r).count();
+    if matching == left.len() && matching == right.len() {
+        return None;
+    }
+
+    Some(Divergence {
+        after: matching.checked_sub(1).map(|index| left[index].step),
+        left: left.get(matching).copied(),
+        right: right.get(matching).copied(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // outputs 2, 1, 0 counting [20] down from 3
+    fn countdown() -> Vec<i64> {
+        let mut program = vec![1001, 20, -1, 20, 4, 20, 1005, 20, 0, 99];
+        program.resize(21, 0);
+        program[20] = 3;
+        program
+    }
+
+    #[test]
+    fn test_state_hash() {
+        let program = countdown();
+        let (_send_in, recv_in) = channel();
+        let (send_out, _recv_out) = channel();
+        let mut computer = Intcode::new(&program, recv_in, send_out);
+        let start = computer.state_hash();
+
+        computer.write_memory(1000, 0).unwrap();
+        assert_eq!(computer.state_hash(), start);
+        computer.write_memory(1000, 1).unwrap();
+        assert_ne!(computer.state_hash(), start);
+
+        // swapping two cells' values changes the hash
+        let mut swapped = program.clone();
+        swapped.swap(0, 1);
+        let (_send_in, recv_in) = channel();
+        let (send_out, _recv_out) = channel();
+        let other = Intcode::new(&swapped, recv_in, send_out);
+        assert_ne!(other.state_hash(), start);
+    }
+
+    #[test]
+    fn test_record() {
+        let audit = record(&countdown(), &[], 4, 100);
+        let steps: Vec<usize> = audit.iter().map(|checkpoint| checkpoint.step).collect();
+        assert_eq!(steps, vec![0, 4, 8, 9]);
+        assert_eq!(audit[3].pc, 9);
+
+        assert_eq!(record(&countdown(), &[], 4, 100), audit);
+        assert_eq!(record(&countdown(), &[], 3, 100).len(), 4);
+
+        let text: String = audit.iter().map(|c| format!("{}\n", c)).collect();
+        assert_eq!(parse(&text), Ok(audit));
+        assert_eq!(
+            parse("0 pc=0 xyz"),
+            Err(String::from("line 1: bad hash \"xyz\""))
+        );
+    }
+
+    #[test]
+    fn test_compare() {
+        let program = countdown();
+        let left = record(&program, &[], 2, 100);
+        assert_eq!(compare(&left, &left), None);
+
+        // two adds, then reads the input
+        let reader = [1101, 1, 1, 30, 1101, 2, 2, 31, 3, 32, 99];
+        let divergence = compare(
+            &record(&reader, &[1], 1, 100),
+            &record(&reader, &[2], 1, 100),
+        )
+        .unwrap();
+        assert_eq!(divergence.after, Some(2));
+        assert_eq!(divergence.left.unwrap().step, 3);
+        assert_eq!(divergence.right.unwrap().step, 3);
+
+        // counts down from 4 instead, so memory differs from the start
+        let mut changed = program.clone();
+        changed[20] = 4;
+        let right = record(&changed, &[], 2, 100);
+        assert!(compare(&left, &right)
+            .unwrap()
+            .to_string()
+            .starts_with("runs differ from the start\n- 0 pc=0 "));
+
+        let shorter = record(&program, &[], 2, 5);
+        assert_eq!(
+            compare(&left, &shorter),
+            Some(Divergence {
+                after: Some(4),
+                left: Some(left[3]),
+                right: Some(shorter[3]),
+            })
+        );
+        assert_eq!(
+            compare(&left[..2], &left).unwrap().to_string(),
+            format!("runs diverge after step 2\n- (audit ended)\n+ {}", left[2])
+        );
+    }
+}
@@ -0,0 +1,577 @@
+//! A debugger that steps through a program, stopping at breakpoints and at
+//! watched memory, driven by a small line-based command language.
+
+mod condition;
+pub mod remote;
+
+pub use condition::Condition;
+
+use crate::memory::MAX_CELLS;
+use crate::{disasm, Access, Intcode, IntcodeError, State};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::mpsc::{channel, Receiver, Sender};
+
+/// Most instructions `continue` runs before giving up on reaching a stop.
+pub const CONTINUE_LIMIT: usize = 100_000_000;
+
+/// Which accesses to a watched address stop the program.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Watch {
+    Read,
+    Write,
+    /// Writes that change the value.
+    Change,
+}
+
+impl Watch {
+    fn matches(self, access: Access) -> bool {
+        match (self, access) {
+            (Watch::Read, Access::Read { .. }) => true,
+            (Watch::Write, Access::Write { .. }) => true,
+            (Watch::Change, Access::Write { old, new, .. }) => old != new,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Watch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Watch::Read => write!(f, "reads of"),
+            Watch::Write => write!(f, "writes to"),
+            Watch::Change => write!(f, "changes to"),
+        }
+    }
+}
+
+impl FromStr for Watch {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "read" => Ok(Watch::Read),
+            "write" => Ok(Watch::Write),
+            "change" => Ok(Watch::Change),
+            _ => Err(format!(
+                "unknown watch {:?}, expected read, write or change",
+                s
+            )),
+        }
+    }
+}
+
+/// Why the program stopped.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Stop {
+    Breakpoint(i64),
+    /// The instruction at `pc` made an access a watchpoint was waiting for.
+    Watchpoint {
+        pc: i64,
+        access: Access,
+    },
+    AwaitingInput,
+    Halted,
+    Error(IntcodeError),
+    /// `continue` ran this many instructions without stopping.
+    Limit(usize),
+}
+
+impl fmt::Display for Stop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Stop::Breakpoint(pc) => write!(f, "breakpoint at {}", pc),
+            Stop::Watchpoint {
+                pc,
+                access: Access::Read { address, value },
+            } => write!(f, "instruction at {} read {} from [{}]", pc, value, address),
+            Stop::Watchpoint {
+                pc,
+                access: Access::Write { address, old, new },
+            } => write!(
+                f,
+                "instruction at {} wrote {} to [{}], was {}",
+                pc, new, address, old
+            ),
+            Stop::AwaitingInput => write!(f, "awaiting input"),
+            Stop::Halted => write!(f, "halted"),
+            Stop::Error(err) => write!(f, "error: {}", err),
+            Stop::Limit(steps) => write!(f, "still running after {} steps", steps),
+        }
+    }
+}
+
+/// A debugger command, parsed from a line like `watch 223 write`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Step(usize),
+    Continue,
+    /// Stops at an address, when a condition holds, or at an address when a
+    /// condition holds.
+    Break(Option<i64>, Option<Condition>),
+    Watch(i64, Watch),
+    /// Removes the breakpoints and watchpoints at an address, or all of them.
+    Delete(Option<i64>),
+    Input(Vec<i64>),
+    /// Shows this many cells starting at an address.
+    Memory(i64, usize),
+    /// Writes a value to an address.
+    Set(i64, i64),
+    Registers,
+    Output,
+    Help,
+}
+
+pub const HELP: &str = "\
+step [n]                          run n instructions (default 1)
+continue                          run until something stops the program
+break <address> [if <condition>]  stop before running the instruction at address
+break if <condition>              stop before any instruction where condition holds,
+                                  e.g. mem[5] > 100 && pc != 42
+watch <address> [read|write|change]
+                                  stop after address is accessed (default write)
+delete [address]                  remove breakpoints and watchpoints at address,
+                                  or everywhere
+input <value>...                  queue input values
+memory <address> [count]          show memory (default 8 cells, at most 100000)
+set <address> <value>             write to memory
+registers                         show registers and upcoming instructions
+output                            show everything output so far
+help                              show this
+quit                              leave the debugger
+commands other than set can be shortened to their first letter";
+
+fn parse_number<T>(word: Option<&str>, what: &str) -> Result<T, String>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    let word = word.ok_or_else(|| format!("missing {}", what))?;
+    word.parse()
+        .map_err(|err| format!("bad {} {:?}: {}", what, word, err))
+}
+
+/// Parses the arguments of `break`: an address, `if` and a condition, or both.
+fn parse_break(args: &str) -> Result<Command, String> {
+    let args = args.trim();
+    let (address, rest) = match args.split_whitespace().next() {
+        None => return Err(String::from("missing address or condition")),
+        Some("if") => (None, args),
+        Some(word) => (
+            Some(parse_number(Some(word), "address")?),
+            args[word.len()..].trim_start(),
+        ),
+    };
+
+    if rest.is_empty() {
+        return Ok(Command::Break(address, None));
+    }
+    let condition = rest
+        .strip_prefix("if")
+        .filter(|condition| condition.is_empty() || condition.starts_with(char::is_whitespace))
+        .ok_or_else(|| format!("expected \"if\", found {:?}", rest))?;
+
+    Ok(Command::Break(address, Some(condition.parse()?)))
+}
+
+impl FromStr for Command {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut words = s.split_whitespace();
+        let name = words.next().ok_or_else(|| String::from("empty command"))?;
+
+        if name == "break" || name == "b" {
+            return parse_break(&s.trim_start()[name.len()..]);
+        }
+
+        let command = match name {
+            "step" | "s" => match words.next() {
+                Some(count) => Command::Step(parse_number(Some(count), "count")?),
+                None => Command::Step(1),
+            },
+            "continue" | "c" => Command::Continue,
+            "watch" | "w" => {
+                let address = parse_number(words.next(), "address")?;
+                let watch = match words.next() {
+                    Some(watch) => watch.parse()?,
+                    None => Watch::Write,
+                };
+                Command::Watch(address, watch)
+            }
+            "delete" | "d" => match words.next() {
+                Some(address) => Command::Delete(Some(parse_number(Some(address), "address")?)),
+                None => Command::Delete(None),
+            },
+            "input" | "i" => {
+                let values = words
+                    .by_ref()
+                    .flat_map(|word| word.split(','))
+                    .filter(|value| !value.is_empty())
+                    .map(|value| parse_number(Some(value), "input"))
+                    .collect::<Result<Vec<i64>, _>>()?;
+                if values.is_empty() {
+                    return Err(String::from("missing input"));
+                }
+                Command::Input(values)
+            }
+            "memory" | "m" => {
+                let address = parse_number(words.next(), "address")?;
+                let count = match words.next() {
+                    Some(count) => parse_number(Some(count), "count")?,
+                    None => 8,
+                };
+                Command::Memory(address, count)
+            }
+            "set" => Command::Set(
+                parse_number(words.next(), "address")?,
+                parse_number(words.next(), "value")?,
+            ),
+            "registers" | "r" => Command::Registers,
+            "output" | "o" => Command::Output,
+            "help" | "h" => Command::Help,
+            _ => return Err(format!("unknown command {:?}, try help", name)),
+        };
+
+        match words.next() {
+            Some(extra) => Err(format!("unexpected {:?}", extra)),
+            None => Ok(command),
+        }
+    }
+}
+
+/// Steps through a program with its own input and output, stopping at
+/// breakpoints and watchpoints.
+pub struct Debugger {
+    computer: Intcode,
+    // kept so reading input never sees a closed channel
+    _input: Sender<i64>,
+    output: Receiver<i64>,
+    outputs: Vec<i64>,
+    breakpoints: Vec<(Option<i64>, Option<Condition>)>,
+    watchpoints: Vec<(i64, Watch)>,
+}
+
+impl Debugger {
+    pub fn new(program: &[i64]) -> Self {
+        let (send_in, recv_in) = channel();
+        let (send_out, recv_out) = channel();
+
+        Debugger {
+            computer: Intcode::new(program, recv_in, send_out).with_access_log(),
+            _input: send_in,
+            output: recv_out,
+            outputs: Vec::new(),
+            breakpoints: Vec::new(),
+            watchpoints: Vec::new(),
+        }
+    }
+
+    pub fn computer(&self) -> &Intcode {
+        &self.computer
+    }
+
+    /// Returns everything the program has output.
+    pub fn outputs(&self) -> &[i64] {
+        &self.outputs
+    }
+
+    pub fn push_input(&mut self, value: i64) {
+        self.computer.push_input(value);
+    }
+
+    pub fn set_breakpoint(&mut self, address: i64) {
+        self.breakpoints.push((Some(address), None));
+    }
+
+    /// Stops before any instruction, or only the one at `address`, when
+    /// `condition` holds.
+    pub fn set_conditional_breakpoint(&mut self, address: Option<i64>, condition: Condition) {
+        self.breakpoints.push((address, Some(condition)));
+    }
+
+    fn at_breakpoint(&self) -> bool {
+        let pc = self.computer.pc();
+        self.breakpoints.iter().any(|(address, condition)| {
+            address.is_none_or(|address| address == pc)
+                && condition
+                    .as_ref()
+                    .is_none_or(|condition| condition.holds(&self.computer))
+        })
+    }
+
+    pub fn watch(&mut self, address: i64, watch: Watch) {
+        if !self.watchpoints.contains(&(address, watch)) {
+            self.watchpoints.push((address, watch));
+        }
+    }
+
+    /// Removes the breakpoints and watchpoints at `address`, or all of them if
+    /// it's None, returning whether there were any.
+    pub fn delete(&mut self, address: Option<i64>) -> bool {
+        let before = self.breakpoints.len() + self.watchpoints.len();
+        match address {
+            Some(address) => {
+                self.breakpoints.retain(|(at, _)| *at != Some(address));
+                self.watchpoints.retain(|(watched, _)| *watched != address);
+            }
+            None => {
+                self.breakpoints.clear();
+                self.watchpoints.clear();
+            }
+        }
+
+        self.breakpoints.len() + self.watchpoints.len() != before
+    }
+
+    /// Runs one instruction, returning why the program stopped if it couldn't
+    /// run or tripped a watchpoint.
+    pub fn step(&mut self) -> Option<Stop> {
+        match self.computer.status().state {
+            State::Ready => {}
+            State::AwaitingInput => return Some(Stop::AwaitingInput),
+            State::Halted => return Some(Stop::Halted),
+        }
+
+        let pc = self.computer.pc();
+        let result = self.computer.step();
+        self.outputs.extend(self.output.try_iter());
+        if let Err(err) = result {
+            return Some(Stop::Error(err));
+        }
+
+        let watchpoints = &self.watchpoints;
+        self.computer
+            .accesses()
+            .iter()
+            .find(|access| {
+                let address = match **access {
+                    Access::Read { address, .. } | Access::Write { address, .. } => address,
+                };
+                watchpoints
+                    .iter()
+                    .any(|&(watched, watch)| watched == address && watch.matches(**access))
+            })
+            .map(|&access| Stop::Watchpoint { pc, access })
+    }
+
+    /// Runs until something stops the program or `limit` instructions have run.
+    /// A breakpoint at the current instruction doesn't stop it, so this can
+    /// continue from one.
+    pub fn resume(&mut self, limit: usize) -> Stop {
+        self.run_for(limit, true).unwrap_or(Stop::Limit(limit))
+    }
+
+    /// Runs up to `limit` instructions, returning None if nothing stopped the
+    /// program. Doesn't check for a breakpoint before the first instruction if
+    /// `starting` is set.
+    fn run_for(&mut self, limit: usize, starting: bool) -> Option<Stop> {
+        for steps in 0..limit {
+            if (steps > 0 || !starting) && self.at_breakpoint() {
+                return Some(Stop::Breakpoint(self.computer.pc()));
+            }
+            if let Some(stop) = self.step() {
+                return Some(stop);
+            }
+        }
+
+        None
+    }
+
+    /// Describes the next instruction, e.g. `12: add [13], 1, [13]`.
+    fn current(&self) -> String {
+        let pc = self.computer.pc();
+        let cells: Vec<i64> = (pc..pc + 4)
+            .map(|address| self.computer.memory(address))
+            .collect();
+
+        match disasm::decode_one(&cells) {
+            Some((text, _)) => format!("{}: {}", pc, text.trim_end()),
+            None => format!("{}: {} (data)", pc, cells[0]),
+        }
+    }
+
+    /// Runs a command, returning what to show for it.
+    pub fn execute(&mut self, command: &Command) -> String {
+        match *command {
+            Command::Step(count) => {
+                for _ in 0..count {
+                    if let Some(stop) = self.step() {
+                        return format!("{}\n{}", stop, self.current());
+                    }
+                }
+                self.current()
+            }
+            Command::Continue => {
+                let stop = self.resume(CONTINUE_LIMIT);
+                format!("{}\n{}", stop, self.current())
+            }
+            Command::Break(address, ref condition) => {
+                let mut text = String::from("breakpoint");
+                if let Some(address) = address {
+                    text += &format!(" at {}", address);
+                }
+                if let Some(condition) = condition {
+                    text += &format!(" if {}", condition);
+                }
+
+                match condition {
+                    Some(condition) => self.set_conditional_breakpoint(address, condition.clone()),
+                    None => {
+                        self.set_breakpoint(address.expect("break without address or condition"))
+                    }
+                }
+                text
+            }
+            Command::Watch(address, watch) => {
+                self.watch(address, watch);
+                format!("watching {} [{}]", watch, address)
+            }
+            Command::Delete(address) => match (self.delete(address), address) {
+                (true, Some(address)) => {
+                    format!("deleted breakpoints and watchpoints at {}", address)
+                }
+                (true, None) => String::from("deleted all breakpoints and watchpoints"),
+                (false, Some(address)) => format!("nothing set at {}", address),
+                (false, None) => String::from("nothing set"),
+            },
+            Command::Input(ref values) => {
+                for value in values {
+                    self.push_input(*value);
+                }
+                format!("queued {} inputs", values.len())
+            }
+            Command::Memory(address, count) => {
+                let count = count.min(MAX_CELLS as usize);
+                (address..address.saturating_add(count as i64))
+                    .map(|address| format!("{}: {}", address, self.computer.memory(address)))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            }
+            Command::Registers => self.computer.to_string().trim_end().to_string(),
+            Command::Output => self
+                .outputs
+                .iter()
+                .map(|value| value.to_string())
+                .collect::<Vec<_>>()
+                .join(","),
+            Command::Set(address, value) => match self.computer.write_memory(address, value) {
+                Ok(()) => format!("{}: {}", address, value),
+                Err(err) => format!("error: {}", err),
+            },
+            Command::Help => String::from(HELP),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // counts [20] down from 3 to 0, outputting each value after decrementing
+    const COUNTDOWN: [i64; 12] = [1001, 20, -1, 20, 4, 20, 1005, 20, 0, 99, 0, 0];
+
+    fn countdown() -> Debugger {
+        let mut program = COUNTDOWN.to_vec();
+        program.resize(21, 0);
+        program[20] = 3;
+        Debugger::new(&program)
+    }
+
+    #[test]
+    fn test_watchpoints() {
+        let mut debugger = countdown();
+        debugger.watch(20, Watch::Write);
+
+        let expected = Stop::Watchpoint {
+            pc: 0,
+            access: Access::Write {
+                address: 20,
+                old: 3,
+                new: 2,
+            },
+        };
+        assert_eq!(debugger.resume(100), expected);
+        assert_eq!(
+            expected.to_string(),
+            "instruction at 0 wrote 2 to [20], was 3"
+        );
+
+        assert!(debugger.delete(Some(20)));
+        debugger.watch(20, Watch::Read);
+        assert!(matches!(
+            debugger.resume(100),
+            Stop::Watchpoint {
+                pc: 4,
+                access: Access::Read {
+                    address: 20,
+                    value: 2
+                }
+            }
+        ));
+
+        assert!(debugger.delete(Some(20)));
+        assert_eq!(debugger.resume(100), Stop::Halted);
+        assert_eq!(debugger.outputs(), &[2, 1, 0]);
+    }
+
+    #[test]
+    fn test_breakpoints() {
+        let mut debugger = countdown();
+        debugger.set_breakpoint(4);
+
+        assert_eq!(debugger.resume(100), Stop::Breakpoint(4));
+        assert_eq!(debugger.resume(100), Stop::Breakpoint(4));
+        assert_eq!(debugger.outputs(), &[2]);
+        assert_eq!(debugger.resume(2), Stop::Limit(2));
+    }
+
+    #[test]
+    fn test_conditional_breakpoints() {
+        let mut debugger = countdown();
+        let mut run = |line: &str| debugger.execute(&line.parse().unwrap());
+
+        assert_eq!(run("break if mem[20] == 1"), "breakpoint if mem[20] == 1");
+        assert_eq!(run("c"), "breakpoint at 4\n4: out [20]");
+        assert_eq!(run("c"), "breakpoint at 6\n6: jt [20], 0");
+        assert_eq!(run("delete"), "deleted all breakpoints and watchpoints");
+        assert_eq!(
+            run("b 9 if pc == 9 && mem[20] == 0"),
+            "breakpoint at 9 if pc == 9 && mem[20] == 0"
+        );
+        assert_eq!(run("c"), "breakpoint at 9\n9: hlt");
+
+        assert!("break 4 when pc == 4".parse::<Command>().is_err());
+        assert!("break if".parse::<Command>().is_err());
+        assert!("b".parse::<Command>().is_err());
+    }
+
+    #[test]
+    fn test_commands() {
+        assert_eq!("s".parse(), Ok(Command::Step(1)));
+        assert_eq!(
+            "watch 223 change".parse(),
+            Ok(Command::Watch(223, Watch::Change))
+        );
+        assert_eq!("i 1,2 3".parse(), Ok(Command::Input(vec![1, 2, 3])));
+        assert!("break".parse::<Command>().is_err());
+        assert!("step 1 2".parse::<Command>().is_err());
+
+        let mut debugger = Debugger::new(&[3, 10, 4, 10, 99]);
+        let mut run = |line: &str| debugger.execute(&line.parse().unwrap());
+
+        assert_eq!(run("continue"), "awaiting input\n0: in [10]");
+        assert_eq!(run("w 10"), "watching writes to [10]");
+        assert_eq!(run("input 7"), "queued 1 inputs");
+        assert_eq!(
+            run("step 2"),
+            "instruction at 0 wrote 7 to [10], was 0\n2: out [10]"
+        );
+        assert_eq!(run("c"), "halted\n4: hlt");
+        assert_eq!(run("m 10 1"), "10: 7");
+        assert_eq!(run("set 10 9"), "10: 9");
+        assert_eq!(run("m 10 1"), "10: 9");
+        assert_eq!(run("m 0 100000000000").lines().count(), MAX_CELLS as usize);
+        assert_eq!(run("o"), "7");
+    }
+}
@@ -0,0 +1,279 @@
+//! Breakpoint conditions like `mem[5] > 100 && pc != 42`.
+//!
+//! Values are integers: `pc`, `rb` (the relative base), `mem[...]` and number
+//! literals, combined with `+ - * / %`, comparisons, `&& || !` and parentheses,
+//! with the usual precedence. Comparisons and logic give 1 or 0, and a
+//! condition holds when it's nonzero. Arithmetic wraps on overflow, and dividing
+//! by zero makes the whole condition false.
+
+use crate::Intcode;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Number(i64),
+    Name(String),
+    Symbol(&'static str),
+}
+
+/// Symbols, longest first so `<=` isn't read as `<`.
+const SYMBOLS: [&str; 19] = [
+    "&&", "||", "==", "!=", "<=", ">=", "<", ">", "+", "-", "*", "/", "%", "!", "(", ")", "[", "]",
+    "=",
+];
+
+fn tokenize(s: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut rest = s.trim_start();
+
+    while let Some(ch) = rest.chars().next() {
+        if ch.is_ascii_digit() {
+            let end = rest
+                .find(|ch: char| !ch.is_ascii_digit())
+                .unwrap_or(rest.len());
+            let number = rest[..end]
+                .parse()
+                .map_err(|err| format!("bad number {:?}: {}", &rest[..end], err))?;
+            tokens.push(Token::Number(number));
+            rest = &rest[end..];
+        } else if ch.is_ascii_alphabetic() {
+            let end = rest
+                .find(|ch: char| !ch.is_ascii_alphanumeric())
+                .unwrap_or(rest.len());
+            tokens.push(Token::Name(rest[..end].to_string()));
+            rest = &rest[end..];
+        } else {
+            let symbol = SYMBOLS
+                .iter()
+                .find(|symbol| rest.starts_with(**symbol))
+                .ok_or_else(|| format!("unexpected {:?}", ch))?;
+            if *symbol == "=" {
+                return Err(String::from("unexpected \"=\", did you mean \"==\"?"));
+            }
+            tokens.push(Token::Symbol(symbol));
+            rest = &rest[symbol.len()..];
+        }
+
+        rest = rest.trim_start();
+    }
+
+    Ok(tokens)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Expr {
+    Number(i64),
+    Pc,
+    RelativeBase,
+    Memory(Box<Expr>),
+    Negate(Box<Expr>),
+    Not(Box<Expr>),
+    Binary(&'static str, Box<Expr>, Box<Expr>),
+}
+
+/// Binary operators from loosest to tightest binding.
+const PRECEDENCE: [&[&str]; 5] = [
+    &["||"],
+    &["&&"],
+    &["==", "!=", "<", "<=", ">", ">="],
+    &["+", "-"],
+    &["*", "/", "%"],
+];
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn expect(&mut self, symbol: &str) -> Result<(), String> {
+        match self.next() {
+            Some(Token::Symbol(found)) if found == symbol => Ok(()),
+            Some(token) => Err(format!("expected {:?}, found {}", symbol, token)),
+            None => Err(format!("expected {:?}", symbol)),
+        }
+    }
+
+    /// Parses operators at `level` of `PRECEDENCE` and tighter, all left
+    /// associative.
+    fn binary(&mut self, level: usize) -> Result<Expr, String> {
+        if level == PRECEDENCE.len() {
+            return self.unary();
+        }
+
+        let mut left = self.binary(level + 1)?;
+        while let Some(Token::Symbol(symbol)) = self.peek() {
+            let symbol = *symbol;
+            if !PRECEDENCE[level].contains(&symbol) {
+                break;
+            }
+            self.position += 1;
+
+            let right = self.binary(level + 1)?;
+            left = Expr::Binary(symbol, Box::new(left), Box::new(right));
+        }
+
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        match self.next() {
+            Some(Token::Number(number)) => Ok(Expr::Number(number)),
+            Some(Token::Name(name)) => match name.as_str() {
+                "pc" => Ok(Expr::Pc),
+                "rb" => Ok(Expr::RelativeBase),
+                "mem" => {
+                    self.expect("[")?;
+                    let address = self.binary(0)?;
+                    self.expect("]")?;
+                    Ok(Expr::Memory(Box::new(address)))
+                }
+                _ => Err(format!("unknown name {:?}, expected pc, rb or mem", name)),
+            },
+            Some(Token::Symbol("-")) => Ok(Expr::Negate(Box::new(self.unary()?))),
+            Some(Token::Symbol("!")) => Ok(Expr::Not(Box::new(self.unary()?))),
+            Some(Token::Symbol("(")) => {
+                let expr = self.binary(0)?;
+                self.expect(")")?;
+                Ok(expr)
+            }
+            Some(token) => Err(format!("unexpected {}", token)),
+            None => Err(String::from("unexpected end of condition")),
+        }
+    }
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Number(number) => write!(f, "{}", number),
+            Token::Name(name) => write!(f, "{:?}", name),
+            Token::Symbol(symbol) => write!(f, "{:?}", symbol),
+        }
+    }
+}
+
+impl Expr {
+    fn evaluate(&self, computer: &Intcode) -> Option<i64> {
+        Some(match self {
+            Expr::Number(number) => *number,
+            Expr::Pc => computer.pc(),
+            Expr::RelativeBase => computer.relative_base(),
+            Expr::Memory(address) => computer.memory(address.evaluate(computer)?),
+            Expr::Negate(expr) => expr.evaluate(computer)?.wrapping_neg(),
+            Expr::Not(expr) => (expr.evaluate(computer)? == 0) as i64,
+            Expr::Binary(symbol, left, right) => {
+                let left = left.evaluate(computer)?;
+
+                // && and || only look at the right side if they need to
+                match *symbol {
+                    "&&" if left == 0 => return Some(0),
+                    "||" if left != 0 => return Some(1),
+                    _ => {}
+                }
+                let right = right.evaluate(computer)?;
+
+                match *symbol {
+                    "&&" | "||" => (right != 0) as i64,
+                    "==" => (left == right) as i64,
+                    "!=" => (left != right) as i64,
+                    "<" => (left < right) as i64,
+                    "<=" => (left <= right) as i64,
+                    ">" => (left > right) as i64,
+                    ">=" => (left >= right) as i64,
+                    "+" => left.wrapping_add(right),
+                    "-" => left.wrapping_sub(right),
+                    "*" => left.wrapping_mul(right),
+                    "/" => left.checked_div(right)?,
+                    "%" => left.checked_rem(right)?,
+                    _ => unreachable!("unknown operator {}", symbol),
+                }
+            }
+        })
+    }
+}
+
+/// A condition on the computer's registers and memory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Condition {
+    text: String,
+    expr: Expr,
+}
+
+impl Condition {
+    pub fn holds(&self, computer: &Intcode) -> bool {
+        self.expr.evaluate(computer).is_some_and(|value| value != 0)
+    }
+}
+
+impl FromStr for Condition {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            tokens: tokenize(s)?,
+            position: 0,
+        };
+
+        let expr = parser.binary(0)?;
+        match parser.next() {
+            Some(token) => Err(format!("unexpected {}", token)),
+            None => Ok(Condition {
+                text: s.trim().to_string(),
+                expr,
+            }),
+        }
+    }
+}
+
+/// Shows the condition as it was written.
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc::channel;
+
+    #[test]
+    fn test_condition() {
+        let (_send_in, recv_in) = channel();
+        let (send_out, _recv_out) = channel();
+        let mut program = vec![0; 10];
+        program[5] = 150;
+        program[6] = 5;
+        let computer = Intcode::new(&program, recv_in, send_out);
+
+        let holds = |condition: &str| condition.parse::<Condition>().unwrap().holds(&computer);
+
+        assert!(holds("mem[5] > 100 && pc != 42"));
+        assert!(!holds("mem[5] > 100 && pc == 42"));
+        assert!(holds("mem[mem[6]] == 150"));
+        assert!(holds("1 + 2 * 3 == 7 && (1 + 2) * 3 == 9"));
+        assert!(holds("-mem[5] < 0 || 1 / 0"));
+        assert!(holds("!(rb > 0) && 10 % 4 == 2 && 8 - 2 - 1 == 5"));
+        assert!(!holds("1 / 0 == 0"));
+
+        for bad in &["", "mem[5", "pc = 1", "x > 1", "1 2", "(1"] {
+            assert!(bad.parse::<Condition>().is_err(), "{:?} parsed", bad);
+        }
+        assert_eq!(
+            "pc  == 4 ".parse::<Condition>().unwrap().to_string(),
+            "pc  == 4"
+        );
+    }
+}
@@ -0,0 +1,205 @@
+//! Serves the debugger over TCP, so another process can drive it.
+//!
+//! Clients send one command per line, in the same language as the interactive
+//! debugger, plus `interrupt` and `quit`. Each command gets a response of zero
+//! or more lines followed by a line holding only `.`.
+//!
+//! `continue` doesn't respond until the program stops. While it runs, commands
+//! that only look at the program (`registers`, `memory`, `output` and `help`)
+//! are answered straight away, so a long run can be watched live, and
+//! `interrupt` stops it. Anything else is refused until the program stops.
+
+use super::{Command, Debugger};
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc::{channel, Receiver, TryRecvError};
+use std::thread;
+
+/// Instructions run between checks for new commands while continuing.
+const SLICE: usize = 10_000;
+
+impl Command {
+    /// Returns whether the command leaves the program as it is.
+    fn is_read_only(&self) -> bool {
+        matches!(
+            self,
+            Command::Memory(_, _) | Command::Registers | Command::Output | Command::Help
+        )
+    }
+}
+
+fn respond(stream: &mut TcpStream, text: &str) -> io::Result<()> {
+    let response = if text.is_empty() {
+        String::from(".\n")
+    } else {
+        format!("{}\n.\n", text)
+    };
+
+    stream.write_all(response.as_bytes())
+}
+
+/// Reads lines from the client on another thread, so they can be checked
+/// without blocking while the program runs.
+fn read_lines(stream: &TcpStream) -> io::Result<Receiver<String>> {
+    let reader = BufReader::new(stream.try_clone()?);
+    let (send, lines) = channel();
+
+    thread::spawn(move || {
+        for line in reader.lines().map_while(Result::ok) {
+            if send.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    Ok(lines)
+}
+
+/// Handles one client until it quits or hangs up. The program is left where
+/// it stopped for the next client.
+fn session(debugger: &mut Debugger, mut stream: TcpStream) -> io::Result<()> {
+    stream.set_nodelay(true)?;
+    let lines = read_lines(&stream)?;
+    let mut running = false;
+    // whether the next slice is the start of a continue, which shouldn't stop at
+    // a breakpoint it's already at
+    let mut starting = false;
+
+    loop {
+        let line = if running {
+            match lines.try_recv() {
+                Ok(line) => Some(line),
+                Err(TryRecvError::Empty) => None,
+                Err(TryRecvError::Disconnected) => return Ok(()),
+            }
+        } else {
+            match lines.recv() {
+                Ok(line) => Some(line),
+                Err(_) => return Ok(()),
+            }
+        };
+
+        if let Some(line) = line {
+            match line.trim() {
+                "" => {}
+                "quit" => return Ok(()),
+                "interrupt" if running => {
+                    running = false;
+                    respond(&mut stream, &format!("interrupted\n{}", debugger.current()))?;
+                }
+                "interrupt" => respond(&mut stream, "error: not running")?,
+                line => match line.parse::<Command>() {
+                    Ok(Command::Continue) if !running => {
+                        running = true;
+                        starting = true;
+                    }
+                    Ok(command) if !running || command.is_read_only() => {
+                        respond(&mut stream, &debugger.execute(&command))?
+                    }
+                    Ok(_) => respond(&mut stream, "error: running, interrupt first")?,
+                    Err(err) => respond(&mut stream, &format!("error: {}", err))?,
+                },
+            }
+        }
+
+        if running {
+            let stop = debugger.run_for(SLICE, starting);
+            starting = false;
+            if let Some(stop) = stop {
+                running = false;
+                respond(&mut stream, &format!("{}\n{}", stop, debugger.current()))?;
+            }
+        }
+    }
+}
+
+/// Serves clients one at a time until accepting a connection fails.
+pub fn serve(debugger: &mut Debugger, listener: TcpListener) -> io::Result<()> {
+    for stream in listener.incoming() {
+        // a client going away mid-response shouldn't stop the server
+        if let Err(err) = session(debugger, stream?) {
+            eprintln!("client disconnected: {}", err);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Client {
+        reader: BufReader<TcpStream>,
+        stream: TcpStream,
+    }
+
+    impl Client {
+        fn send(&mut self, line: &str) {
+            self.stream
+                .write_all(format!("{}\n", line).as_bytes())
+                .unwrap();
+        }
+
+        fn response(&mut self) -> String {
+            let mut lines = Vec::new();
+            loop {
+                let mut line = String::new();
+                self.reader.read_line(&mut line).unwrap();
+                match line.trim_end() {
+                    "." => return lines.join("\n"),
+                    line => lines.push(line.to_string()),
+                }
+            }
+        }
+
+        fn command(&mut self, line: &str) -> String {
+            self.send(line);
+            self.response()
+        }
+    }
+
+    fn start(program: &[i64]) -> Client {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let mut debugger = Debugger::new(program);
+        thread::spawn(move || serve(&mut debugger, listener));
+
+        let stream = TcpStream::connect(addr).unwrap();
+        Client {
+            reader: BufReader::new(stream.try_clone().unwrap()),
+            stream,
+        }
+    }
+
+    #[test]
+    fn test_remote() {
+        // adds one to [20] forever
+        let mut client = start(&[1001, 20, 1, 20, 1105, 1, 0]);
+
+        assert_eq!(client.command("break 4"), "breakpoint at 4");
+        assert_eq!(client.command("continue"), "breakpoint at 4\n4: jt 1, 0");
+        assert_eq!(client.command("memory 20 1"), "20: 1");
+        assert_eq!(client.command("set 20 -5"), "20: -5");
+        assert_eq!(client.command("c"), "breakpoint at 4\n4: jt 1, 0");
+        assert_eq!(client.command("m 20 1"), "20: -4");
+        assert_eq!(
+            client.command("delete 4"),
+            "deleted breakpoints and watchpoints at 4"
+        );
+        assert!(client
+            .command("bogus")
+            .starts_with("error: unknown command"));
+
+        client.send("continue");
+        assert!(client.command("m 20 1").starts_with("20: "));
+        assert_eq!(
+            client.command("set 20 0"),
+            "error: running, interrupt first"
+        );
+        let interrupted = client.command("interrupt");
+        assert!(interrupted.starts_with("interrupted\n"), "{}", interrupted);
+        assert_eq!(client.command("interrupt"), "error: not running");
+    }
+}
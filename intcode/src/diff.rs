@@ -0,0 +1,250 @@
+//! Differential testing: runs the same program and input through two Intcode
+//! engines and reports the first place their results differ.
+
+use crate::Intcode;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::mpsc::channel;
+
+/// Everything an engine produced from running a program to the end.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Run {
+    pub outputs: Vec<i64>,
+    /// Nonzero memory cells when the program stopped, so engines that store
+    /// memory differently can be compared.
+    pub memory: BTreeMap<i64, i64>,
+    /// Why the program stopped early, if it didn't halt. Engines word their
+    /// errors differently, so only whether there was one is compared.
+    pub error: Option<String>,
+}
+
+impl Run {
+    /// Collects the nonzero cells out of `memory`.
+    pub fn new<I>(outputs: Vec<i64>, memory: I, error: Option<String>) -> Self
+    where
+        I: IntoIterator<Item = (i64, i64)>,
+    {
+        Run {
+            outputs,
+            memory: memory
+                .into_iter()
+                .filter(|(_, value)| *value != 0)
+                .collect(),
+            error,
+        }
+    }
+}
+
+/// A way of running Intcode programs.
+pub trait Engine {
+    fn name(&self) -> &str;
+
+    /// Runs `program` until it halts or fails, reading from `input` in order.
+    /// Reading past the end of `input` should fail rather than block.
+    fn run(&self, program: &[i64], input: &[i64]) -> Run;
+}
+
+/// The interpreter in this crate.
+pub struct Interpreter;
+
+impl Engine for Interpreter {
+    fn name(&self) -> &str {
+        "interpreter"
+    }
+
+    fn run(&self, program: &[i64], input: &[i64]) -> Run {
+        // dropping the sender makes reading past the input an error
+        let (_, recv_in) = channel();
+        let (send_out, recv_out) = channel();
+
+        let mut computer = Intcode::new(program, recv_in, send_out);
+        for value in input {
+            computer.push_input(*value);
+        }
+
+        let error = computer.evaluate().err().map(|err| err.to_string());
+        Run::new(recv_out.try_iter().collect(), computer.memory.iter(), error)
+    }
+}
+
+/// The first difference found between two engines' runs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Divergence {
+    /// The outputs differ at `index`; None means that engine output less.
+    Output {
+        index: usize,
+        left: Option<i64>,
+        right: Option<i64>,
+    },
+    /// One engine failed and the other didn't.
+    Error {
+        left: Option<String>,
+        right: Option<String>,
+    },
+    Memory {
+        address: i64,
+        left: i64,
+        right: i64,
+    },
+}
+
+fn describe(error: &Option<String>) -> &str {
+    error.as_deref().unwrap_or("halted")
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = |value: &Option<i64>| match value {
+            Some(value) => value.to_string(),
+            None => String::from("nothing"),
+        };
+
+        match self {
+            Divergence::Output { index, left, right } => write!(
+                f,
+                "output {} differs: {} vs {}",
+                index,
+                value(left),
+                value(right)
+            ),
+            Divergence::Error { left, right } => write!(
+                f,
+                "programs stopped differently: {} vs {}",
+                describe(left),
+                describe(right)
+            ),
+            Divergence::Memory {
+                address,
+                left,
+                right,
+            } => write!(f, "memory at {} differs: {} vs {}", address, left, right),
+        }
+    }
+}
+
+/// Compares two runs, checking outputs in order, then how the programs
+/// stopped, then memory from the lowest address up.
+pub fn first_divergence(left: &Run, right: &Run) -> Option<Divergence> {
+    let outputs = left.outputs.len().max(right.outputs.len());
+    for index in 0..outputs {
+        let (l, r) = (left.outputs.get(index), right.outputs.get(index));
+        if l != r {
+            return Some(Divergence::Output {
+                index,
+                left: l.copied(),
+                right: r.copied(),
+            });
+        }
+    }
+
+    if left.error.is_some() != right.error.is_some() {
+        return Some(Divergence::Error {
+            left: left.error.clone(),
+            right: right.error.clone(),
+        });
+    }
+
+    let mut addresses: Vec<i64> = left
+        .memory
+        .keys()
+        .chain(right.memory.keys())
+        .copied()
+        .collect();
+    addresses.sort_unstable();
+    addresses.dedup();
+
+    addresses.into_iter().find_map(|address| {
+        let l = left.memory.get(&address).copied().unwrap_or(0);
+        let r = right.memory.get(&address).copied().unwrap_or(0);
+        if l != r {
+            Some(Divergence::Memory {
+                address,
+                left: l,
+                right: r,
+            })
+        } else {
+            None
+        }
+    })
+}
+
+/// Runs the program through both engines, returning the run they agree on or
+/// the first divergence.
+pub fn compare(
+    left: &dyn Engine,
+    right: &dyn Engine,
+    program: &[i64],
+    input: &[i64],
+) -> Result<Run, Divergence> {
+    let left = left.run(program, input);
+    let right = right.run(program, input);
+
+    match first_divergence(&left, &right) {
+        Some(divergence) => Err(divergence),
+        None => Ok(left),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An interpreter that adds one to every output after the first.
+    struct OffByOne;
+
+    impl Engine for OffByOne {
+        fn name(&self) -> &str {
+            "off by one"
+        }
+
+        fn run(&self, program: &[i64], input: &[i64]) -> Run {
+            let mut run = Interpreter.run(program, input);
+            for output in run.outputs.iter_mut().skip(1) {
+                *output += 1;
+            }
+            run
+        }
+    }
+
+    #[test]
+    fn test_compare() {
+        // outputs its input twice, then doubles it in cell 11
+        let program = [3, 11, 4, 11, 4, 11, 1, 11, 11, 11, 99];
+
+        let run = compare(&Interpreter, &Interpreter, &program, &[21]).unwrap();
+        assert_eq!(run.outputs, vec![21, 21]);
+        assert_eq!(run.memory.get(&11), Some(&42));
+        assert_eq!(run.error, None);
+
+        let divergence = compare(&Interpreter, &OffByOne, &program, &[21]).unwrap_err();
+        assert_eq!(
+            divergence,
+            Divergence::Output {
+                index: 1,
+                left: Some(21),
+                right: Some(22)
+            }
+        );
+        assert_eq!(divergence.to_string(), "output 1 differs: 21 vs 22");
+
+        let mut changed = run.clone();
+        changed.memory.insert(11, 43);
+        assert_eq!(
+            first_divergence(&run, &changed),
+            Some(Divergence::Memory {
+                address: 11,
+                left: 42,
+                right: 43
+            })
+        );
+
+        let failed = Interpreter.run(&program, &[]);
+        assert_eq!(
+            first_divergence(&Run::new(vec![], vec![], None), &failed),
+            Some(Divergence::Error {
+                left: None,
+                right: failed.error.clone()
+            })
+        );
+    }
+}
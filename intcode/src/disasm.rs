@@ -0,0 +1,256 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+use crate::{Instruction, Mode};
+
+/// A single decoded line of a program: either an instruction or a data cell that
+/// doesn't decode to one.
+enum Line<'a> {
+    Instruction(usize, Option<Instruction>, &'a [i64]),
+    Data(usize, i64),
+}
+
+/// Returns whether `value` is a well-formed instruction, i.e. a known op code with
+/// at most three mode digits, each of which is a known mode.
+fn is_instruction(value: i64) -> bool {
+    if !(0..100_000).contains(&value) {
+        return false;
+    }
+
+    let op_code = value % 100;
+    let valid_op_code = (1..=9).contains(&op_code) || op_code == 99;
+    let valid_modes = (value / 100)
+        .to_string()
+        .chars()
+        .all(|ch| ch == '0' || ch == '1' || ch == '2');
+
+    valid_op_code && valid_modes
+}
+
+/// Splits the program into instructions and data with a linear sweep, decoding
+/// anything that isn't a valid instruction as a single data cell.
+fn decode(program: &[i64]) -> Vec<Line<'_>> {
+    let mut lines = Vec::new();
+    let mut pc = 0;
+
+    while pc < program.len() {
+        let value = program[pc];
+
+        if !is_instruction(value) {
+            lines.push(Line::Data(pc, value));
+            pc += 1;
+            continue;
+        }
+
+        // 99 would otherwise decode as an adjust relative base instruction
+        let instr = if value == 99 {
+            None
+        } else {
+            Some(Instruction::decode_lenient(value).unwrap())
+        };
+        let len = instr.as_ref().map_or(0, |instr| instr.parameters()) + 1;
+
+        if pc + len > program.len() {
+            lines.push(Line::Data(pc, value));
+            pc += 1;
+            continue;
+        }
+
+        lines.push(Line::Instruction(pc, instr, &program[pc..pc + len]));
+        pc += len;
+    }
+
+    lines
+}
+
+fn mnemonic(instr: &Option<Instruction>) -> &'static str {
+    match instr {
+        None => "hlt",
+        Some(Instruction::Add(_, _, _)) => "add",
+        Some(Instruction::Mul(_, _, _)) => "mul",
+        Some(Instruction::Input(_)) => "in",
+        Some(Instruction::Output(_)) => "out",
+        Some(Instruction::JumpTrue(_, _)) => "jt",
+        Some(Instruction::JumpFalse(_, _)) => "jf",
+        Some(Instruction::LessThan(_, _, _)) => "lt",
+        Some(Instruction::Equals(_, _, _)) => "eq",
+        Some(Instruction::AdjRelative(_)) => "arb",
+    }
+}
+
+pub(crate) fn modes(instr: &Option<Instruction>) -> Vec<Mode> {
+    match *instr {
+        None => vec![],
+        Some(Instruction::Add(a, b, c))
+        | Some(Instruction::Mul(a, b, c))
+        | Some(Instruction::LessThan(a, b, c))
+        | Some(Instruction::Equals(a, b, c)) => vec![a, b, c],
+        Some(Instruction::Input(a))
+        | Some(Instruction::Output(a))
+        | Some(Instruction::AdjRelative(a)) => vec![a],
+        Some(Instruction::JumpTrue(a, b)) | Some(Instruction::JumpFalse(a, b)) => vec![a, b],
+    }
+}
+
+fn mode_name(mode: Mode) -> &'static str {
+    match mode {
+        Mode::Position => "position",
+        Mode::Immediate => "immediate",
+        Mode::Relative => "relative",
+    }
+}
+
+fn operand(mode: Mode, value: i64) -> String {
+    match mode {
+        Mode::Position => format!("[{}]", value),
+        Mode::Immediate => format!("{}", value),
+        Mode::Relative if value < 0 => format!("[rb-{}]", -value),
+        Mode::Relative => format!("[rb+{}]", value),
+    }
+}
+
+/// Decodes the instruction at the start of `cells`, returning its text (e.g.
+/// `add [13], 1, [13]`) and length, or None if it isn't a complete instruction.
+pub(crate) fn decode_one(cells: &[i64]) -> Option<(String, usize)> {
+    let value = *cells.first()?;
+    if !is_instruction(value) {
+        return None;
+    }
+
+    if value == 99 {
+        return Some((String::from("hlt"), 1));
+    }
+
+    let instr = Some(Instruction::decode_lenient(value).ok()?);
+    let len = instr.as_ref().map_or(0, |instr| instr.parameters()) + 1;
+    if cells.len() < len {
+        return None;
+    }
+
+    let operands: Vec<String> = modes(&instr)
+        .into_iter()
+        .zip(cells[1..len].iter())
+        .map(|(mode, value)| operand(mode, *value))
+        .collect();
+
+    Some((format!("{} {}", mnemonic(&instr), operands.join(", ")), len))
+}
+
+/// Describes the feature an instruction exercises, e.g. `in (relative)`.
+fn feature(instr: &Option<Instruction>) -> String {
+    let modes: Vec<&str> = modes(instr).into_iter().map(mode_name).collect();
+    format!("{} ({})", mnemonic(instr), modes.join(", "))
+}
+
+/// Disassembles the program, labeling the BOOST self-test checks.
+///
+/// The self-test reports a malfunction by outputting an instruction straight out of
+/// memory in position mode (e.g. `4,25` outputs the 203 stored at address 25), so
+/// any instruction that is the target of such an output is treated as the check
+/// for the opcode and modes it uses.
+pub fn disassemble(program: &[i64]) -> String {
+    let lines = decode(program);
+
+    let starts: HashSet<usize> = lines
+        .iter()
+        .filter_map(|line| match line {
+            Line::Instruction(pc, _, _) => Some(*pc),
+            Line::Data(_, _) => None,
+        })
+        .collect();
+
+    // maps the address of each checked instruction to the outputs reporting it
+    let mut checks: HashMap<usize, Vec<usize>> = HashMap::new();
+    for line in lines.iter() {
+        if let Line::Instruction(pc, Some(Instruction::Output(Mode::Position)), cells) = line {
+            let target = cells[1] as usize;
+            if cells[1] >= 0 && starts.contains(&target) {
+                checks.entry(target).or_default().push(*pc);
+            }
+        }
+    }
+
+    let features: HashMap<usize, String> = lines
+        .iter()
+        .filter_map(|line| match line {
+            Line::Instruction(pc, instr, _) => Some((*pc, feature(instr))),
+            Line::Data(_, _) => None,
+        })
+        .collect();
+
+    let mut out = String::new();
+
+    for line in lines.iter() {
+        match line {
+            Line::Data(pc, value) => {
+                writeln!(out, "{:>6}: {:<24} data", pc, value).unwrap();
+            }
+            Line::Instruction(pc, instr, cells) => {
+                if let Some(reporters) = checks.get(pc) {
+                    let reporters: Vec<String> = reporters.iter().map(|r| r.to_string()).collect();
+                    writeln!(
+                        out,
+                        "\n        ; self-test: {}, reported by {}",
+                        features[pc],
+                        reporters.join(", ")
+                    )
+                    .unwrap();
+                }
+
+                let raw: Vec<String> = cells.iter().map(|cell| cell.to_string()).collect();
+                let operands: Vec<String> = modes(instr)
+                    .into_iter()
+                    .zip(cells[1..].iter())
+                    .map(|(mode, value)| operand(mode, *value))
+                    .collect();
+                let text = format!("{} {}", mnemonic(instr), operands.join(", "));
+
+                let mut annotation = String::new();
+                if let Some(Instruction::Output(Mode::Position)) = instr {
+                    let target = cells[1] as usize;
+                    if checks.contains_key(&target) {
+                        annotation = format!(
+                            "opcode {} not functioning: {}",
+                            program[target], features[&target]
+                        );
+                    }
+                }
+
+                if annotation.is_empty() {
+                    write!(out, "{:>6}: {:<24} {}", pc, raw.join(","), text.trim_end()).unwrap();
+                } else {
+                    write!(
+                        out,
+                        "{:>6}: {:<24} {:<28} ; {}",
+                        pc,
+                        raw.join(","),
+                        text.trim_end(),
+                        annotation
+                    )
+                    .unwrap();
+                }
+
+                writeln!(out).unwrap();
+            }
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disassemble() {
+        let program = vec![203, 0, 1005, 0, 7, 4, 0, 99, -3];
+        let listing = disassemble(&program);
+
+        assert!(listing.contains("self-test: in (relative), reported by 5"));
+        assert!(listing.contains("in [rb+0]"));
+        assert!(listing.contains("jt [0], 7"));
+        assert!(listing.contains("opcode 203 not functioning: in (relative)"));
+        assert!(listing.contains("-3                       data"));
+    }
+}
@@ -0,0 +1,215 @@
+//! Describes each step of a program in plain English, for learning how Intcode
+//! works: the instruction's cells, what the instruction does with its
+//! parameters' modes spelled out, and what happened when it ran.
+
+use crate::trace::Step;
+use crate::{Access, Instruction, Mode};
+use std::fmt;
+
+/// One step, explained.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Explanation {
+    pub pc: i64,
+    pub cells: Vec<i64>,
+    /// What the instruction does, e.g. "add the value at 9 to the literal 19,
+    /// store at 223".
+    pub action: String,
+    /// What it did, e.g. "3 + 19 = 22" and "[223]: 0 -> 22".
+    pub effects: Vec<String>,
+}
+
+/// Formats as the address and cells, then the action and effects indented
+/// under them.
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cells: Vec<String> = self.cells.iter().map(i64::to_string).collect();
+        writeln!(f, "{:>5}: {}", self.pc, cells.join(","))?;
+        writeln!(f, "       {}", self.action)?;
+        if !self.effects.is_empty() {
+            writeln!(f, "       {}", self.effects.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Describes a relative-mode address, e.g. "7 (relative base 5 + 2)".
+fn relative(param: i64, relative_base: i64) -> String {
+    let sign = if param < 0 { '-' } else { '+' };
+    format!(
+        "{} (relative base {} {} {})",
+        relative_base + param,
+        relative_base,
+        sign,
+        param.abs()
+    )
+}
+
+/// Describes a parameter that's read.
+fn operand(mode: Mode, param: i64, relative_base: i64) -> String {
+    match mode {
+        Mode::Position => format!("the value at {}", param),
+        Mode::Immediate => format!("the literal {}", param),
+        Mode::Relative => format!("the value at {}", relative(param, relative_base)),
+    }
+}
+
+/// Describes a parameter that's written to.
+fn destination(mode: Mode, param: i64, relative_base: i64, address: i64) -> String {
+    match mode {
+        Mode::Position => param.to_string(),
+        Mode::Immediate => format!("{}, the parameter itself", address),
+        Mode::Relative => relative(param, relative_base),
+    }
+}
+
+pub fn explain(step: &Step) -> Explanation {
+    let cells = step.instruction.clone();
+    let param = |index: usize| cells.get(index + 1).copied().unwrap_or(0);
+    let rb = step.relative_base;
+
+    // the values of the parameters that are read, in order: immediate ones are
+    // the parameter and the rest were logged as reads
+    let mut reads = step.accesses.iter().filter_map(|access| match access {
+        Access::Read { value, .. } => Some(*value),
+        Access::Write { .. } => None,
+    });
+    let mut value = |mode: Mode, index: usize| match mode {
+        Mode::Immediate => param(index),
+        _ => reads.next().unwrap_or(0),
+    };
+
+    let (action, mut effects) = match Instruction::decode(cells[0]) {
+        Err(_) => (format!("{} isn't an instruction", cells[0]), Vec::new()),
+        Ok(instr) => match instr {
+            Instruction::Add(a, b, c)
+            | Instruction::Mul(a, b, c)
+            | Instruction::LessThan(a, b, c)
+            | Instruction::Equals(a, b, c) => {
+                let (x, y) = (operand(a, param(0), rb), operand(b, param(1), rb));
+                let dest = destination(c, param(2), rb, step.pc + 3);
+                let (va, vb) = (value(a, 0), value(b, 1));
+                match instr {
+                    Instruction::Add(_, _, _) => (
+                        format!("add {} to {}, store at {}", x, y, dest),
+                        vec![format!("{} + {} = {}", va, vb, va + vb)],
+                    ),
+                    Instruction::Mul(_, _, _) => (
+                        format!("multiply {} by {}, store at {}", x, y, dest),
+                        vec![format!("{} * {} = {}", va, vb, va * vb)],
+                    ),
+                    Instruction::LessThan(_, _, _) => (
+                        format!(
+                            "store 1 at {} if {} is less than {}, otherwise 0",
+                            dest, x, y
+                        ),
+                        vec![format!("{} < {} is {}", va, vb, va < vb)],
+                    ),
+                    _ => (
+                        format!("store 1 at {} if {} equals {}, otherwise 0", dest, x, y),
+                        vec![format!("{} == {} is {}", va, vb, va == vb)],
+                    ),
+                }
+            }
+            Instruction::Input(a) => (
+                format!(
+                    "read the next input, store at {}",
+                    destination(a, param(0), rb, step.pc + 1)
+                ),
+                Vec::new(),
+            ),
+            Instruction::Output(a) => {
+                let out = value(Mode::Position, 0);
+                (
+                    format!("output {}", operand(a, param(0), rb)),
+                    vec![format!("outputs {}", out)],
+                )
+            }
+            Instruction::JumpTrue(a, b) | Instruction::JumpFalse(a, b) => {
+                let if_true = matches!(instr, Instruction::JumpTrue(_, _));
+                let (cond, target) = (operand(a, param(0), rb), operand(b, param(1), rb));
+                let (va, vb) = (value(a, 0), value(b, 1));
+                let action = if if_true {
+                    format!("jump to {} if {} isn't 0", target, cond)
+                } else {
+                    format!("jump to {} if {} is 0", target, cond)
+                };
+                let effect = if (va != 0) == if_true {
+                    format!("{} so jumps to {}", va, vb)
+                } else {
+                    format!("{} so carries on", va)
+                };
+                (action, vec![effect])
+            }
+            Instruction::AdjRelative(a) => {
+                let va = value(a, 0);
+                (
+                    format!("add {} to the relative base", operand(a, param(0), rb)),
+                    vec![format!("relative base {} -> {}", rb, rb + va)],
+                )
+            }
+        },
+    };
+
+    for access in &step.accesses {
+        if let Access::Write { address, old, new } = access {
+            effects.push(format!("[{}]: {} -> {}", address, old, new));
+        }
+    }
+
+    Explanation {
+        pc: step.pc,
+        cells,
+        action,
+        effects,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trace::record;
+
+    #[test]
+    fn test_explain() {
+        #[rustfmt::skip]
+        let program = [
+            1001, 9, 19, 10,  // [10] = [9] + 19
+            4, 10,            // out [10]
+            1006, 9, 8,       // jf [9], 8
+            3,                // 3 is read by the first instruction
+            0,
+        ];
+        let steps: Vec<Explanation> = record(&program, &[], 3).iter().map(explain).collect();
+
+        assert_eq!(
+            steps[0].action,
+            "add the value at 9 to the literal 19, store at 10"
+        );
+        assert_eq!(steps[0].effects, vec!["3 + 19 = 22", "[10]: 0 -> 22"]);
+        assert_eq!(steps[1].effects, vec!["outputs 22"]);
+        assert_eq!(
+            steps[2].to_string(),
+            "    6: 1006,9,8\n       jump to the literal 8 if the value at 9 is 0\n       \
+             3 so carries on\n"
+        );
+    }
+
+    #[test]
+    fn test_modes() {
+        // rb += 5; [rb + 2] = input; out [rb + 2]
+        let program = [109, 5, 203, 2, 204, 2, 99];
+        let steps: Vec<Explanation> = record(&program, &[42], 10).iter().map(explain).collect();
+
+        assert_eq!(steps[0].effects, vec!["relative base 0 -> 5"]);
+        assert_eq!(
+            steps[1].action,
+            "read the next input, store at 7 (relative base 5 + 2)"
+        );
+        assert_eq!(steps[1].effects, vec!["[7]: 0 -> 42"]);
+        assert_eq!(
+            steps[2].action,
+            "output the value at 7 (relative base 5 + 2)"
+        );
+        assert_eq!(steps[2].effects, vec!["outputs 42"]);
+    }
+}
@@ -0,0 +1,931 @@
+//! The Intcode computer, first used by day 2 and extended on later days.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::mem;
+use std::str::FromStr;
+use std::sync::mpsc::{channel, Receiver, RecvError, Sender};
+
+use crate::memory::Memory;
+
+pub mod analyze;
+pub mod audit;
+#[cfg(feature = "debugger")]
+pub mod debugger;
+pub mod diff;
+pub mod disasm;
+pub mod explain;
+pub mod memory;
+pub mod minimize;
+pub mod multiplex;
+pub mod optimize;
+pub mod pack;
+#[cfg(feature = "rpc")]
+pub mod rpc;
+pub mod scheduler;
+#[cfg(feature = "serve")]
+pub mod serve;
+pub mod snapshot;
+pub mod trace;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Mode {
+    Position,
+    Immediate,
+    Relative,
+}
+
+impl From<char> for Mode {
+    fn from(ch: char) -> Self {
+        match ch {
+            '2' => Mode::Relative,
+            '1' => Mode::Immediate,
+            _ => Mode::Position,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Instruction {
+    Add(Mode, Mode, Mode),
+    Mul(Mode, Mode, Mode),
+    Input(Mode),
+    Output(Mode),
+    JumpTrue(Mode, Mode),
+    JumpFalse(Mode, Mode),
+    LessThan(Mode, Mode, Mode),
+    Equals(Mode, Mode, Mode),
+    AdjRelative(Mode),
+}
+
+impl Instruction {
+    pub fn parameters(&self) -> usize {
+        match *self {
+            Instruction::Add(_, _, _) | Instruction::Mul(_, _, _) => 3,
+            Instruction::Input(_) | Instruction::Output(_) => 1,
+            Instruction::JumpTrue(_, _) | Instruction::JumpFalse(_, _) => 2,
+            Instruction::LessThan(_, _, _) | Instruction::Equals(_, _, _) => 3,
+            Instruction::AdjRelative(_) => 1,
+        }
+    }
+}
+
+/// Why a value isn't a valid instruction when decoding strictly.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DecodeError {
+    Negative,
+    OpCode(i64),
+    Mode(i64),
+    /// Nonzero digits past the modes of the instruction's parameters.
+    ExtraDigits,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            DecodeError::Negative => write!(f, "negative instruction"),
+            DecodeError::OpCode(code) => write!(f, "unknown op code {}", code),
+            DecodeError::Mode(mode) => write!(f, "unknown mode {}", mode),
+            DecodeError::ExtraDigits => write!(f, "more modes than parameters"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum IntcodeError {
+    OpCode(Option<char>),
+    Decode { value: i64, error: DecodeError },
+    Input(RecvError),
+    MemoryLimitExceeded { address: i64, limit: usize },
+}
+
+impl From<RecvError> for IntcodeError {
+    fn from(error: RecvError) -> Self {
+        IntcodeError::Input(error)
+    }
+}
+
+impl fmt::Display for IntcodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            IntcodeError::OpCode(code) => match code {
+                Some(code) => write!(f, "unknown op code: {}", code),
+                None => write!(f, "empty op code"),
+            },
+            IntcodeError::Decode { value, error } => {
+                write!(f, "can't decode instruction {}: {}", value, error)
+            }
+            IntcodeError::Input(recv_err) => write!(f, "unable to get input: {}", recv_err),
+            IntcodeError::MemoryLimitExceeded { address, limit } => write!(
+                f,
+                "writing to address {} would use more than {} memory cells",
+                address, limit
+            ),
+        }
+    }
+}
+
+impl Instruction {
+    /// Decodes an instruction, rejecting anything the puzzles don't define:
+    /// negative values, unknown op codes or modes, and modes for parameters the
+    /// instruction doesn't have.
+    pub fn decode(value: i64) -> Result<Self, DecodeError> {
+        if value < 0 {
+            return Err(DecodeError::Negative);
+        }
+
+        let mut digits = value / 100;
+        let mut modes = [Mode::Position; 3];
+        for mode in modes.iter_mut() {
+            *mode = match digits % 10 {
+                0 => Mode::Position,
+                1 => Mode::Immediate,
+                2 => Mode::Relative,
+                digit => return Err(DecodeError::Mode(digit)),
+            };
+            digits /= 10;
+        }
+
+        let instr = match value % 100 {
+            1 => Instruction::Add(modes[0], modes[1], modes[2]),
+            2 => Instruction::Mul(modes[0], modes[1], modes[2]),
+            3 => Instruction::Input(modes[0]),
+            4 => Instruction::Output(modes[0]),
+            5 => Instruction::JumpTrue(modes[0], modes[1]),
+            6 => Instruction::JumpFalse(modes[0], modes[1]),
+            7 => Instruction::LessThan(modes[0], modes[1], modes[2]),
+            8 => Instruction::Equals(modes[0], modes[1], modes[2]),
+            9 => Instruction::AdjRelative(modes[0]),
+            code => return Err(DecodeError::OpCode(code)),
+        };
+
+        if value / 10_i64.pow(2 + instr.parameters() as u32) != 0 {
+            return Err(DecodeError::ExtraDigits);
+        }
+
+        Ok(instr)
+    }
+
+    /// Decodes the way `from_str` does on the value's digits, without
+    /// formatting it first.
+    pub fn decode_lenient(value: i64) -> Result<Self, IntcodeError> {
+        let mut digits = value.unsigned_abs();
+        let op_code = (digits % 10) as u32;
+        digits /= 100;
+
+        let mut modes = [Mode::Position; 3];
+        for mode in modes.iter_mut() {
+            *mode = match digits % 10 {
+                2 => Mode::Relative,
+                1 => Mode::Immediate,
+                _ => Mode::Position,
+            };
+            digits /= 10;
+        }
+
+        match op_code {
+            1 => Ok(Instruction::Add(modes[0], modes[1], modes[2])),
+            2 => Ok(Instruction::Mul(modes[0], modes[1], modes[2])),
+            3 => Ok(Instruction::Input(modes[0])),
+            4 => Ok(Instruction::Output(modes[0])),
+            5 => Ok(Instruction::JumpTrue(modes[0], modes[1])),
+            6 => Ok(Instruction::JumpFalse(modes[0], modes[1])),
+            7 => Ok(Instruction::LessThan(modes[0], modes[1], modes[2])),
+            8 => Ok(Instruction::Equals(modes[0], modes[1], modes[2])),
+            9 => Ok(Instruction::AdjRelative(modes[0])),
+            _ => Err(IntcodeError::OpCode(std::char::from_digit(op_code, 10))),
+        }
+    }
+}
+
+/// Decodes leniently, the way the computer always has: only the last digit of
+/// the op code is looked at, unknown modes are position mode, and extra digits
+/// are ignored.
+impl FromStr for Instruction {
+    type Err = IntcodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let chars = &mut s.chars().rev();
+
+        let op_code = match chars.next() {
+            Some(op_code) => op_code,
+            None => return Err(IntcodeError::OpCode(None)),
+        };
+
+        // skip the zero in the op code since they're two-padded and we only care about
+        // the first digit
+        chars.next();
+
+        // always take three modes, default to position
+        let mut modes = [Mode::Position; 3];
+
+        for mode in modes.iter_mut() {
+            if let Some(ch) = chars.next() {
+                *mode = Mode::from(ch);
+            }
+        }
+
+        match op_code {
+            '1' => Ok(Instruction::Add(modes[0], modes[1], modes[2])),
+            '2' => Ok(Instruction::Mul(modes[0], modes[1], modes[2])),
+            '3' => Ok(Instruction::Input(modes[0])),
+            '4' => Ok(Instruction::Output(modes[0])),
+            '5' => Ok(Instruction::JumpTrue(modes[0], modes[1])),
+            '6' => Ok(Instruction::JumpFalse(modes[0], modes[1])),
+            '7' => Ok(Instruction::LessThan(modes[0], modes[1], modes[2])),
+            '8' => Ok(Instruction::Equals(modes[0], modes[1], modes[2])),
+            '9' => Ok(Instruction::AdjRelative(modes[0])),
+            _ => Err(IntcodeError::OpCode(Some(op_code))),
+        }
+    }
+}
+
+/// How much memory a program may use before it's stopped with
+/// `IntcodeError::MemoryLimitExceeded`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MemoryLimit {
+    Cells(usize),
+    /// Approximate, counting each cell as an address and a value.
+    Bytes(usize),
+}
+
+impl MemoryLimit {
+    /// Size of a cell for `MemoryLimit::Bytes`.
+    pub const CELL_BYTES: usize = mem::size_of::<(i64, i64)>();
+
+    pub fn cells(self) -> usize {
+        match self {
+            MemoryLimit::Cells(cells) => cells,
+            MemoryLimit::Bytes(bytes) => bytes / Self::CELL_BYTES,
+        }
+    }
+}
+
+/// What the computer will do on its next step.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum State {
+    Ready,
+    /// The next instruction reads input and none is pending, so stepping will
+    /// block until some arrives.
+    AwaitingInput,
+    Halted,
+}
+
+/// A snapshot of the computer's registers and status.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Status {
+    pub pc: i64,
+    pub relative_base: i64,
+    pub state: State,
+    pub pending_input: usize,
+    pub memory_cells: usize,
+}
+
+/// A memory access made by an instruction, not counting reads of the
+/// instruction itself or its immediate parameters.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Access {
+    Read { address: i64, value: i64 },
+    Write { address: i64, old: i64, new: i64 },
+}
+
+/// Called for input when none is pending, instead of waiting on the input
+/// channel. Returning None ends the input, failing the program.
+pub type InputFallback = Box<dyn FnMut() -> Option<i64> + Send>;
+
+/// An Intcode computer that reads its input from and sends its output over
+/// channels, so it can run on its own thread.
+pub struct Intcode {
+    memory: Memory,
+    input: Receiver<i64>,
+    // input received from the channel or pushed directly but not yet read
+    pending: VecDeque<i64>,
+    output: Sender<i64>,
+    pc: i64,
+    relative_base: i64,
+    max_cells: Option<usize>,
+    fallback: Option<InputFallback>,
+    lenient: bool,
+    // memory accesses made by the last step, if they're being logged
+    accesses: Option<Vec<Access>>,
+}
+
+impl Intcode {
+    pub fn new(program: &[i64], input: Receiver<i64>, output: Sender<i64>) -> Self {
+        Intcode {
+            memory: Memory::new(program),
+            input,
+            pending: VecDeque::new(),
+            output,
+            pc: 0,
+            relative_base: 0,
+            max_cells: None,
+            fallback: None,
+            lenient: false,
+            accesses: None,
+        }
+    }
+
+    /// Stops the program with an error instead of letting it grow memory past
+    /// `limit`. The program itself counts towards the limit, but writes to cells
+    /// that are already in use are always allowed.
+    pub fn with_memory_limit(mut self, limit: MemoryLimit) -> Self {
+        self.max_cells = Some(limit.cells());
+        self
+    }
+
+    /// Calls `fallback` whenever the program reads input and none has been sent,
+    /// rather than blocking until some is, e.g. to prompt for it on stdin.
+    pub fn with_input_fallback<F>(mut self, fallback: F) -> Self
+    where
+        F: FnMut() -> Option<i64> + Send + 'static,
+    {
+        self.fallback = Some(Box::new(fallback));
+        self
+    }
+
+    /// Decodes instructions leniently (see `Instruction::from_str`) instead of
+    /// failing on malformed ones, for programs that rely on the old behavior.
+    pub fn with_lenient_decoding(mut self) -> Self {
+        self.lenient = true;
+        self
+    }
+
+    /// Logs the memory accesses each step makes, see `accesses`.
+    pub fn with_access_log(mut self) -> Self {
+        self.accesses = Some(Vec::new());
+        self
+    }
+
+    /// Returns the memory accesses made by the last step, in order. Always empty
+    /// unless logging was turned on with `with_access_log`.
+    pub fn accesses(&self) -> &[Access] {
+        self.accesses.as_deref().unwrap_or(&[])
+    }
+
+    fn log(&mut self, access: Access) {
+        if let Some(accesses) = &mut self.accesses {
+            accesses.push(access);
+        }
+    }
+
+    fn set_memory(&mut self, position: i64, value: i64) -> Result<(), IntcodeError> {
+        if let Some(limit) = self.max_cells {
+            if self.memory.len() >= limit && !self.memory.contains(position) {
+                return Err(IntcodeError::MemoryLimitExceeded {
+                    address: position,
+                    limit,
+                });
+            }
+        }
+
+        let old = self.memory.set(position, value);
+        self.log(Access::Write {
+            address: position,
+            old,
+            new: value,
+        });
+        Ok(())
+    }
+
+    /// Queues a value to be read before anything waiting in the input channel.
+    pub fn push_input(&mut self, value: i64) {
+        self.pending.push_back(value);
+    }
+
+    /// Moves anything waiting in the input channel into the pending queue without
+    /// blocking.
+    fn poll_input(&mut self) {
+        self.pending.extend(self.input.try_iter());
+    }
+
+    pub fn pc(&self) -> i64 {
+        self.pc
+    }
+
+    pub fn relative_base(&self) -> i64 {
+        self.relative_base
+    }
+
+    /// Returns the value at `address`, which is 0 if it was never written.
+    pub fn memory(&self, address: i64) -> i64 {
+        self.get_memory(address)
+    }
+
+    /// Writes `value` to `address` from outside the program, e.g. from a
+    /// debugger. The memory limit still applies.
+    pub fn write_memory(&mut self, address: i64, value: i64) -> Result<(), IntcodeError> {
+        self.set_memory(address, value)?;
+
+        // the program didn't make this access, so don't log it
+        if let Some(accesses) = &mut self.accesses {
+            accesses.pop();
+        }
+        Ok(())
+    }
+
+    /// Returns how many memory cells are in use.
+    pub fn memory_cells(&self) -> usize {
+        self.memory.len()
+    }
+
+    /// Returns the number of inputs received but not yet read, as of the last
+    /// step or call to `status`.
+    pub fn pending_input(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.get_memory(self.pc) == 99
+    }
+
+    /// Returns whether the next instruction reads input but none is pending, as
+    /// of the last step or call to `status`.
+    pub fn is_blocked(&self) -> bool {
+        self.pending.is_empty() && self.get_memory(self.pc) % 100 == 3
+    }
+
+    fn state(&self) -> State {
+        if self.is_halted() {
+            State::Halted
+        } else if self.is_blocked() {
+            State::AwaitingInput
+        } else {
+            State::Ready
+        }
+    }
+
+    /// Returns the computer's registers and status, first checking the input
+    /// channel for new values.
+    pub fn status(&mut self) -> Status {
+        self.poll_input();
+
+        Status {
+            pc: self.pc,
+            relative_base: self.relative_base,
+            state: self.state(),
+            pending_input: self.pending.len(),
+            memory_cells: self.memory.len(),
+        }
+    }
+
+    fn get_memory(&self, position: i64) -> i64 {
+        self.memory.get(position)
+    }
+
+    /// Returns the "value" indicated by the offset and mode.
+    fn get_value(&mut self, offset: i64, mode: Mode) -> i64 {
+        if mode == Mode::Immediate {
+            return self.get_memory(self.pc + offset);
+        }
+
+        let address = self.get_location(offset, mode);
+        self.read(address)
+    }
+
+    /// Reads memory on behalf of an instruction, logging the access.
+    fn read(&mut self, address: i64) -> i64 {
+        let value = self.get_memory(address);
+        self.log(Access::Read { address, value });
+        value
+    }
+
+    /// Returns the storage location indicated by the offset and mode.
+    fn get_location(&self, offset: i64, mode: Mode) -> i64 {
+        let pos = self.pc + offset;
+
+        match mode {
+            Mode::Position => self.get_memory(pos),
+            Mode::Immediate => pos,
+            Mode::Relative => self.get_memory(pos) + self.relative_base,
+        }
+    }
+
+    /// Executes the current instruction and advances the program counter. If Ok
+    /// None is returned, the instruction was executed successfully, and the program is
+    /// not finished. If Ok Some is returned, the program has halted with a diagnostic
+    /// code.
+    pub fn step(&mut self) -> Result<Option<i64>, IntcodeError> {
+        self.poll_input();
+        if let Some(accesses) = &mut self.accesses {
+            accesses.clear();
+        }
+
+        let value = self.get_memory(self.pc);
+        let instr = if self.lenient {
+            Instruction::decode_lenient(value)?
+        } else {
+            Instruction::decode(value).map_err(|error| IntcodeError::Decode { value, error })?
+        };
+
+        match instr {
+            Instruction::Add(a, b, location)
+            | Instruction::Mul(a, b, location)
+            | Instruction::LessThan(a, b, location)
+            | Instruction::Equals(a, b, location) => {
+                let a = self.get_value(1, a);
+                let b = self.get_value(2, b);
+
+                let v = match instr {
+                    Instruction::Add(_, _, _) => a + b,
+                    Instruction::Mul(_, _, _) => a * b,
+                    Instruction::LessThan(_, _, _) => (a < b) as i64,
+                    _ => (a == b) as i64,
+                };
+
+                let location = self.get_location(3, location);
+
+                self.set_memory(location, v)?;
+            }
+            Instruction::Input(location) => {
+                let location = self.get_location(1, location);
+
+                let v = match (self.pending.pop_front(), &mut self.fallback) {
+                    (Some(v), _) => v,
+                    (None, Some(fallback)) => fallback().ok_or(IntcodeError::Input(RecvError))?,
+                    (None, None) => self.input.recv()?,
+                };
+                self.set_memory(location, v)?;
+            }
+            Instruction::Output(location) => {
+                let location = self.get_location(1, location);
+                let out = self.read(location);
+
+                // any error means amplification is done since nobody's listening,
+                // we should return the last output
+                if self.output.send(out).is_err() {
+                    return Ok(Some(out));
+                }
+            }
+            Instruction::JumpTrue(a, jmp) | Instruction::JumpFalse(a, jmp) => {
+                let a = self.get_value(1, a);
+                let jmp = self.get_value(2, jmp);
+
+                let cond = match instr {
+                    Instruction::JumpTrue(_, _) => a != 0,
+                    _ => a == 0,
+                };
+
+                if cond {
+                    self.pc = jmp;
+                    return Ok(None);
+                }
+            }
+            Instruction::AdjRelative(a) => {
+                let a = self.get_value(1, a);
+                self.relative_base += a;
+            }
+        }
+
+        let params = instr.parameters();
+        self.pc += (params as i64) + 1;
+
+        Ok(None)
+    }
+
+    /// Evaluate runs the program until it halts.
+    pub fn evaluate(&mut self) -> Result<Option<i64>, IntcodeError> {
+        while self.get_memory(self.pc) != 99 {
+            let out = self.step()?;
+
+            if out.is_some() {
+                return Ok(out);
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Runs the program until it halts with the given input, and returns
+    /// everything it output. Asking for more input than it's given is an error.
+    pub fn run_with_inputs(program: &[i64], inputs: &[i64]) -> Result<Vec<i64>, IntcodeError> {
+        let (_, recv_in) = channel();
+        let (send_out, recv_out) = channel();
+
+        let mut computer = Intcode::new(program, recv_in, send_out);
+        for input in inputs {
+            computer.push_input(*input);
+        }
+        computer.evaluate()?;
+
+        Ok(recv_out.try_iter().collect())
+    }
+}
+
+/// Returns the addresses holding nonzero values as sorted, inclusive ranges of
+/// consecutive addresses.
+fn nonzero_ranges(memory: &Memory) -> Vec<(i64, i64)> {
+    let mut addresses: Vec<i64> = memory
+        .iter()
+        .filter(|(_, value)| *value != 0)
+        .map(|(address, _)| address)
+        .collect();
+    addresses.sort_unstable();
+
+    let mut ranges: Vec<(i64, i64)> = Vec::new();
+    for address in addresses {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == address => *end = address,
+            _ => ranges.push((address, address)),
+        }
+    }
+
+    ranges
+}
+
+/// Number of upcoming instructions shown by `Display`.
+const SHOWN_INSTRUCTIONS: usize = 4;
+/// Number of values shown for each memory range by `Display`.
+const SHOWN_VALUES: usize = 8;
+/// Number of memory ranges shown by `Display`.
+const SHOWN_RANGES: usize = 16;
+
+/// Shows the registers, the next few instructions and the nonzero memory, e.g.
+///
+/// ```text
+/// pc 6, rb 5, ready, 0 pending inputs, 15 cells
+///        6: 22201,10,11,12   add [rb+10], [rb+11], [rb+12]
+///       10: 204,12           out [rb+12]
+///       12: 99               hlt
+/// memory:
+///       0..=12: 109,5,203,10,203,11,22201,10,... (5 more)
+///      15..=16: 20,22
+/// ```
+impl fmt::Display for Intcode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = match self.state() {
+            State::Ready => "ready",
+            State::AwaitingInput => "awaiting input",
+            State::Halted => "halted",
+        };
+        writeln!(
+            f,
+            "pc {}, rb {}, {}, {} pending inputs, {} cells",
+            self.pc,
+            self.relative_base,
+            state,
+            self.pending.len(),
+            self.memory.len()
+        )?;
+
+        let mut pc = self.pc;
+        for _ in 0..SHOWN_INSTRUCTIONS {
+            // the longest instruction is four cells
+            let cells: Vec<i64> = (pc..pc + 4)
+                .map(|address| self.get_memory(address))
+                .collect();
+            let (text, len) = match disasm::decode_one(&cells) {
+                Some(decoded) => decoded,
+                None => {
+                    writeln!(f, "{:>8}: {:<16} data", pc, cells[0])?;
+                    break;
+                }
+            };
+
+            let raw: Vec<String> = cells[..len].iter().map(|cell| cell.to_string()).collect();
+            writeln!(f, "{:>8}: {:<16} {}", pc, raw.join(","), text.trim_end())?;
+
+            if cells[0] == 99 {
+                break;
+            }
+            pc += len as i64;
+        }
+
+        writeln!(f, "memory:")?;
+        let ranges = nonzero_ranges(&self.memory);
+        for &(start, end) in ranges.iter().take(SHOWN_RANGES) {
+            let values: Vec<String> = (start..=end)
+                .take(SHOWN_VALUES)
+                .map(|address| self.get_memory(address).to_string())
+                .collect();
+
+            let len = (end - start + 1) as usize;
+            let rest = if len > SHOWN_VALUES {
+                format!(",... ({} more)", len - SHOWN_VALUES)
+            } else {
+                String::new()
+            };
+
+            writeln!(
+                f,
+                "{:>12}: {}{}",
+                format!("{}..={}", start, end),
+                values.join(","),
+                rest
+            )?;
+        }
+
+        if ranges.len() > SHOWN_RANGES {
+            writeln!(
+                f,
+                "{:>12}  ... {} more ranges",
+                "",
+                ranges.len() - SHOWN_RANGES
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Summarizes memory instead of listing every cell.
+impl fmt::Debug for Intcode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Intcode")
+            .field("pc", &self.pc)
+            .field("relative_base", &self.relative_base)
+            .field("state", &self.state())
+            .field("pending_input", &self.pending.len())
+            .field("memory_cells", &self.memory.len())
+            .field("nonzero_ranges", &nonzero_ranges(&self.memory).len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc::channel;
+
+    fn evaluate(program: &[i64], limit: Option<MemoryLimit>) -> Result<Option<i64>, IntcodeError> {
+        let (_send_in, recv_in) = channel();
+        let (send_out, _recv_out) = channel();
+
+        let mut computer = Intcode::new(program, recv_in, send_out);
+        if let Some(limit) = limit {
+            computer = computer.with_memory_limit(limit);
+        }
+
+        computer.evaluate()
+    }
+
+    #[test]
+    fn test_memory_limit() {
+        // writes to ever higher addresses: 1000, 1001, ...
+        let runaway = [1101, 0, 0, 1000, 1001, 3, 1, 3, 1105, 1, 0];
+
+        assert_eq!(
+            evaluate(&runaway, Some(MemoryLimit::Cells(20))),
+            Err(IntcodeError::MemoryLimitExceeded {
+                address: 1009,
+                limit: 20
+            })
+        );
+        assert_eq!(MemoryLimit::Bytes(320).cells(), 20);
+
+        // counts to 5 in a cell inside the program, so never needs new cells
+        let bounded = [1001, 13, 1, 13, 1008, 13, 5, 14, 1006, 14, 0, 99, 0, 0, 0];
+        assert_eq!(
+            evaluate(&bounded, Some(MemoryLimit::Cells(bounded.len()))),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn test_status() {
+        // reads two values and outputs their sum
+        let program = [109, 5, 203, 10, 203, 11, 22201, 10, 11, 12, 204, 12, 99];
+        let (send_in, recv_in) = channel();
+        let (send_out, recv_out) = channel();
+        let mut computer = Intcode::new(&program, recv_in, send_out);
+
+        computer.step().unwrap();
+        let status = computer.status();
+        assert_eq!((status.pc, status.relative_base), (2, 5));
+        assert_eq!(status.state, State::AwaitingInput);
+
+        send_in.send(20).unwrap();
+        computer.push_input(22);
+        let status = computer.status();
+        assert_eq!(status.state, State::Ready);
+        // pushed values are read first
+        assert_eq!(status.pending_input, 2);
+
+        computer.step().unwrap();
+        assert_eq!(computer.memory(15), 22);
+        assert_eq!(computer.pending_input(), 1);
+        assert!(!computer.is_blocked());
+
+        computer.evaluate().unwrap();
+        assert!(computer.is_halted());
+        assert_eq!(computer.status().state, State::Halted);
+        assert_eq!(computer.memory_cells(), program.len() + 3);
+        assert_eq!(recv_out.recv(), Ok(42));
+    }
+
+    #[test]
+    fn test_display() {
+        let program = [109, 5, 203, 10, 203, 11, 22201, 10, 11, 12, 204, 12, 99];
+        let (_send_in, recv_in) = channel();
+        let (send_out, _recv_out) = channel();
+        let mut computer = Intcode::new(&program, recv_in, send_out);
+        computer.push_input(20);
+        computer.push_input(22);
+        for _ in 0..3 {
+            computer.step().unwrap();
+        }
+
+        assert_eq!(
+            computer.to_string(),
+            "pc 6, rb 5, ready, 0 pending inputs, 15 cells\n\
+             \x20      6: 22201,10,11,12   add [rb+10], [rb+11], [rb+12]\n\
+             \x20     10: 204,12           out [rb+12]\n\
+             \x20     12: 99               hlt\n\
+             memory:\n\
+             \x20     0..=12: 109,5,203,10,203,11,22201,10,... (5 more)\n\
+             \x20    15..=16: 20,22\n"
+        );
+        assert_eq!(
+            format!("{:?}", computer),
+            "Intcode { pc: 6, relative_base: 5, state: Ready, pending_input: 0, \
+             memory_cells: 15, nonzero_ranges: 2 }"
+        );
+    }
+
+    #[test]
+    fn test_far_write() {
+        let program = [1101, 1, 1, 1_000_000_000_000_000, 99];
+        assert!(evaluate(&program, Some(MemoryLimit::Cells(5))).is_err());
+        assert_eq!(evaluate(&program, None), Ok(None));
+    }
+
+    #[test]
+    fn test_input_fallback() {
+        // outputs the sum of three inputs
+        let program = [3, 20, 3, 21, 3, 22, 1, 20, 21, 23, 1, 22, 23, 23, 4, 23, 99];
+        let (_send_in, recv_in) = channel();
+        let (send_out, recv_out) = channel();
+
+        let mut fallback = vec![20, 10];
+        let mut computer =
+            Intcode::new(&program, recv_in, send_out).with_input_fallback(move || fallback.pop());
+        computer.push_input(1);
+        assert_eq!(computer.evaluate(), Ok(None));
+        assert_eq!(recv_out.try_iter().collect::<Vec<_>>(), vec![31]);
+
+        let (_send_in, recv_in) = channel();
+        let (send_out, _recv_out) = channel();
+        let mut computer = Intcode::new(&program, recv_in, send_out).with_input_fallback(|| None);
+        assert_eq!(computer.evaluate(), Err(IntcodeError::Input(RecvError)));
+    }
+
+    #[test]
+    fn test_run_with_inputs() {
+        // outputs the sum of two inputs, then 99
+        let program = [3, 13, 3, 14, 1, 13, 14, 13, 4, 13, 104, 99, 99, 0, 0];
+        assert_eq!(Intcode::run_with_inputs(&program, &[2, 3]), Ok(vec![5, 99]));
+        assert!(matches!(
+            Intcode::run_with_inputs(&program, &[2]),
+            Err(IntcodeError::Input(_))
+        ));
+    }
+
+    #[test]
+    fn test_decode() {
+        use Mode::*;
+
+        assert_eq!(
+            Instruction::decode(1002),
+            Ok(Instruction::Mul(Position, Immediate, Position))
+        );
+        assert_eq!(
+            Instruction::decode(21107),
+            Ok(Instruction::LessThan(Immediate, Immediate, Relative))
+        );
+        assert_eq!(Instruction::decode(-1), Err(DecodeError::Negative));
+        assert_eq!(Instruction::decode(42), Err(DecodeError::OpCode(42)));
+        assert_eq!(Instruction::decode(1301), Err(DecodeError::Mode(3)));
+        assert_eq!(Instruction::decode(1104), Err(DecodeError::ExtraDigits));
+        assert_eq!(Instruction::decode(101101), Err(DecodeError::ExtraDigits));
+
+        // adds [5] and 5, with an unknown first mode that lenient decoding reads as
+        // position mode
+        let program = [1301, 5, 5, 5, 99, 21];
+        assert_eq!(
+            evaluate(&program, None),
+            Err(IntcodeError::Decode {
+                value: 1301,
+                error: DecodeError::Mode(3)
+            })
+        );
+
+        let (_send_in, recv_in) = channel();
+        let (send_out, _recv_out) = channel();
+        let mut computer = Intcode::new(&program, recv_in, send_out).with_lenient_decoding();
+        assert_eq!(computer.evaluate(), Ok(None));
+        assert_eq!(computer.memory(5), 26);
+    }
+
+    #[test]
+    fn test_decode_lenient() {
+        let values = (-30_000..30_000).chain(vec![i64::MIN, i64::MAX, 1_234_567_890_109]);
+        for value in values {
+            assert_eq!(
+                Instruction::decode_lenient(value),
+                Instruction::from_str(&value.to_string()),
+                "{}",
+                value
+            );
+        }
+    }
+}
@@ -0,0 +1,561 @@
+use common::parse::parse_csv;
+#[cfg(feature = "debugger")]
+use intcode::debugger::{remote, Command, Debugger};
+use intcode::multiplex::Multiplexer;
+use intcode::snapshot::Snapshot;
+use intcode::{analyze, audit, disasm, explain, optimize, pack, trace, Intcode, State};
+use std::convert::TryFrom;
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, BufWriter, Write};
+use std::process;
+use std::sync::mpsc::channel;
+
+const USAGE: &str = "usage: intcode analyze <program>
+       intcode audit <program> [--input <values>] [--every <steps>]
+       intcode audit-diff <audit> <audit>
+       intcode debug <program> [--listen <host:port>]
+       intcode disasm <program>
+       intcode explain <program> [--input <values>]
+       intcode optimize <program> [--input <values>]
+       intcode pack <program or snapshot> <out>
+       intcode play <program> [--script <file>] [--transcript <file>]
+       intcode rpc [--listen <host:port>]
+       intcode serve <program> [--addr <host:port>]
+       intcode trace <program> [--input <values>]
+       intcode trace-diff <trace> <trace> [--context <steps>]
+       intcode unpack <file>";
+
+/// Default address for `serve`, only reachable from this machine.
+const DEFAULT_ADDR: &str = "127.0.0.1:8019";
+/// Default address for `rpc`, also only reachable from this machine.
+const DEFAULT_RPC_ADDR: &str = "127.0.0.1:8020";
+/// Most steps `trace` records.
+const TRACE_LIMIT: usize = 10_000_000;
+/// Most steps `audit` runs.
+const AUDIT_LIMIT: usize = 1_000_000_000;
+/// Default steps between `audit` checkpoints.
+const DEFAULT_AUDIT_INTERVAL: usize = 10_000;
+/// Most steps `explain` describes, since it's meant for small programs.
+const EXPLAIN_LIMIT: usize = 1_000;
+/// Default steps of context `trace-diff` shows around a divergence.
+const DEFAULT_CONTEXT: usize = 5;
+
+/// Reads a program from a file of comma-separated values, or one packed with
+/// `pack`.
+fn read_program(path: &str) -> Vec<i64> {
+    let bytes = match fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(err) => {
+            eprintln!("can't read {}: {}", path, err);
+            process::exit(1);
+        }
+    };
+
+    if bytes.starts_with(pack::PROGRAM_MAGIC) {
+        return pack::unpack_program(&bytes).unwrap_or_else(|err| {
+            eprintln!("{}: {}", path, err);
+            process::exit(1);
+        });
+    }
+
+    match parse_csv(&String::from_utf8_lossy(&bytes)) {
+        Ok(program) => program,
+        Err(err) => {
+            eprintln!("{}: {}", path, err);
+            process::exit(1);
+        }
+    }
+}
+
+fn parse_or_exit<T>(text: &str, what: &str) -> T
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    text.parse().unwrap_or_else(|err| {
+        eprintln!("bad {} {:?}: {}", what, text, err);
+        process::exit(2);
+    })
+}
+
+fn parse_input(input: &str) -> Vec<i64> {
+    parse_csv(input).unwrap_or_else(|err| {
+        eprintln!("bad input: {}", err);
+        process::exit(2);
+    })
+}
+
+fn usage() -> ! {
+    eprintln!("{}", USAGE);
+    process::exit(2);
+}
+
+/// Reads debugger commands from stdin until it's closed or told to quit.
+#[cfg(feature = "debugger")]
+fn debug(program: &[i64]) {
+    let mut debugger = Debugger::new(program);
+    let stdin = io::stdin();
+
+    print!("(intcode) ");
+    io::stdout().flush().unwrap();
+    for line in stdin.lock().lines() {
+        let line = line.unwrap();
+        match line.trim() {
+            "" => {}
+            "quit" | "q" => return,
+            line => match line.parse::<Command>() {
+                Ok(command) => println!("{}", debugger.execute(&command)),
+                Err(err) => println!("{}", err),
+            },
+        }
+
+        print!("(intcode) ");
+        io::stdout().flush().unwrap();
+    }
+}
+
+/// Serves the debugger on `addr` for other processes to drive.
+#[cfg(feature = "debugger")]
+fn debug_remote(program: &[i64], addr: &str) {
+    let listener = std::net::TcpListener::bind(addr).unwrap_or_else(|err| {
+        eprintln!("can't listen on {}: {}", addr, err);
+        process::exit(1);
+    });
+    eprintln!("debugger listening on {}", listener.local_addr().unwrap());
+
+    if let Err(err) = remote::serve(&mut Debugger::new(program), listener) {
+        eprintln!("{}", err);
+        process::exit(1);
+    }
+}
+
+#[cfg(not(feature = "debugger"))]
+fn debug(_program: &[i64]) {
+    eprintln!("intcode was built without the debugger, rebuild with `--features debugger`");
+    process::exit(1);
+}
+
+#[cfg(not(feature = "debugger"))]
+fn debug_remote(program: &[i64], _addr: &str) {
+    debug(program)
+}
+
+/// Shows output as ASCII where it can, and anything else as a number on its
+/// own line.
+fn ascii(output: impl Iterator<Item = i64>) -> String {
+    output
+        .map(|value| match u8::try_from(value) {
+            Ok(byte) if byte.is_ascii() => (byte as char).to_string(),
+            _ => format!("{}\n", value),
+        })
+        .collect()
+}
+
+/// Handles `save <file>` and `load <file>`, returning whether the line was
+/// one of them. Snapshots saved to a `.bin` file are packed.
+fn meta_command(computer: &mut Intcode, line: &str) -> bool {
+    let (command, path) = match line.trim().split_once(' ') {
+        Some((command, path)) => (command, path.trim()),
+        None => return false,
+    };
+
+    let result = match command {
+        "save" => {
+            let snapshot = computer.snapshot();
+            let contents = if path.ends_with(".bin") {
+                snapshot.pack()
+            } else {
+                snapshot.to_string().into_bytes()
+            };
+            fs::write(path, contents)
+                .map(|_| "saved")
+                .map_err(|err| err.to_string())
+        }
+        "load" => fs::read(path)
+            .map_err(|err| err.to_string())
+            .and_then(|bytes| read_snapshot(&bytes))
+            .map(|snapshot| computer.restore(&snapshot))
+            .map(|_| "loaded"),
+        _ => return false,
+    };
+
+    match result {
+        Ok(done) => eprintln!("({} {})", done, path),
+        Err(err) => eprintln!("(can't {} {}: {})", command, path, err),
+    }
+    true
+}
+
+/// Reads a snapshot saved as text or packed.
+fn read_snapshot(bytes: &[u8]) -> Result<Snapshot, String> {
+    if bytes.starts_with(pack::SNAPSHOT_MAGIC) {
+        Snapshot::unpack(bytes).map_err(|err| err.to_string())
+    } else {
+        String::from_utf8_lossy(bytes).parse()
+    }
+}
+
+/// Prints text from the program, copying it to the transcript if there is one.
+fn show(text: &str, transcript: &mut Option<fs::File>) {
+    print!("{}", text);
+    io::stdout().flush().unwrap();
+    if let Some(transcript) = transcript {
+        transcript.write_all(text.as_bytes()).unwrap();
+    }
+}
+
+/// Runs the program interactively, reading its input from stdin a line at a
+/// time and printing output as ASCII where it can. `save <file>` and
+/// `load <file>` save and restore the computer instead of going to the
+/// program. With a transcript, everything the program prints and everything
+/// typed is also written to that file. With a script, its lines are typed
+/// first, and stdin takes over once they run out.
+fn play(program: &[i64], script: Option<&str>, transcript: Option<&str>) {
+    let (_send_in, recv_in) = channel();
+    let (send_out, recv_out) = channel();
+    let mut computer = Intcode::new(program, recv_in, send_out);
+
+    let mut transcript = transcript.map(|path| {
+        fs::File::create(path).unwrap_or_else(|err| {
+            eprintln!("can't create {}: {}", path, err);
+            process::exit(1);
+        })
+    });
+
+    let script = match script {
+        Some(path) => fs::read_to_string(path).unwrap_or_else(|err| {
+            eprintln!("can't read {}: {}", path, err);
+            process::exit(1);
+        }),
+        None => String::new(),
+    };
+    let mut script = script
+        .lines()
+        .map(String::from)
+        .collect::<Vec<_>>()
+        .into_iter();
+    let mut stdin = io::BufReader::new(io::stdin()).lines();
+    let mut lines = Multiplexer::new()
+        .blocking(move || {
+            // echo scripted lines as if they'd been typed
+            let line = script.next()?;
+            println!("{}", line);
+            Some(line)
+        })
+        .blocking(move || stdin.next()?.ok());
+    loop {
+        // run until the program wants input it hasn't been given
+        let state = loop {
+            match computer.status().state {
+                State::Ready => {
+                    if let Err(err) = computer.step() {
+                        show(&ascii(recv_out.try_iter()), &mut transcript);
+                        eprintln!("{}", err);
+                        process::exit(1);
+                    }
+                }
+                state => break state,
+            }
+        };
+        show(&ascii(recv_out.try_iter()), &mut transcript);
+
+        if state == State::Halted {
+            return;
+        }
+        let line = match lines.next() {
+            Some(line) => line,
+            None => return,
+        };
+
+        if let Some(transcript) = &mut transcript {
+            writeln!(transcript, "{}", line).unwrap();
+        }
+        if !meta_command(&mut computer, &line) {
+            for byte in line.bytes().chain(Some(b'\n')) {
+                computer.push_input(i64::from(byte));
+            }
+        }
+    }
+}
+
+/// Prints a trace of the program, one step per line.
+fn trace(program: &[i64], input: &[i64]) {
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    for step in trace::record(program, input, TRACE_LIMIT) {
+        writeln!(out, "{}", step).unwrap();
+    }
+}
+
+/// Prints a checkpoint of the program's state every `every` steps, one per
+/// line.
+fn audit(program: &[i64], input: &[i64], every: usize) {
+    if every == 0 {
+        usage();
+    }
+    for checkpoint in audit::record(program, input, every, AUDIT_LIMIT) {
+        println!("{}", checkpoint);
+    }
+}
+
+/// Reports where two audits diverge, exiting with 1 if they do.
+fn audit_diff(left: &str, right: &str) {
+    let read = |path: &str| {
+        let text = fs::read_to_string(path).unwrap_or_else(|err| {
+            eprintln!("can't read {}: {}", path, err);
+            process::exit(1);
+        });
+        audit::parse(&text).unwrap_or_else(|err| {
+            eprintln!("{}: {}", path, err);
+            process::exit(1);
+        })
+    };
+
+    match audit::compare(&read(left), &read(right)) {
+        Some(divergence) => {
+            println!("{}", divergence);
+            process::exit(1);
+        }
+        None => println!("audits are the same"),
+    }
+}
+
+/// Packs a program or a saved snapshot into `out`.
+fn pack(path: &str, out: &str) {
+    let text = fs::read_to_string(path).unwrap_or_else(|err| {
+        eprintln!("can't read {}: {}", path, err);
+        process::exit(1);
+    });
+
+    let packed = match (parse_csv(&text), text.parse::<Snapshot>()) {
+        (Ok(program), _) => pack::pack_program(&program),
+        (_, Ok(snapshot)) => snapshot.pack(),
+        (Err(program_err), Err(snapshot_err)) => {
+            eprintln!(
+                "{} is neither a program ({}) nor a snapshot ({})",
+                path, program_err, snapshot_err
+            );
+            process::exit(1);
+        }
+    };
+
+    if let Err(err) = fs::write(out, &packed) {
+        eprintln!("can't write {}: {}", out, err);
+        process::exit(1);
+    }
+    eprintln!("{} bytes down to {}", text.len(), packed.len());
+}
+
+/// Prints a packed program or snapshot as text.
+fn unpack(path: &str) {
+    let bytes = fs::read(path).unwrap_or_else(|err| {
+        eprintln!("can't read {}: {}", path, err);
+        process::exit(1);
+    });
+
+    if bytes.starts_with(pack::SNAPSHOT_MAGIC) {
+        match Snapshot::unpack(&bytes) {
+            Ok(snapshot) => print!("{}", snapshot),
+            Err(err) => {
+                eprintln!("{}: {}", path, err);
+                process::exit(1);
+            }
+        }
+    } else {
+        let cells: Vec<String> = read_program(path).iter().map(i64::to_string).collect();
+        println!("{}", cells.join(","));
+    }
+}
+
+/// Runs the program, describing every step and then why it stopped.
+fn explain(program: &[i64], input: &[i64]) {
+    let (_send_in, recv_in) = channel();
+    let (send_out, _recv_out) = channel();
+    let mut computer = Intcode::new(program, recv_in, send_out);
+    for value in input {
+        computer.push_input(*value);
+    }
+
+    let mut steps = Vec::new();
+    let result = trace::record_from(&mut computer, EXPLAIN_LIMIT, &mut steps);
+    for step in &steps {
+        println!("{}", explain::explain(step));
+    }
+
+    match (result, computer.status().state) {
+        (Err(err), _) => println!("{:>5}: fails: {}", computer.pc(), err),
+        (Ok(()), State::Halted) => println!("{:>5}: 99\n       halt", computer.pc()),
+        (Ok(()), State::AwaitingInput) => println!(
+            "{:>5}: waits for input, pass more with --input",
+            computer.pc()
+        ),
+        (Ok(()), State::Ready) => println!("stopped after {} steps", EXPLAIN_LIMIT),
+    }
+}
+
+fn analyze(program: &[i64]) {
+    let findings = analyze::analyze(program);
+    if findings.is_empty() {
+        println!("nothing found");
+    }
+    for finding in findings {
+        println!("{}", finding);
+    }
+}
+
+/// Prints the optimized program, checking it against the original on the input.
+fn optimize(program: &[i64], input: &[i64]) {
+    let optimized = optimize::optimize(program).unwrap_or_else(|err| {
+        eprintln!("can't optimize: {}", err);
+        process::exit(1);
+    });
+
+    match optimize::verify(program, &optimized.program, input) {
+        Ok((original, faster)) => eprintln!(
+            "{} constants, {} branches resolved, {} instructions skipped, {} jumps threaded: \
+             {} steps down to {}",
+            optimized.constants,
+            optimized.branches,
+            optimized.removed,
+            optimized.threaded,
+            original.steps,
+            faster.steps
+        ),
+        Err(mismatch) => {
+            eprintln!("the optimized program behaves differently: {}", mismatch);
+            process::exit(1);
+        }
+    }
+
+    let cells: Vec<String> = optimized.program.iter().map(i64::to_string).collect();
+    println!("{}", cells.join(","));
+}
+
+fn read_trace(path: &str) -> Vec<trace::Step> {
+    let text = fs::read_to_string(path).unwrap_or_else(|err| {
+        eprintln!("can't read {}: {}", path, err);
+        process::exit(1);
+    });
+
+    trace::parse(&text).unwrap_or_else(|err| {
+        eprintln!("{}: {}", path, err);
+        process::exit(1);
+    })
+}
+
+/// Reports where two traces diverge, exiting with 1 if they do.
+fn trace_diff(left: &str, right: &str, context: usize) {
+    match trace::diff(&read_trace(left), &read_trace(right), context) {
+        Some(report) => {
+            print!("{}", report);
+            process::exit(1);
+        }
+        None => println!("traces are the same"),
+    }
+}
+
+#[cfg(feature = "serve")]
+fn serve(program: &[i64], addr: &str) {
+    let addr = addr.parse().unwrap_or_else(|err| {
+        eprintln!("bad address {:?}: {}", addr, err);
+        process::exit(2);
+    });
+
+    if let Err(err) = intcode::serve::serve(program, addr) {
+        eprintln!("can't serve on {}: {}", addr, err);
+        process::exit(1);
+    }
+}
+
+#[cfg(not(feature = "serve"))]
+fn serve(_program: &[i64], _addr: &str) {
+    eprintln!("intcode was built without the web UI, rebuild with `--features serve`");
+    process::exit(1);
+}
+
+#[cfg(feature = "rpc")]
+fn rpc(addr: &str) {
+    let listener = std::net::TcpListener::bind(addr).unwrap_or_else(|err| {
+        eprintln!("can't listen on {}: {}", addr, err);
+        process::exit(1);
+    });
+    eprintln!("JSON-RPC listening on {}", listener.local_addr().unwrap());
+
+    if let Err(err) = intcode::rpc::serve(listener) {
+        eprintln!("rpc: {}", err);
+        process::exit(1);
+    }
+}
+
+#[cfg(not(feature = "rpc"))]
+fn rpc(_addr: &str) {
+    eprintln!("intcode was built without the JSON-RPC service, rebuild with `--features rpc`");
+    process::exit(1);
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    // the only command that doesn't take a program
+    match args.as_slice() {
+        [command] if command == "rpc" => return rpc(DEFAULT_RPC_ADDR),
+        [command, flag, addr] if command == "rpc" && flag == "--listen" => return rpc(addr),
+        _ => {}
+    }
+
+    let (command, path) = match args.as_slice() {
+        [command, path, ..] => (command.as_str(), path.as_str()),
+        _ => usage(),
+    };
+
+    match (command, &args[2..]) {
+        ("analyze", []) => analyze(&read_program(path)),
+        ("audit", flags) => {
+            let (mut input, mut every) = (Vec::new(), DEFAULT_AUDIT_INTERVAL);
+            for pair in flags.chunks(2) {
+                match pair {
+                    [flag, values] if flag == "--input" => input = parse_input(values),
+                    [flag, steps] if flag == "--every" => every = parse_or_exit(steps, "steps"),
+                    _ => usage(),
+                }
+            }
+            audit(&read_program(path), &input, every)
+        }
+        ("audit-diff", [right]) => audit_diff(path, right),
+        ("debug", []) => debug(&read_program(path)),
+        ("debug", [flag, addr]) if flag == "--listen" => debug_remote(&read_program(path), addr),
+        ("explain", []) => explain(&read_program(path), &[]),
+        ("explain", [flag, input]) if flag == "--input" => {
+            explain(&read_program(path), &parse_input(input))
+        }
+        ("disasm", []) => print!("{}", disasm::disassemble(&read_program(path))),
+        ("optimize", []) => optimize(&read_program(path), &[]),
+        ("optimize", [flag, input]) if flag == "--input" => {
+            optimize(&read_program(path), &parse_input(input))
+        }
+        ("pack", [out]) => pack(path, out),
+        ("unpack", []) => unpack(path),
+        ("play", flags) => {
+            let (mut script, mut transcript) = (None, None);
+            for pair in flags.chunks(2) {
+                match pair {
+                    [flag, file] if flag == "--script" => script = Some(file.as_str()),
+                    [flag, file] if flag == "--transcript" => transcript = Some(file.as_str()),
+                    _ => usage(),
+                }
+            }
+            play(&read_program(path), script, transcript)
+        }
+        ("serve", []) => serve(&read_program(path), DEFAULT_ADDR),
+        ("serve", [flag, addr]) if flag == "--addr" => serve(&read_program(path), addr),
+        ("trace", []) => trace(&read_program(path), &[]),
+        ("trace", [flag, input]) if flag == "--input" => {
+            trace(&read_program(path), &parse_input(input))
+        }
+        ("trace-diff", [right]) => trace_diff(path, right, DEFAULT_CONTEXT),
+        ("trace-diff", [right, flag, context]) if flag == "--context" => {
+            trace_diff(path, right, parse_or_exit(context, "context"))
+        }
+        _ => usage(),
+    }
+}
@@ -0,0 +1,156 @@
+//! The computer's memory. By default it's sparse, a map from address to value,
+//! so a program that writes far past its own end only pays for the cells it
+//! uses. The `dense-memory` feature keeps the first `DENSE_CELLS` cells in one
+//! array instead, which is faster for programs that stay near their code, as
+//! every puzzle so far does, with anything outside it in a map as before.
+//! Either way the same cells count as in use, so the memory limit and
+//! everything that lists memory behave the same.
+
+use std::collections::HashMap;
+use std::iter::FromIterator;
+
+/// Most cells shown by one request from the debugger or the RPC service, which
+/// may be serving someone else.
+pub const MAX_CELLS: u64 = 100_000;
+
+/// How many cells from address 0 dense memory keeps in its array, 16 MiB
+/// worth at most.
+#[cfg(feature = "dense-memory")]
+pub const DENSE_CELLS: usize = 1 << 20;
+
+#[derive(Debug, Clone, Default)]
+pub struct Memory {
+    #[cfg(feature = "dense-memory")]
+    dense: Vec<Option<i64>>,
+    // cells written in `dense`
+    #[cfg(feature = "dense-memory")]
+    used: usize,
+    // every cell with sparse memory, or the ones outside `dense`
+    sparse: HashMap<i64, i64>,
+}
+
+impl Memory {
+    /// Memory holding the program from address 0.
+    pub fn new(program: &[i64]) -> Self {
+        program
+            .iter()
+            .enumerate()
+            .map(|(address, value)| (address as i64, *value))
+            .collect()
+    }
+
+    /// Returns the index of `address` in the array, if it belongs there.
+    #[cfg(feature = "dense-memory")]
+    fn index(address: i64) -> Option<usize> {
+        Some(address as usize).filter(|_| (0..DENSE_CELLS as i64).contains(&address))
+    }
+
+    /// Returns the value at `address`, which is 0 if it was never written.
+    pub fn get(&self, address: i64) -> i64 {
+        #[cfg(feature = "dense-memory")]
+        {
+            if let Some(index) = Memory::index(address) {
+                return self.dense.get(index).copied().flatten().unwrap_or(0);
+            }
+        }
+        self.sparse.get(&address).copied().unwrap_or(0)
+    }
+
+    /// Writes `value` to `address`, returning what was there before.
+    pub fn set(&mut self, address: i64, value: i64) -> i64 {
+        #[cfg(feature = "dense-memory")]
+        {
+            if let Some(index) = Memory::index(address) {
+                if index >= self.dense.len() {
+                    self.dense.resize(index + 1, None);
+                }
+                let old = self.dense[index].replace(value);
+                if old.is_none() {
+                    self.used += 1;
+                }
+                return old.unwrap_or(0);
+            }
+        }
+        self.sparse.insert(address, value).unwrap_or(0)
+    }
+
+    /// Returns whether `address` is one of the cells in use.
+    pub fn contains(&self, address: i64) -> bool {
+        #[cfg(feature = "dense-memory")]
+        {
+            if let Some(index) = Memory::index(address) {
+                return matches!(self.dense.get(index), Some(Some(_)));
+            }
+        }
+        self.sparse.contains_key(&address)
+    }
+
+    /// Returns how many cells are in use.
+    pub fn len(&self) -> usize {
+        #[cfg(feature = "dense-memory")]
+        return self.used + self.sparse.len();
+        #[cfg(not(feature = "dense-memory"))]
+        return self.sparse.len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns every cell in use with its address, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (i64, i64)> + '_ {
+        let sparse = self
+            .sparse
+            .iter()
+            .map(|(address, value)| (*address, *value));
+        #[cfg(feature = "dense-memory")]
+        return self
+            .dense
+            .iter()
+            .enumerate()
+            .filter_map(|(address, value)| value.map(|value| (address as i64, value)))
+            .chain(sparse);
+        #[cfg(not(feature = "dense-memory"))]
+        return sparse;
+    }
+}
+
+impl FromIterator<(i64, i64)> for Memory {
+    fn from_iter<I: IntoIterator<Item = (i64, i64)>>(cells: I) -> Self {
+        let mut memory = Memory::default();
+        for (address, value) in cells {
+            memory.set(address, value);
+        }
+        memory
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory() {
+        let mut memory = Memory::new(&[1, 2, 3]);
+        assert_eq!(memory.len(), 3);
+        assert_eq!(memory.get(1), 2);
+        assert_eq!(memory.get(100), 0);
+        assert!(!memory.contains(100));
+
+        assert_eq!(memory.set(1, 5), 2);
+        assert_eq!(memory.set(-4, 7), 0);
+        assert_eq!(memory.set(10, 0), 0);
+        assert_eq!(memory.set(1 << 40, 8), 0);
+        assert_eq!(memory.get(-4), 7);
+        assert!(memory.contains(10));
+        assert!(!memory.contains(9));
+        assert_eq!(memory.len(), 6);
+
+        let mut cells: Vec<(i64, i64)> = memory.iter().collect();
+        cells.sort_unstable();
+        assert_eq!(
+            cells,
+            vec![(-4, 7), (0, 1), (1, 5), (2, 3), (10, 0), (1 << 40, 8)]
+        );
+    }
+}
@@ -0,0 +1,127 @@
+//! Shrinks a failing program into a small reproduction, e.g. turning a fuzzer
+//! crash into a regression test.
+
+/// Returns a smaller program that `fails` still holds for, by repeatedly
+/// removing runs of cells and zeroing single cells until neither helps.
+///
+/// `fails` is called on many mangled programs, so it should bound how long it
+/// runs them for. Panics if `fails` doesn't hold for the original program.
+pub fn minimize<F>(program: &[i64], mut fails: F) -> Vec<i64>
+where
+    F: FnMut(&[i64]) -> bool,
+{
+    assert!(fails(program), "the program doesn't fail to begin with");
+
+    let mut program = program.to_vec();
+    loop {
+        let removed = remove_chunks(&mut program, &mut fails);
+        let zeroed = zero_cells(&mut program, &mut fails);
+
+        if !removed && !zeroed {
+            return program;
+        }
+    }
+}
+
+/// Removes runs of cells, starting with about half the program and halving the
+/// run's length down to single cells. Returns whether anything was removed.
+fn remove_chunks<F>(program: &mut Vec<i64>, fails: &mut F) -> bool
+where
+    F: FnMut(&[i64]) -> bool,
+{
+    let mut removed = false;
+    let mut chunk = (program.len().next_power_of_two() / 2).max(1);
+
+    loop {
+        let mut start = 0;
+        while start < program.len() {
+            let end = (start + chunk).min(program.len());
+            let candidate: Vec<i64> = program[..start]
+                .iter()
+                .chain(&program[end..])
+                .copied()
+                .collect();
+
+            if fails(&candidate) {
+                *program = candidate;
+                removed = true;
+            } else {
+                start += chunk;
+            }
+        }
+
+        if chunk == 1 {
+            return removed;
+        }
+        chunk /= 2;
+    }
+}
+
+/// Zeroes each nonzero cell that doesn't need its value. Returns whether
+/// anything was zeroed.
+fn zero_cells<F>(program: &mut [i64], fails: &mut F) -> bool
+where
+    F: FnMut(&[i64]) -> bool,
+{
+    let mut zeroed = false;
+
+    for index in 0..program.len() {
+        let value = program[index];
+        if value == 0 {
+            continue;
+        }
+
+        program[index] = 0;
+        if fails(program) {
+            zeroed = true;
+        } else {
+            program[index] = value;
+        }
+    }
+
+    zeroed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Intcode, IntcodeError};
+    use std::sync::mpsc::channel;
+
+    /// Runs at most 100 steps with no input, returning the outputs and whether
+    /// the program stopped with an error.
+    fn run(program: &[i64]) -> (Vec<i64>, Option<IntcodeError>) {
+        let (_, recv_in) = channel();
+        let (send_out, recv_out) = channel();
+        let mut computer = Intcode::new(program, recv_in, send_out);
+
+        let mut error = None;
+        for _ in 0..100 {
+            if computer.is_halted() {
+                break;
+            }
+            if let Err(err) = computer.step() {
+                error = Some(err);
+                break;
+            }
+        }
+
+        (recv_out.try_iter().collect(), error)
+    }
+
+    #[test]
+    fn test_minimize() {
+        // outputs 7 from a jump past dead code, then some unused data
+        let program = [1105, 1, 7, 1101, 1, 1, 0, 104, 7, 99, 5, 6, 7];
+        assert_eq!(run(&program).0, vec![7]);
+
+        let minimized = minimize(&program, |program| run(program).0.contains(&7));
+        assert_eq!(minimized, vec![104, 7]);
+
+        let crashes = |program: &[i64]| matches!(run(program).1, Some(IntcodeError::Input(_)));
+        assert_eq!(
+            minimize(&[1101, 1, 2, 9, 3, 9, 4, 9, 99, 0], crashes),
+            vec![3]
+        );
+    }
+}
@@ -0,0 +1,177 @@
+//! Merges several sources of input into one stream, e.g. scripted commands
+//! before whatever's typed on stdin, or packets injected ahead of a computer's
+//! own queue. Each value comes from the first source, in the order they were
+//! added, that has one ready.
+
+use std::sync::mpsc::{Receiver, TryRecvError};
+use std::thread;
+use std::time::Duration;
+
+/// How long to wait before asking again when every source is waiting.
+const POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+/// What a source has to give.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Supply<T> {
+    Value(T),
+    /// Nothing yet, but maybe later.
+    Pending,
+    /// Nothing ever again.
+    Exhausted,
+}
+
+type Source<T> = Box<dyn FnMut() -> Supply<T> + Send>;
+
+/// Sources of input in priority order. Iterating takes the next value, waiting
+/// for one if every source is pending, and ends once they're all exhausted.
+pub struct Multiplexer<T> {
+    sources: Vec<Source<T>>,
+    idle: Option<T>,
+}
+
+impl<T> Default for Multiplexer<T> {
+    fn default() -> Self {
+        Multiplexer {
+            sources: Vec::new(),
+            idle: None,
+        }
+    }
+}
+
+impl<T: Send + 'static> Multiplexer<T> {
+    pub fn new() -> Self {
+        Multiplexer::default()
+    }
+
+    /// Adds a source below every source added so far.
+    pub fn source<F>(mut self, source: F) -> Self
+    where
+        F: FnMut() -> Supply<T> + Send + 'static,
+    {
+        self.sources.push(Box::new(source));
+        self
+    }
+
+    /// Adds a fixed list of values, such as a script, given in order.
+    pub fn values<I>(self, values: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        I::IntoIter: Send + 'static,
+    {
+        let mut values = values.into_iter();
+        self.source(move || values.next().map_or(Supply::Exhausted, Supply::Value))
+    }
+
+    /// Adds a channel, which is pending while empty and exhausted once every
+    /// sender is gone.
+    pub fn receiver(self, receiver: Receiver<T>) -> Self {
+        self.source(move || match receiver.try_recv() {
+            Ok(value) => Supply::Value(value),
+            Err(TryRecvError::Empty) => Supply::Pending,
+            Err(TryRecvError::Disconnected) => Supply::Exhausted,
+        })
+    }
+
+    /// Adds a source that blocks until it has a value, such as stdin, which is
+    /// exhausted once it returns None. Nothing below it is ever reached until
+    /// then.
+    pub fn blocking<F>(self, mut source: F) -> Self
+    where
+        F: FnMut() -> Option<T> + Send + 'static,
+    {
+        self.source(move || source().map_or(Supply::Exhausted, Supply::Value))
+    }
+
+    /// Gives `value` whenever every source is pending, instead of waiting, e.g.
+    /// the -1 a networked computer reads when no packet has arrived.
+    pub fn idle(mut self, value: T) -> Self {
+        self.idle = Some(value);
+        self
+    }
+}
+
+impl<T: Clone> Iterator for Multiplexer<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        loop {
+            let mut waiting = false;
+            let mut index = 0;
+            while index < self.sources.len() {
+                match (self.sources[index])() {
+                    Supply::Value(value) => return Some(value),
+                    Supply::Pending => {
+                        waiting = true;
+                        index += 1;
+                    }
+                    Supply::Exhausted => {
+                        drop(self.sources.remove(index));
+                    }
+                }
+            }
+
+            if !waiting {
+                return None;
+            }
+            if let Some(idle) = &self.idle {
+                return Some(idle.clone());
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Intcode;
+    use std::sync::mpsc::channel;
+
+    #[test]
+    fn test_priority() {
+        let (inject, injected) = channel();
+        let mut input = Multiplexer::new().receiver(injected).values(vec![1, 2, 3]);
+
+        assert_eq!(input.next(), Some(1));
+        inject.send(10).unwrap();
+        inject.send(11).unwrap();
+        assert_eq!(input.next(), Some(10));
+        assert_eq!(input.next(), Some(11));
+        assert_eq!(input.next(), Some(2));
+
+        drop(inject);
+        assert_eq!(input.collect::<Vec<_>>(), vec![3]);
+    }
+
+    #[test]
+    fn test_idle() {
+        let (inject, injected) = channel();
+        let mut input = Multiplexer::new().receiver(injected).idle(-1);
+
+        assert_eq!(input.next(), Some(-1));
+        inject.send(5).unwrap();
+        assert_eq!(input.next(), Some(5));
+        assert_eq!(input.next(), Some(-1));
+    }
+
+    #[test]
+    fn test_takeover() {
+        // a script that runs out, then someone typing
+        let mut typed = vec![3, 4].into_iter();
+        let mut input = Multiplexer::new()
+            .values(vec![1, 2])
+            .blocking(move || typed.next());
+
+        // reads three inputs and outputs their sum
+        let program = [
+            3, 17, 3, 18, 3, 19, 1, 17, 18, 20, 1, 20, 19, 20, 4, 20, 99, 0, 0, 0, 0,
+        ];
+        let (_send_in, recv_in) = channel();
+        let (send_out, recv_out) = channel();
+        let mut computer =
+            Intcode::new(&program, recv_in, send_out).with_input_fallback(move || input.next());
+        computer.evaluate().unwrap();
+
+        assert_eq!(recv_out.try_iter().collect::<Vec<_>>(), vec![1 + 2 + 3]);
+    }
+}
@@ -0,0 +1,609 @@
+//! Rewrites a program into an equivalent one that takes fewer steps: reads of
+//! cells nothing writes become constants, branches on constants are resolved,
+//! stores nothing reads are skipped and chains of jumps are shortcut.
+//!
+//! Equivalent means the same outputs, or the same error, for the same inputs;
+//! memory once the program halts isn't kept. Only programs whose control flow
+//! and writes are known up front are optimized: every jump target is immediate,
+//! nothing uses the relative base and no instruction is ever written to.
+
+use crate::{Instruction, Intcode, Mode};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::convert::TryFrom;
+use std::fmt;
+use std::sync::mpsc::channel;
+
+/// Most steps [`verify`] runs each program for.
+pub const MAX_STEPS: u64 = 100_000_000;
+
+/// Why a program can't be optimized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unsupported {
+    /// A reachable cell isn't a valid instruction.
+    Decode {
+        address: usize,
+        value: i64,
+    },
+    /// A reachable instruction runs off the end of the program.
+    Truncated {
+        address: usize,
+    },
+    RelativeMode {
+        address: usize,
+    },
+    /// A jump whose target is read from memory.
+    ComputedJump {
+        address: usize,
+    },
+    /// A reachable instruction that something writes to.
+    SelfModifying {
+        address: usize,
+    },
+}
+
+impl fmt::Display for Unsupported {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Unsupported::Decode { address, value } => {
+                write!(f, "{} at {} isn't an instruction", value, address)
+            }
+            Unsupported::Truncated { address } => {
+                write!(f, "the instruction at {} runs off the end", address)
+            }
+            Unsupported::RelativeMode { address } => {
+                write!(f, "the instruction at {} uses the relative base", address)
+            }
+            Unsupported::ComputedJump { address } => {
+                write!(f, "the jump at {} has a computed target", address)
+            }
+            Unsupported::SelfModifying { address } => {
+                write!(f, "the instruction at {} is written to", address)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Add,
+    Mul,
+    LessThan,
+    Equals,
+    Input,
+    Output,
+    JumpTrue,
+    JumpFalse,
+    AdjRelative,
+    Halt,
+}
+
+impl Kind {
+    fn op_code(self) -> i64 {
+        match self {
+            Kind::Add => 1,
+            Kind::Mul => 2,
+            Kind::Input => 3,
+            Kind::Output => 4,
+            Kind::JumpTrue => 5,
+            Kind::JumpFalse => 6,
+            Kind::LessThan => 7,
+            Kind::Equals => 8,
+            Kind::AdjRelative => 9,
+            Kind::Halt => 99,
+        }
+    }
+
+    /// The index of the parameter the instruction writes to, if any.
+    fn written(self) -> Option<usize> {
+        match self {
+            Kind::Add | Kind::Mul | Kind::LessThan | Kind::Equals => Some(2),
+            Kind::Input => Some(0),
+            _ => None,
+        }
+    }
+}
+
+/// A reachable instruction as it's being rewritten.
+#[derive(Debug, Clone)]
+struct Op {
+    kind: Kind,
+    params: Vec<(Mode, i64)>,
+    /// Running it no longer does anything that matters.
+    dead: bool,
+}
+
+impl Op {
+    fn len(&self) -> usize {
+        self.params.len() + 1
+    }
+
+    /// The cell the instruction writes to, if it writes.
+    fn target(&self, address: usize) -> Option<i64> {
+        let index = self.kind.written()?;
+        Some(match self.params[index] {
+            (Mode::Immediate, _) => (address + 1 + index) as i64,
+            (_, value) => value,
+        })
+    }
+
+    /// The cells the instruction reads, not counting itself.
+    fn reads(&self) -> impl Iterator<Item = i64> + '_ {
+        let written = self.kind.written();
+        self.params
+            .iter()
+            .enumerate()
+            .filter(move |&(index, _)| Some(index) != written)
+            .filter(|(_, (mode, _))| *mode == Mode::Position)
+            .map(|(_, &(_, address))| address)
+    }
+
+    fn is_jump(&self) -> bool {
+        matches!(self.kind, Kind::JumpTrue | Kind::JumpFalse)
+    }
+
+    /// Where the instruction always jumps to, if it's a jump that's always taken.
+    fn always_jumps(&self) -> Option<i64> {
+        match (self.kind, self.params.first()) {
+            (Kind::JumpTrue, Some(&(Mode::Immediate, cond))) if cond != 0 => Some(self.params[1].1),
+            (Kind::JumpFalse, Some(&(Mode::Immediate, 0))) => Some(self.params[1].1),
+            _ => None,
+        }
+    }
+
+    fn encode(&self) -> Vec<i64> {
+        let modes = self.params.iter().rev().fold(0, |modes, &(mode, _)| {
+            modes * 10 + (mode == Mode::Immediate) as i64
+        });
+        let mut cells = vec![self.kind.op_code() + modes * 100];
+        cells.extend(self.params.iter().map(|&(_, value)| value));
+        cells
+    }
+}
+
+fn cell(program: &[i64], address: i64) -> i64 {
+    usize::try_from(address)
+        .ok()
+        .and_then(|address| program.get(address))
+        .copied()
+        .unwrap_or(0)
+}
+
+fn decode(program: &[i64], address: usize) -> Result<Op, Unsupported> {
+    let value = cell(program, address as i64);
+    if value == 99 {
+        return Ok(Op {
+            kind: Kind::Halt,
+            params: Vec::new(),
+            dead: false,
+        });
+    }
+
+    let (kind, modes) = match Instruction::decode(value) {
+        Ok(Instruction::Add(a, b, c)) => (Kind::Add, vec![a, b, c]),
+        Ok(Instruction::Mul(a, b, c)) => (Kind::Mul, vec![a, b, c]),
+        Ok(Instruction::LessThan(a, b, c)) => (Kind::LessThan, vec![a, b, c]),
+        Ok(Instruction::Equals(a, b, c)) => (Kind::Equals, vec![a, b, c]),
+        Ok(Instruction::Input(a)) => (Kind::Input, vec![a]),
+        Ok(Instruction::Output(a)) => (Kind::Output, vec![a]),
+        Ok(Instruction::JumpTrue(a, b)) => (Kind::JumpTrue, vec![a, b]),
+        Ok(Instruction::JumpFalse(a, b)) => (Kind::JumpFalse, vec![a, b]),
+        Ok(Instruction::AdjRelative(a)) => (Kind::AdjRelative, vec![a]),
+        Err(_) => return Err(Unsupported::Decode { address, value }),
+    };
+
+    if address + modes.len() >= program.len() {
+        return Err(Unsupported::Truncated { address });
+    }
+    if modes.contains(&Mode::Relative) {
+        return Err(Unsupported::RelativeMode { address });
+    }
+
+    let params = modes
+        .into_iter()
+        .zip(&program[address + 1..])
+        .map(|(mode, &value)| (mode, value))
+        .collect();
+    Ok(Op {
+        kind,
+        params,
+        dead: false,
+    })
+}
+
+/// Finds every instruction reachable from the start, by address.
+fn reachable(program: &[i64]) -> Result<BTreeMap<usize, Op>, Unsupported> {
+    let mut ops = BTreeMap::new();
+    let mut pending = vec![0];
+
+    while let Some(address) = pending.pop() {
+        if ops.contains_key(&address) {
+            continue;
+        }
+
+        let op = decode(program, address)?;
+        if op.is_jump() {
+            match op.params[1] {
+                (Mode::Immediate, target) if target >= 0 => pending.push(target as usize),
+                _ => return Err(Unsupported::ComputedJump { address }),
+            }
+        }
+        if op.kind != Kind::Halt && op.always_jumps().is_none() {
+            pending.push(address + op.len());
+        }
+        ops.insert(address, op);
+    }
+
+    Ok(ops)
+}
+
+/// A program rewritten to take fewer steps, and what was done to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Optimized {
+    pub program: Vec<i64>,
+    /// Reads replaced with the value they always read.
+    pub constants: usize,
+    /// Branches whose condition is always the same.
+    pub branches: usize,
+    /// Instructions jumped over instead of run.
+    pub removed: usize,
+    /// Jumps sent straight to the end of a chain of jumps.
+    pub threaded: usize,
+}
+
+/// Optimizes the program, or says why it can't be.
+pub fn optimize(program: &[i64]) -> Result<Optimized, Unsupported> {
+    let mut ops = reachable(program)?;
+
+    let written: HashSet<i64> = ops
+        .iter()
+        .filter_map(|(&address, op)| op.target(address))
+        .collect();
+    for (&address, op) in &ops {
+        if (address..address + op.len()).any(|cell| written.contains(&(cell as i64))) {
+            return Err(Unsupported::SelfModifying { address });
+        }
+    }
+
+    // control can only arrive somewhere other than from the instruction before
+    // at the start, at jump targets and after jumps
+    let mut leaders: HashSet<usize> = HashSet::new();
+    leaders.insert(0);
+    let mut end = 0;
+    for (&address, op) in &ops {
+        if address != end {
+            leaders.insert(address);
+        }
+        if op.is_jump() {
+            leaders.insert(op.params[1].1 as usize);
+            leaders.insert(address + op.len());
+        }
+        end = address + op.len();
+    }
+
+    let mut optimized = Optimized {
+        program: program.to_vec(),
+        constants: 0,
+        branches: 0,
+        removed: 0,
+        threaded: 0,
+    };
+
+    // Propagate constants: a cell nothing writes always holds its initial value,
+    // and within a straight run of instructions so does a cell just set to one.
+    let mut known: HashMap<i64, i64> = HashMap::new();
+    for (address, op) in ops.iter_mut() {
+        if leaders.contains(address) {
+            known.clear();
+        }
+
+        let unconditional = op.always_jumps().is_some();
+        let write = op.kind.written();
+        for (index, param) in op.params.iter_mut().enumerate() {
+            if Some(index) == write || param.0 != Mode::Position {
+                continue;
+            }
+            let value = if written.contains(&param.1) {
+                known.get(&param.1).copied()
+            } else {
+                Some(cell(program, param.1))
+            };
+            if let Some(value) = value {
+                *param = (Mode::Immediate, value);
+                optimized.constants += 1;
+            }
+        }
+
+        let operands = match op.params.as_slice() {
+            [(Mode::Immediate, a), (Mode::Immediate, b), ..] => Some((*a, *b)),
+            _ => None,
+        };
+        match (op.kind, operands) {
+            (Kind::JumpTrue, _) | (Kind::JumpFalse, _) if !unconditional => {
+                if let (Mode::Immediate, cond) = op.params[0] {
+                    optimized.branches += 1;
+                    if (cond != 0) == (op.kind == Kind::JumpTrue) {
+                        op.params[0] = (Mode::Immediate, 1);
+                        op.kind = Kind::JumpTrue;
+                    } else {
+                        op.dead = true;
+                    }
+                }
+            }
+            (kind, Some((a, b))) if kind.written() == Some(2) => {
+                let value = match kind {
+                    Kind::Add => a.checked_add(b),
+                    Kind::Mul => a.checked_mul(b),
+                    Kind::LessThan => Some((a < b) as i64),
+                    _ => Some((a == b) as i64),
+                };
+                let target = op.target(*address).unwrap();
+                match value {
+                    Some(value) => known.insert(target, value),
+                    None => known.remove(&target),
+                };
+            }
+            (_, _) => {
+                if let Some(target) = op.target(*address) {
+                    known.remove(&target);
+                }
+            }
+        }
+    }
+
+    // Skip stores nothing reads, until skipping them leaves no more unread. Input
+    // is kept since reading it is observable.
+    loop {
+        let read: HashSet<i64> = ops
+            .values()
+            .filter(|op| !op.dead)
+            .flat_map(|op| op.reads())
+            .collect();
+        let mut changed = false;
+        for (&address, op) in ops.iter_mut() {
+            if op.dead || op.kind.written() != Some(2) {
+                continue;
+            }
+            if !read.contains(&op.target(address).unwrap()) {
+                op.dead = true;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    // Jump over runs of dead instructions, when that saves a step.
+    let addresses: Vec<usize> = ops.keys().copied().collect();
+    let mut index = 0;
+    while index < addresses.len() {
+        let start = addresses[index];
+        let mut end = start;
+        let mut run = 0;
+        while let Some(op) = ops.get(&end) {
+            if !op.dead || (run > 0 && leaders.contains(&end)) {
+                break;
+            }
+            end += op.len();
+            run += 1;
+        }
+        index += run.max(1);
+
+        let before_jump = ops.get(&end).is_some_and(|op| op.always_jumps().is_some());
+        if run >= 2 || (run == 1 && before_jump) {
+            let op = ops.get_mut(&start).unwrap();
+            *op = Op {
+                kind: Kind::JumpTrue,
+                params: vec![(Mode::Immediate, 1), (Mode::Immediate, end as i64)],
+                dead: false,
+            };
+            optimized.removed += run;
+        }
+    }
+
+    // Thread jumps through any chain of jumps that are always taken.
+    let targets: HashMap<usize, i64> = ops
+        .iter()
+        .filter(|(_, op)| !op.dead)
+        .filter_map(|(&address, op)| Some((address, op.always_jumps()?)))
+        .collect();
+    for op in ops.values_mut().filter(|op| op.is_jump() && !op.dead) {
+        let mut target = op.params[1].1;
+        let mut seen = HashSet::new();
+        while let Some(&next) = targets.get(&(target as usize)) {
+            if !seen.insert(target) {
+                break;
+            }
+            target = next;
+        }
+        if target != op.params[1].1 {
+            op.params[1].1 = target;
+            optimized.threaded += 1;
+        }
+    }
+
+    for (&address, op) in ops.iter().filter(|(_, op)| !op.dead) {
+        let cells = op.encode();
+        optimized.program[address..address + cells.len()].copy_from_slice(&cells);
+    }
+    Ok(optimized)
+}
+
+/// What running a program produced.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Execution {
+    pub outputs: Vec<i64>,
+    pub steps: u64,
+    /// Why the program stopped, if it didn't halt.
+    pub error: Option<String>,
+}
+
+/// Runs the program on the inputs for at most `max_steps` steps.
+pub fn execute(program: &[i64], inputs: &[i64], max_steps: u64) -> Execution {
+    let (_, recv_in) = channel();
+    let (send_out, recv_out) = channel();
+    let mut computer = Intcode::new(program, recv_in, send_out);
+    for &input in inputs {
+        computer.push_input(input);
+    }
+
+    let mut steps = 0;
+    let mut error = None;
+    while !computer.is_halted() {
+        if steps == max_steps {
+            error = Some(format!("still running after {} steps", max_steps));
+            break;
+        }
+        if let Err(err) = computer.step() {
+            error = Some(err.to_string());
+            break;
+        }
+        steps += 1;
+    }
+
+    Execution {
+        outputs: recv_out.try_iter().collect(),
+        steps,
+        error,
+    }
+}
+
+/// How an optimized program behaved differently from the original.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    pub original: Execution,
+    pub optimized: Execution,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (original, optimized) = (&self.original, &self.optimized);
+        let outputs = original.outputs.iter().zip(&optimized.outputs);
+        match outputs.enumerate().find(|(_, (a, b))| a != b) {
+            Some((index, (a, b))) => write!(f, "output {} was {}, optimized {}", index, a, b),
+            None if original.outputs.len() != optimized.outputs.len() => write!(
+                f,
+                "{} outputs, optimized {}",
+                original.outputs.len(),
+                optimized.outputs.len()
+            ),
+            None => write!(
+                f,
+                "stopped with {:?}, optimized {:?}",
+                original.error, optimized.error
+            ),
+        }
+    }
+}
+
+/// Runs both programs on the inputs and checks they output the same and stop
+/// the same way, returning both runs.
+pub fn verify(
+    original: &[i64],
+    optimized: &[i64],
+    inputs: &[i64],
+) -> Result<(Execution, Execution), Mismatch> {
+    let original = execute(original, inputs, MAX_STEPS);
+    let optimized = execute(optimized, inputs, MAX_STEPS);
+
+    if original.outputs == optimized.outputs && original.error == optimized.error {
+        Ok((original, optimized))
+    } else {
+        Err(Mismatch {
+            original,
+            optimized,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[rustfmt::skip]
+    const PROGRAM: [i64; 35] = [
+        3, 30,              // in [30]
+        1, 31, 30, 32,      // add [31], [30], [32]; nothing writes [31]
+        1101, 1, 1, 33,     // add 1, 1, [33]; nothing reads [33]
+        1006, 34, 20,       // jf [34], 20; [34] is always 0
+        4, 32, 99,          // out [32]; hlt
+        0, 0, 0, 0,
+        1105, 1, 24, 0,     // jt 1, 24
+        4, 32, 99,          // out [32]; hlt
+        0, 0, 0,
+        0, 7, 0, 0, 0,
+    ];
+
+    #[test]
+    fn test_optimize() {
+        let optimized = optimize(&PROGRAM).unwrap();
+
+        assert_eq!(
+            optimized.program[2..13],
+            [101, 7, 30, 32, 1105, 1, 24, 33, 1105, 1, 24]
+        );
+        assert_eq!(optimized.program[13..], PROGRAM[13..]);
+        assert_eq!(
+            (
+                optimized.constants,
+                optimized.branches,
+                optimized.removed,
+                optimized.threaded
+            ),
+            (2, 1, 1, 2)
+        );
+
+        let (original, faster) = verify(&PROGRAM, &optimized.program, &[5]).unwrap();
+        assert_eq!(faster.outputs, vec![12]);
+        assert_eq!((original.steps, faster.steps), (6, 4));
+
+        // running out of input has to happen the same way too
+        assert!(verify(&PROGRAM, &optimized.program, &[]).is_ok());
+    }
+
+    #[test]
+    fn test_constants_in_a_run() {
+        // add 2, 3, [11]; mul [11], 4, [12]; out [12]; hlt
+        let program = [1101, 2, 3, 11, 1002, 11, 4, 12, 4, 12, 99, 0, 0];
+        let optimized = optimize(&program).unwrap();
+
+        // the first store is skipped along with the second, which only fed the output
+        assert_eq!(optimized.program[..4], [1105, 1, 8, 11]);
+        assert_eq!(optimized.program[8..10], [104, 20]);
+        let (original, faster) = verify(&program, &optimized.program, &[]).unwrap();
+        assert_eq!((original.steps, faster.steps), (3, 2));
+    }
+
+    #[test]
+    fn test_unsupported() {
+        assert_eq!(
+            optimize(&[1, 0, 0, 3, 99]),
+            Err(Unsupported::SelfModifying { address: 0 })
+        );
+        assert_eq!(
+            optimize(&[1105, 1, 4, 99, 5, 7, 8, 99]),
+            Err(Unsupported::ComputedJump { address: 4 })
+        );
+        assert_eq!(
+            optimize(&[204, 0, 99]),
+            Err(Unsupported::RelativeMode { address: 0 })
+        );
+        assert_eq!(
+            optimize(&[4, 0]),
+            Err(Unsupported::Decode {
+                address: 2,
+                value: 0
+            })
+        );
+        assert_eq!(
+            optimize(&[1, 0]),
+            Err(Unsupported::Truncated { address: 0 })
+        );
+    }
+
+    #[test]
+    fn test_mismatch() {
+        let mismatch = verify(&[104, 1, 99], &[104, 2, 99], &[]).unwrap_err();
+        assert_eq!(mismatch.to_string(), "output 0 was 1, optimized 2");
+    }
+}
@@ -0,0 +1,242 @@
+//! A compact binary format for programs and snapshots. Cells are written as
+//! zigzag varints, so small values of either sign take a byte or two, and
+//! memory as regions of consecutive addresses, so the gaps in a sparse memory
+//! take nothing.
+//!
+//! Every file starts with a four-byte magic number saying what it holds.
+
+use std::fmt;
+
+/// Starts a packed program.
+pub const PROGRAM_MAGIC: &[u8; 4] = b"ICP\x01";
+/// Starts a packed snapshot.
+pub const SNAPSHOT_MAGIC: &[u8; 4] = b"ICS\x01";
+/// Longest program `unpack_program` allocates, far longer than any puzzle's.
+pub const MAX_PROGRAM_LEN: u64 = 1 << 24;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UnpackError {
+    /// The data doesn't start with the expected magic number.
+    Magic,
+    Truncated,
+    /// A varint that doesn't fit in 64 bits.
+    Overflow,
+    /// Bytes left over after everything was read.
+    Trailing(usize),
+    TooLong(u64),
+    /// A region that's out of order or runs past the end of the program.
+    Region {
+        start: i64,
+        len: u64,
+    },
+}
+
+impl fmt::Display for UnpackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnpackError::Magic => write!(f, "not packed Intcode, or the wrong kind"),
+            UnpackError::Truncated => write!(f, "ends in the middle of a value"),
+            UnpackError::Overflow => write!(f, "value too large"),
+            UnpackError::Trailing(bytes) => write!(f, "{} bytes left over", bytes),
+            UnpackError::TooLong(len) => write!(f, "a program of {} cells is too long", len),
+            UnpackError::Region { start, len } => {
+                write!(f, "bad region of {} cells at {}", len, start)
+            }
+        }
+    }
+}
+
+pub(crate) fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+pub(crate) fn write_signed(out: &mut Vec<u8>, value: i64) {
+    write_varint(out, ((value << 1) ^ (value >> 63)) as u64);
+}
+
+/// Writes sorted `(address, value)` cells as regions: how many there are,
+/// then for each the gap since the end of the last one, its length and its
+/// values.
+pub(crate) fn write_cells(out: &mut Vec<u8>, cells: &[(i64, i64)]) {
+    let mut regions: Vec<&[(i64, i64)]> = Vec::new();
+    let mut start = 0;
+    for index in 1..=cells.len() {
+        if index == cells.len() || cells[index].0 != cells[index - 1].0 + 1 {
+            regions.push(&cells[start..index]);
+            start = index;
+        }
+    }
+
+    write_varint(out, regions.len() as u64);
+    let mut end = 0;
+    for region in regions {
+        write_signed(out, region[0].0.wrapping_sub(end));
+        write_varint(out, region.len() as u64);
+        for (_, value) in region {
+            write_signed(out, *value);
+        }
+        end = region[0].0 + region.len() as i64;
+    }
+}
+
+/// Reads what the `write_` functions wrote.
+pub(crate) struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub(crate) fn new(bytes: &'a [u8], magic: &[u8; 4]) -> Result<Self, UnpackError> {
+        match bytes.strip_prefix(&magic[..]) {
+            Some(bytes) => Ok(Reader { bytes }),
+            None => Err(UnpackError::Magic),
+        }
+    }
+
+    pub(crate) fn varint(&mut self) -> Result<u64, UnpackError> {
+        let mut value = 0;
+        for shift in (0..64).step_by(7) {
+            let (&byte, rest) = self.bytes.split_first().ok_or(UnpackError::Truncated)?;
+            self.bytes = rest;
+            if shift == 63 && byte > 1 {
+                return Err(UnpackError::Overflow);
+            }
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(UnpackError::Overflow)
+    }
+
+    pub(crate) fn signed(&mut self) -> Result<i64, UnpackError> {
+        let value = self.varint()?;
+        Ok((value >> 1) as i64 ^ -((value & 1) as i64))
+    }
+
+    /// Reads cells written by `write_cells`, in order.
+    pub(crate) fn cells(&mut self) -> Result<Vec<(i64, i64)>, UnpackError> {
+        let mut cells = Vec::new();
+        let mut end: i64 = 0;
+        for _ in 0..self.varint()? {
+            let gap = self.signed()?;
+            let len = self.varint()?;
+            let start = end.wrapping_add(gap);
+            // regions never touch or overlap, or they'd have been written as one
+            if (!cells.is_empty() && gap <= 0) || len == 0 || len > self.bytes.len() as u64 {
+                return Err(UnpackError::Region { start, len });
+            }
+            // a region can't run past the highest address either
+            let region_end = start
+                .checked_add(len as i64)
+                .ok_or(UnpackError::Region { start, len })?;
+            for address in start..region_end {
+                cells.push((address, self.signed()?));
+            }
+            end = region_end;
+        }
+        Ok(cells)
+    }
+
+    pub(crate) fn finish(self) -> Result<(), UnpackError> {
+        match self.bytes.len() {
+            0 => Ok(()),
+            left => Err(UnpackError::Trailing(left)),
+        }
+    }
+}
+
+/// Packs a program: its length, then its cells that aren't 0.
+pub fn pack_program(program: &[i64]) -> Vec<u8> {
+    let cells: Vec<(i64, i64)> = (0..)
+        .zip(program.iter().copied())
+        .filter(|(_, value)| *value != 0)
+        .collect();
+
+    let mut out = PROGRAM_MAGIC.to_vec();
+    write_varint(&mut out, program.len() as u64);
+    write_cells(&mut out, &cells);
+    out
+}
+
+pub fn unpack_program(bytes: &[u8]) -> Result<Vec<i64>, UnpackError> {
+    let mut reader = Reader::new(bytes, PROGRAM_MAGIC)?;
+    let len = reader.varint()?;
+    if len > MAX_PROGRAM_LEN {
+        return Err(UnpackError::TooLong(len));
+    }
+    let cells = reader.cells()?;
+    reader.finish()?;
+
+    let bad_region = |&(address, _): &(i64, i64)| address < 0 || address as u64 >= len;
+    if let Some(&(start, _)) = cells.iter().find(|cell| bad_region(cell)) {
+        return Err(UnpackError::Region { start, len: 1 });
+    }
+
+    let mut program = vec![0; len as usize];
+    for (address, value) in cells {
+        program[address as usize] = value;
+    }
+    Ok(program)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_varint() {
+        let values = [0, 1, -1, 63, -64, 64, 1 << 40, i64::MAX, i64::MIN];
+        let mut out = SNAPSHOT_MAGIC.to_vec();
+        for &value in &values {
+            write_signed(&mut out, value);
+        }
+        assert_eq!(out[4..7], [0, 2, 1]);
+
+        let mut reader = Reader::new(&out, SNAPSHOT_MAGIC).unwrap();
+        for &value in &values {
+            assert_eq!(reader.signed(), Ok(value));
+        }
+        assert_eq!(reader.finish(), Ok(()));
+
+        let mut reader = Reader::new(b"ICS\x01\xff\xff", SNAPSHOT_MAGIC).unwrap();
+        assert_eq!(reader.varint(), Err(UnpackError::Truncated));
+        let mut reader = Reader::new(
+            b"ICS\x01\xff\xff\xff\xff\xff\xff\xff\xff\xff\x7f",
+            SNAPSHOT_MAGIC,
+        )
+        .unwrap();
+        assert_eq!(reader.varint(), Err(UnpackError::Overflow));
+    }
+
+    #[test]
+    fn test_program() {
+        let mut program = vec![1002, 4, 3, 4, 33, -7];
+        program.resize(1000, 0);
+        program.push(99);
+
+        let packed = pack_program(&program);
+        // two regions of mostly one-byte values, against over 2000 bytes as text
+        assert_eq!(packed.len(), 21);
+        assert_eq!(unpack_program(&packed), Ok(program));
+
+        assert_eq!(unpack_program(&pack_program(&[])), Ok(vec![]));
+        assert_eq!(unpack_program(b"ICS\x01\x00\x00"), Err(UnpackError::Magic));
+        assert_eq!(
+            unpack_program(b"ICP\x01\xff\xff\xff\xff\x0f\x00"),
+            Err(UnpackError::TooLong(u64::from(u32::MAX)))
+        );
+        assert_eq!(
+            unpack_program(b"ICP\x01\x00\x00\x00"),
+            Err(UnpackError::Trailing(1))
+        );
+        // a cell at 2 in a program of 2
+        assert_eq!(
+            unpack_program(b"ICP\x01\x02\x01\x04\x01\x02"),
+            Err(UnpackError::Region { start: 2, len: 1 })
+        );
+    }
+}
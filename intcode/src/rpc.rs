@@ -0,0 +1,394 @@
+//! A JSON-RPC 2.0 service for driving programs from other processes, served on a
+//! local TCP socket with one request or response per line. Sessions are shared
+//! between connections, so one tool can load a program and another watch it.
+//!
+//! - `load` with `{"program": [..] or "1,2,.."}` starts a session and returns
+//!   `{"session": id}`.
+//! - `push_input` with `{"session", "values": [..]}` queues input and returns
+//!   `{"pending": n}`.
+//! - `run` with `{"session", "max_steps"?}` runs until the program outputs,
+//!   needs input, halts or uses up its steps, and returns
+//!   `{"event": "output" | "input" | "halted" | "limit", "value"?, "pc", "steps"}`.
+//! - `read_memory` with `{"session", "address", "count"?}` returns
+//!   `{"values": [..]}`, stopping short of the highest address.
+//! - `unload` with `{"session"}` ends the session.
+
+use crate::memory::MAX_CELLS;
+use crate::{Intcode, State};
+use common::parse::parse_csv;
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// Steps `run` takes when the request doesn't say.
+pub const DEFAULT_STEPS: u64 = 1_000_000;
+
+pub const PARSE_ERROR: i64 = -32700;
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+/// The program failed, or the session doesn't exist.
+pub const EXECUTION_ERROR: i64 = -32000;
+
+struct Vm {
+    computer: Intcode,
+    // kept so the program never sees a closed channel
+    _input: Sender<i64>,
+    output: Receiver<i64>,
+}
+
+/// A failed call, as sent back in the response's `error`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        RpcError {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+fn param<'a>(params: &'a Map<String, Value>, name: &str) -> Result<&'a Value, RpcError> {
+    params
+        .get(name)
+        .ok_or_else(|| RpcError::new(INVALID_PARAMS, format!("missing {:?}", name)))
+}
+
+fn integer(params: &Map<String, Value>, name: &str) -> Result<i64, RpcError> {
+    param(params, name)?
+        .as_i64()
+        .ok_or_else(|| RpcError::new(INVALID_PARAMS, format!("{:?} must be an integer", name)))
+}
+
+fn integers(value: &Value, name: &str) -> Result<Vec<i64>, RpcError> {
+    let invalid = || RpcError::new(INVALID_PARAMS, format!("{:?} must be integers", name));
+    match value {
+        Value::String(text) => parse_csv(text).map_err(|_| invalid()),
+        Value::Array(values) => values
+            .iter()
+            .map(|value| value.as_i64().ok_or_else(invalid))
+            .collect(),
+        _ => Err(invalid()),
+    }
+}
+
+/// Every session, by id.
+#[derive(Default)]
+pub struct Service {
+    sessions: HashMap<u64, Vm>,
+    next: u64,
+}
+
+impl Service {
+    pub fn new() -> Self {
+        Service::default()
+    }
+
+    fn session(&mut self, params: &Map<String, Value>) -> Result<&mut Vm, RpcError> {
+        let id = integer(params, "session")?;
+        self.sessions
+            .get_mut(&(id as u64))
+            .ok_or_else(|| RpcError::new(EXECUTION_ERROR, format!("no session {}", id)))
+    }
+
+    /// Calls the method, returning its result.
+    pub fn call(&mut self, method: &str, params: &Map<String, Value>) -> Result<Value, RpcError> {
+        match method {
+            "load" => {
+                let program = integers(param(params, "program")?, "program")?;
+                let (input, recv_in) = channel();
+                let (send_out, output) = channel();
+
+                let id = self.next;
+                self.next += 1;
+                self.sessions.insert(
+                    id,
+                    Vm {
+                        computer: Intcode::new(&program, recv_in, send_out),
+                        _input: input,
+                        output,
+                    },
+                );
+                Ok(json!({ "session": id }))
+            }
+            "push_input" => {
+                let values = integers(param(params, "values")?, "values")?;
+                let vm = self.session(params)?;
+                for value in values {
+                    vm.computer.push_input(value);
+                }
+                Ok(json!({ "pending": vm.computer.pending_input() }))
+            }
+            "run" => {
+                let max_steps = match params.get("max_steps") {
+                    Some(_) => integer(params, "max_steps")?.max(0) as u64,
+                    None => DEFAULT_STEPS,
+                };
+                run(self.session(params)?, max_steps)
+            }
+            "read_memory" => {
+                let address = integer(params, "address")?;
+                let count = match params.get("count") {
+                    Some(_) => integer(params, "count")?.clamp(0, MAX_CELLS as i64),
+                    None => 1,
+                };
+                let vm = self.session(params)?;
+                let values: Vec<i64> = (address..address.saturating_add(count))
+                    .map(|address| vm.computer.memory(address))
+                    .collect();
+                Ok(json!({ "values": values }))
+            }
+            "unload" => {
+                let id = integer(params, "session")?;
+                match self.sessions.remove(&(id as u64)) {
+                    Some(_) => Ok(Value::Bool(true)),
+                    None => Err(RpcError::new(EXECUTION_ERROR, format!("no session {}", id))),
+                }
+            }
+            _ => Err(RpcError::new(
+                METHOD_NOT_FOUND,
+                format!("no method {:?}", method),
+            )),
+        }
+    }
+
+    /// Handles one line of JSON-RPC, returning the response to send back, or
+    /// None for a notification.
+    pub fn handle(&mut self, line: &str) -> Option<String> {
+        let request: Value = match serde_json::from_str(line) {
+            Ok(request) => request,
+            Err(err) => {
+                return Some(response(
+                    Value::Null,
+                    Err(RpcError::new(PARSE_ERROR, err.to_string())),
+                ))
+            }
+        };
+
+        let id = request.get("id").cloned();
+        let result = match (&request["jsonrpc"], &request["method"], &request["params"]) {
+            (Value::String(version), Value::String(method), params) if version == "2.0" => {
+                match params {
+                    Value::Object(params) => self.call(method, params),
+                    Value::Null => self.call(method, &Map::new()),
+                    _ => Err(RpcError::new(INVALID_PARAMS, "params must be an object")),
+                }
+            }
+            _ => Err(RpcError::new(INVALID_REQUEST, "not a JSON-RPC 2.0 request")),
+        };
+
+        id.map(|id| response(id, result))
+    }
+}
+
+/// Runs the program until something happens that the caller needs to know about.
+fn run(vm: &mut Vm, max_steps: u64) -> Result<Value, RpcError> {
+    let mut steps = 0;
+    let mut event = loop {
+        match vm.computer.status().state {
+            State::Halted => break json!({ "event": "halted" }),
+            State::AwaitingInput => break json!({ "event": "input" }),
+            State::Ready if steps == max_steps => break json!({ "event": "limit" }),
+            State::Ready => {}
+        }
+
+        vm.computer
+            .step()
+            .map_err(|err| RpcError::new(EXECUTION_ERROR, err.to_string()))?;
+        steps += 1;
+
+        if let Ok(value) = vm.output.try_recv() {
+            break json!({ "event": "output", "value": value });
+        }
+    };
+
+    event["pc"] = json!(vm.computer.pc());
+    event["steps"] = json!(steps);
+    Ok(event)
+}
+
+fn response(id: Value, result: Result<Value, RpcError>) -> String {
+    let response = match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(err) => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": err.code, "message": err.message },
+        }),
+    };
+    response.to_string()
+}
+
+fn connection(service: &Mutex<Service>, stream: TcpStream) -> io::Result<()> {
+    let mut writer = stream.try_clone()?;
+    for line in BufReader::new(stream).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = service
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .handle(&line);
+        if let Some(response) = response {
+            writeln!(writer, "{}", response)?;
+        }
+    }
+    Ok(())
+}
+
+/// Serves every connection on a thread of its own until accepting one fails.
+pub fn serve(listener: TcpListener) -> io::Result<()> {
+    let service = Arc::new(Mutex::new(Service::new()));
+
+    for stream in listener.incoming() {
+        let stream = stream?;
+        let service = Arc::clone(&service);
+        thread::spawn(move || {
+            // a client going away mid-response only ends its own connection
+            let _ = connection(&service, stream);
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(service: &mut Service, method: &str, params: Value) -> Value {
+        let request = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let response: Value =
+            serde_json::from_str(&service.handle(&request.to_string()).unwrap()).unwrap();
+        if response.get("error").is_some() {
+            response
+        } else {
+            response["result"].clone()
+        }
+    }
+
+    #[test]
+    fn test_session() {
+        let mut service = Service::new();
+
+        // doubles each input until it reads a zero
+        let program = "3,13,1006,13,11,1002,13,2,14,4,14,99,0,0,0";
+        let loaded = call(&mut service, "load", json!({ "program": program }));
+        let session = loaded["session"].clone();
+
+        let run = call(&mut service, "run", json!({ "session": session }));
+        assert_eq!(run, json!({ "event": "input", "pc": 0, "steps": 0 }));
+
+        let pushed = call(
+            &mut service,
+            "push_input",
+            json!({ "session": session, "values": [21] }),
+        );
+        assert_eq!(pushed, json!({ "pending": 1 }));
+
+        let run = call(&mut service, "run", json!({ "session": session }));
+        assert_eq!(
+            run,
+            json!({ "event": "output", "value": 42, "pc": 11, "steps": 4 })
+        );
+
+        let run = call(
+            &mut service,
+            "run",
+            json!({ "session": session, "max_steps": 0 }),
+        );
+        assert_eq!(run["event"], "halted");
+
+        let memory = call(
+            &mut service,
+            "read_memory",
+            json!({ "session": session, "address": 13, "count": 3 }),
+        );
+        assert_eq!(memory, json!({ "values": [21, 42, 0] }));
+        // stops short at the highest address rather than overflowing
+        let memory = call(
+            &mut service,
+            "read_memory",
+            json!({ "session": session, "address": i64::MAX - 2, "count": 5 }),
+        );
+        assert_eq!(memory, json!({ "values": [0, 0] }));
+
+        assert_eq!(
+            call(&mut service, "unload", json!({ "session": session })),
+            json!(true)
+        );
+        let gone = call(&mut service, "run", json!({ "session": session }));
+        assert_eq!(gone["error"]["code"], EXECUTION_ERROR);
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut service = Service::new();
+        let code = |response: Option<String>| {
+            let response: Value = serde_json::from_str(&response.unwrap()).unwrap();
+            response["error"]["code"].as_i64().unwrap()
+        };
+
+        assert_eq!(code(service.handle("{")), PARSE_ERROR);
+        assert_eq!(
+            code(service.handle(r#"{"id": 1, "method": "run"}"#)),
+            INVALID_REQUEST
+        );
+        assert_eq!(
+            code(service.handle(r#"{"jsonrpc": "2.0", "id": 1, "method": "fly"}"#)),
+            METHOD_NOT_FOUND
+        );
+        assert_eq!(
+            code(service.handle(
+                r#"{"jsonrpc": "2.0", "id": 1, "method": "load", "params": {"program": [1.5]}}"#
+            )),
+            INVALID_PARAMS
+        );
+
+        // notifications get no response
+        assert_eq!(
+            service.handle(r#"{"jsonrpc": "2.0", "method": "load", "params": {"program": "99"}}"#),
+            None
+        );
+
+        let loaded = call(&mut service, "load", json!({ "program": [77] }));
+        let failed = call(&mut service, "run", json!({ "session": loaded["session"] }));
+        assert_eq!(failed["error"]["code"], EXECUTION_ERROR);
+    }
+
+    #[test]
+    fn test_serve() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || serve(listener));
+
+        let stream = TcpStream::connect(addr).unwrap();
+        let mut writer = stream.try_clone().unwrap();
+        let mut lines = BufReader::new(stream).lines();
+
+        writeln!(writer, r#"{{"jsonrpc": "2.0", "id": "a", "method": "load", "params": {{"program": "104,7,99"}}}}"#).unwrap();
+        let response: Value = serde_json::from_str(&lines.next().unwrap().unwrap()).unwrap();
+        assert_eq!(
+            response,
+            json!({ "jsonrpc": "2.0", "id": "a", "result": { "session": 0 } })
+        );
+
+        writeln!(
+            writer,
+            r#"{{"jsonrpc": "2.0", "id": "b", "method": "run", "params": {{"session": 0}}}}"#
+        )
+        .unwrap();
+        let response: Value = serde_json::from_str(&lines.next().unwrap().unwrap()).unwrap();
+        assert_eq!(response["result"]["value"], 7);
+    }
+}
@@ -0,0 +1,247 @@
+//! Runs many computers on a few threads, for networks too big to give every
+//! computer its own thread blocking on a channel.
+
+use crate::{Intcode, IntcodeError, State};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::thread;
+
+/// What a computer does when it reads input and none has arrived.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum EmptyInput {
+    /// Sleep until something is sent to it.
+    Sleep,
+    /// Read this value instead, like a network card with no packets.
+    Read(i64),
+}
+
+struct Machine {
+    computer: Intcode,
+    // kept so the computer's input channel never closes
+    _input: Sender<i64>,
+    output: Receiver<i64>,
+    failed: bool,
+}
+
+/// What a single computer did in its quantum.
+struct Quantum {
+    outputs: Vec<i64>,
+    error: Option<IntcodeError>,
+    idle: bool,
+}
+
+impl Machine {
+    /// Runs up to `quantum` instructions. The computer is idle if it produced no
+    /// output and ran out of input, or can't run at all.
+    fn run(&mut self, quantum: usize, empty_input: EmptyInput) -> Quantum {
+        let mut error = None;
+        let mut starved = false;
+
+        for _ in 0..quantum {
+            if self.failed {
+                starved = true;
+                break;
+            }
+
+            match self.computer.status().state {
+                State::Ready => {}
+                State::AwaitingInput => {
+                    starved = true;
+                    match empty_input {
+                        EmptyInput::Sleep => break,
+                        EmptyInput::Read(value) => self.computer.push_input(value),
+                    }
+                }
+                State::Halted => {
+                    starved = true;
+                    break;
+                }
+            }
+
+            if let Err(err) = self.computer.step() {
+                self.failed = true;
+                error = Some(err);
+                break;
+            }
+        }
+
+        let outputs: Vec<i64> = self.output.try_iter().collect();
+        Quantum {
+            idle: starved && outputs.is_empty(),
+            outputs,
+            error,
+        }
+    }
+}
+
+/// Everything that happened in one round of the scheduler.
+#[derive(Debug)]
+pub struct Round {
+    /// Each output along with the computer that sent it, in order for each
+    /// computer.
+    pub outputs: Vec<(usize, i64)>,
+    /// Computers that failed this round. They won't be run again.
+    pub errors: Vec<(usize, IntcodeError)>,
+    /// Whether every computer was idle: halted, failed, or waiting on input
+    /// without producing any output.
+    pub idle: bool,
+}
+
+/// Multiplexes computers over a small pool of threads, running each for a
+/// quantum of instructions per round. Computers waiting on input sleep until
+/// something is sent to them, unless `on_empty_input` says otherwise.
+pub struct Scheduler {
+    machines: Vec<Machine>,
+    workers: usize,
+    quantum: usize,
+    empty_input: EmptyInput,
+}
+
+impl Scheduler {
+    /// Creates a scheduler that runs each computer for up to `quantum`
+    /// instructions a round, using up to `workers` threads.
+    pub fn new(workers: usize, quantum: usize) -> Self {
+        assert!(workers > 0, "the scheduler needs at least one worker");
+
+        Scheduler {
+            machines: Vec::new(),
+            workers,
+            quantum,
+            empty_input: EmptyInput::Sleep,
+        }
+    }
+
+    /// Makes computers read `value` when they want input and none has been sent,
+    /// instead of sleeping. They still count as idle for that round unless they
+    /// output something.
+    pub fn on_empty_input(mut self, value: i64) -> Self {
+        self.empty_input = EmptyInput::Read(value);
+        self
+    }
+
+    /// Adds a computer running `program`, returning its id. Ids count up from 0.
+    pub fn spawn(&mut self, program: &[i64]) -> usize {
+        let (send_in, recv_in) = channel();
+        let (send_out, recv_out) = channel();
+
+        self.machines.push(Machine {
+            computer: Intcode::new(program, recv_in, send_out),
+            _input: send_in,
+            output: recv_out,
+            failed: false,
+        });
+
+        self.machines.len() - 1
+    }
+
+    pub fn len(&self) -> usize {
+        self.machines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.machines.is_empty()
+    }
+
+    /// Queues input for a computer, waking it if it was sleeping.
+    pub fn send(&mut self, id: usize, value: i64) {
+        self.machines[id].computer.push_input(value);
+    }
+
+    /// Gives every computer one quantum, spread over the workers.
+    pub fn run_round(&mut self) -> Round {
+        let quantum = self.quantum;
+        let empty_input = self.empty_input;
+        let chunk = self.machines.len().div_ceil(self.workers).max(1);
+
+        let quanta: Vec<Quantum> = thread::scope(|scope| {
+            let workers: Vec<_> = self
+                .machines
+                .chunks_mut(chunk)
+                .map(|machines| {
+                    scope.spawn(move || {
+                        machines
+                            .iter_mut()
+                            .map(|machine| machine.run(quantum, empty_input))
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+
+            workers
+                .into_iter()
+                .flat_map(|worker| worker.join().unwrap())
+                .collect()
+        });
+
+        let mut round = Round {
+            outputs: Vec::new(),
+            errors: Vec::new(),
+            idle: true,
+        };
+        for (id, quantum) in quanta.into_iter().enumerate() {
+            round.idle &= quantum.idle;
+            round
+                .outputs
+                .extend(quantum.outputs.into_iter().map(|value| (id, value)));
+            if let Some(err) = quantum.error {
+                round.errors.push((id, err));
+            }
+        }
+
+        round
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ring() {
+        // reads its address, then forever reads a value and outputs it plus one
+        let program = [3, 100, 3, 101, 1001, 101, 1, 101, 4, 101, 1105, 1, 2];
+
+        let mut scheduler = Scheduler::new(4, 3);
+        for address in 0..50 {
+            assert_eq!(scheduler.spawn(&program), address);
+            scheduler.send(address, address as i64);
+        }
+        assert_eq!(scheduler.len(), 50);
+
+        // pass a counter around the ring until it reaches 120
+        scheduler.send(0, 0);
+        let mut last = None;
+        while last != Some((19, 120)) {
+            let round = scheduler.run_round();
+            assert!(!round.idle && round.errors.is_empty());
+
+            for &(id, value) in &round.outputs {
+                last = Some((id, value));
+                if value < 120 {
+                    scheduler.send((id + 1) % 50, value);
+                }
+            }
+        }
+
+        assert!(scheduler.run_round().idle);
+    }
+
+    #[test]
+    fn test_empty_input() {
+        // reads until it gets something other than -1, outputs it, and repeats
+        let program = [3, 100, 1008, 100, -1, 101, 1005, 101, 0, 4, 100, 1105, 1, 0];
+
+        let mut scheduler = Scheduler::new(1, 20).on_empty_input(-1);
+        scheduler.spawn(&program);
+        scheduler.spawn(&program);
+
+        let round = scheduler.run_round();
+        assert!(round.idle && round.outputs.is_empty());
+
+        scheduler.send(1, 7);
+        let round = scheduler.run_round();
+        assert_eq!(round.outputs, vec![(1, 7)]);
+        assert!(!round.idle);
+
+        assert!(scheduler.run_round().idle);
+    }
+}
@@ -0,0 +1,264 @@
+//! A local web UI for stepping through a program, served over a small JSON API:
+//!
+//! - `GET /api/state` returns the current [`View`].
+//! - `POST /api/step` with `{"count": n}` runs up to `n` instructions.
+//! - `POST /api/input` with `{"value": v}` queues an input.
+//! - `POST /api/reset` reloads the program.
+//!
+//! Every `POST` responds with the new [`View`].
+
+use crate::{disasm, nonzero_ranges, Intcode, State};
+use axum::extract::State as Shared;
+use axum::response::Html;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::net::SocketAddr;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+
+/// Number of upcoming instructions in the disassembly view.
+const SHOWN_INSTRUCTIONS: usize = 24;
+/// Most instructions a single step request may run.
+const MAX_STEPS: usize = 1_000_000;
+
+const PAGE: &str = include_str!("serve.html");
+
+/// A program being stepped through, along with everything it has output.
+pub struct Session {
+    program: Vec<i64>,
+    computer: Intcode,
+    // kept so stepping never sees a closed channel
+    _input: Sender<i64>,
+    output: Receiver<i64>,
+    outputs: Vec<i64>,
+    error: Option<String>,
+}
+
+/// A decoded instruction in the disassembly view.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Line {
+    pub address: i64,
+    pub cells: Vec<i64>,
+    /// The disassembled instruction, or None for a cell that isn't one.
+    pub text: Option<String>,
+}
+
+/// A run of consecutive nonzero memory cells.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Range {
+    pub start: i64,
+    pub values: Vec<i64>,
+}
+
+/// Everything the UI shows about a session.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct View {
+    pub pc: i64,
+    pub relative_base: i64,
+    pub state: &'static str,
+    pub pending_input: usize,
+    pub memory_cells: usize,
+    pub instructions: Vec<Line>,
+    pub memory: Vec<Range>,
+    pub outputs: Vec<i64>,
+    pub error: Option<String>,
+}
+
+impl Session {
+    pub fn new(program: &[i64]) -> Self {
+        let (send_in, recv_in) = channel();
+        let (send_out, recv_out) = channel();
+
+        Session {
+            program: program.to_vec(),
+            computer: Intcode::new(program, recv_in, send_out),
+            _input: send_in,
+            output: recv_out,
+            outputs: Vec::new(),
+            error: None,
+        }
+    }
+
+    /// Starts the program over, forgetting its inputs and outputs.
+    pub fn reset(&mut self) {
+        *self = Session::new(&self.program);
+    }
+
+    pub fn push_input(&mut self, value: i64) {
+        self.computer.push_input(value);
+    }
+
+    /// Runs up to `count` instructions, stopping early if the program halts,
+    /// fails or needs input that hasn't been given yet.
+    pub fn step(&mut self, count: usize) {
+        for _ in 0..count {
+            if self.error.is_some() || self.computer.status().state != State::Ready {
+                break;
+            }
+
+            if let Err(err) = self.computer.step() {
+                self.error = Some(err.to_string());
+            }
+            self.outputs.extend(self.output.try_iter());
+        }
+    }
+
+    pub fn view(&mut self) -> View {
+        let status = self.computer.status();
+        let state = match status.state {
+            State::Ready => "ready",
+            State::AwaitingInput => "awaiting input",
+            State::Halted => "halted",
+        };
+
+        View {
+            pc: status.pc,
+            relative_base: status.relative_base,
+            state,
+            pending_input: status.pending_input,
+            memory_cells: status.memory_cells,
+            instructions: self.instructions(),
+            memory: nonzero_ranges(&self.computer.memory)
+                .into_iter()
+                .map(|(start, end)| Range {
+                    start,
+                    values: (start..=end)
+                        .map(|address| self.computer.memory(address))
+                        .collect(),
+                })
+                .collect(),
+            outputs: self.outputs.clone(),
+            error: self.error.clone(),
+        }
+    }
+
+    /// Decodes the instructions from the program counter onwards, treating
+    /// anything that doesn't decode as a single data cell.
+    fn instructions(&self) -> Vec<Line> {
+        let mut lines = Vec::new();
+        let mut address = self.computer.pc();
+
+        for _ in 0..SHOWN_INSTRUCTIONS {
+            // the longest instruction is four cells
+            let cells: Vec<i64> = (address..address + 4)
+                .map(|address| self.computer.memory(address))
+                .collect();
+            let (text, len) = match disasm::decode_one(&cells) {
+                Some((text, len)) => (Some(text.trim_end().to_string()), len),
+                None => (None, 1),
+            };
+
+            lines.push(Line {
+                address,
+                cells: cells[..len].to_vec(),
+                text,
+            });
+            address += len as i64;
+        }
+
+        lines
+    }
+}
+
+type SharedSession = Arc<Mutex<Session>>;
+
+#[derive(Deserialize)]
+struct StepRequest {
+    count: usize,
+}
+
+#[derive(Deserialize)]
+struct InputRequest {
+    value: i64,
+}
+
+async fn page() -> Html<&'static str> {
+    Html(PAGE)
+}
+
+async fn state(Shared(session): Shared<SharedSession>) -> Json<View> {
+    Json(session.lock().unwrap().view())
+}
+
+async fn step(
+    Shared(session): Shared<SharedSession>,
+    Json(request): Json<StepRequest>,
+) -> Json<View> {
+    let mut session = session.lock().unwrap();
+    session.step(request.count.min(MAX_STEPS));
+    Json(session.view())
+}
+
+async fn input(
+    Shared(session): Shared<SharedSession>,
+    Json(request): Json<InputRequest>,
+) -> Json<View> {
+    let mut session = session.lock().unwrap();
+    session.push_input(request.value);
+    Json(session.view())
+}
+
+async fn reset(Shared(session): Shared<SharedSession>) -> Json<View> {
+    let mut session = session.lock().unwrap();
+    session.reset();
+    Json(session.view())
+}
+
+/// Returns the routes for the UI and JSON API, all sharing one session.
+pub fn router(program: &[i64]) -> Router {
+    Router::new()
+        .route("/", get(page))
+        .route("/api/state", get(state))
+        .route("/api/step", post(step))
+        .route("/api/input", post(input))
+        .route("/api/reset", post(reset))
+        .with_state(Arc::new(Mutex::new(Session::new(program))))
+}
+
+/// Serves the UI for `program` on `addr` until the process is killed.
+pub fn serve(program: &[i64], addr: SocketAddr) -> io::Result<()> {
+    let app = router(program);
+    let runtime = tokio::runtime::Runtime::new()?;
+
+    runtime.block_on(async move {
+        let listener = tokio::net::TcpListener::bind(addr).await?;
+        println!("serving on http://{}", listener.local_addr()?);
+        axum::serve(listener, app).await
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session() {
+        // reads two inputs and outputs their sum
+        let mut session = Session::new(&[3, 20, 3, 21, 1, 20, 21, 22, 4, 22, 99]);
+
+        session.step(10);
+        let view = session.view();
+        assert_eq!(view.state, "awaiting input");
+        assert_eq!(view.pc, 0);
+        assert_eq!(view.instructions[0].text.as_deref(), Some("in [20]"));
+
+        session.push_input(20);
+        session.push_input(22);
+        session.step(3);
+        let view = session.view();
+        assert_eq!((view.pc, view.state), (8, "ready"));
+        assert!(view.outputs.is_empty());
+
+        session.step(10);
+        let view = session.view();
+        assert_eq!(view.state, "halted");
+        assert_eq!(view.outputs, vec![42]);
+        assert_eq!(view.memory.last().unwrap().values, vec![20, 22, 42]);
+
+        session.reset();
+        assert_eq!(session.view().pc, 0);
+        assert!(session.view().outputs.is_empty());
+    }
+}
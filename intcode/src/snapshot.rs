@@ -0,0 +1,203 @@
+//! Saving a computer's state and restoring it later, e.g. to branch off from
+//! a point in an interactive game instead of replaying the commands that got
+//! there.
+
+use crate::pack::{self, Reader, UnpackError, SNAPSHOT_MAGIC};
+use crate::Intcode;
+use std::fmt;
+use std::str::FromStr;
+
+/// Everything needed to pick a program up where it left off: its registers,
+/// memory and any input it hasn't read yet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    pc: i64,
+    relative_base: i64,
+    pending: Vec<i64>,
+    // every cell in use, sorted by address
+    memory: Vec<(i64, i64)>,
+}
+
+impl Intcode {
+    pub fn snapshot(&self) -> Snapshot {
+        let mut memory: Vec<(i64, i64)> = self.memory.iter().collect();
+        memory.sort_unstable();
+
+        Snapshot {
+            pc: self.pc,
+            relative_base: self.relative_base,
+            pending: self.pending.iter().copied().collect(),
+            memory,
+        }
+    }
+
+    /// Puts the computer back in the state it was in when the snapshot was
+    /// taken. Its channels and settings like the memory limit are kept.
+    pub fn restore(&mut self, snapshot: &Snapshot) {
+        self.pc = snapshot.pc;
+        self.relative_base = snapshot.relative_base;
+        self.pending = snapshot.pending.iter().copied().collect();
+        self.memory = snapshot.memory.iter().copied().collect();
+        if let Some(accesses) = &mut self.accesses {
+            accesses.clear();
+        }
+    }
+}
+
+impl Snapshot {
+    /// Packs the snapshot into the binary format (see `pack`): its registers,
+    /// pending input and memory, every cell in use kept even if it's 0.
+    pub fn pack(&self) -> Vec<u8> {
+        let mut out = SNAPSHOT_MAGIC.to_vec();
+        pack::write_signed(&mut out, self.pc);
+        pack::write_signed(&mut out, self.relative_base);
+        pack::write_varint(&mut out, self.pending.len() as u64);
+        for value in &self.pending {
+            pack::write_signed(&mut out, *value);
+        }
+        pack::write_cells(&mut out, &self.memory);
+        out
+    }
+
+    pub fn unpack(bytes: &[u8]) -> Result<Self, UnpackError> {
+        let mut reader = Reader::new(bytes, SNAPSHOT_MAGIC)?;
+        let pc = reader.signed()?;
+        let relative_base = reader.signed()?;
+        let pending = (0..reader.varint()?)
+            .map(|_| reader.signed())
+            .collect::<Result<_, _>>()?;
+        let memory = reader.cells()?;
+        reader.finish()?;
+
+        Ok(Snapshot {
+            pc,
+            relative_base,
+            pending,
+            memory,
+        })
+    }
+}
+
+fn join<T: fmt::Display>(values: impl Iterator<Item = T>) -> String {
+    values
+        .map(|value| value.to_string())
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+/// Writes one field per line, e.g. `memory 0=1,1=0,2=99`, which `from_str` reads
+/// back.
+impl fmt::Display for Snapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "pc {}", self.pc)?;
+        writeln!(f, "rb {}", self.relative_base)?;
+        writeln!(f, "pending {}", join(self.pending.iter()))?;
+        writeln!(
+            f,
+            "memory {}",
+            join(
+                self.memory
+                    .iter()
+                    .map(|(address, value)| format!("{}={}", address, value))
+            )
+        )
+    }
+}
+
+fn parse_list<T>(list: &str, item: impl Fn(&str) -> Option<T>) -> Result<Vec<T>, String> {
+    list.split(',')
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| item(entry).ok_or_else(|| format!("bad entry {:?}", entry)))
+        .collect()
+}
+
+impl FromStr for Snapshot {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines();
+        let mut field = |name: &str| {
+            lines
+                .next()
+                .and_then(|line| line.strip_prefix(name))
+                .map(str::trim)
+                .ok_or_else(|| format!("missing {}", name))
+        };
+
+        let pc = field("pc")?
+            .parse()
+            .map_err(|err| format!("bad pc: {}", err))?;
+        let relative_base = field("rb")?
+            .parse()
+            .map_err(|err| format!("bad rb: {}", err))?;
+        let pending = parse_list(field("pending")?, |value| value.parse().ok())?;
+        let memory = parse_list(field("memory")?, |cell| {
+            let (address, value) = cell.split_once('=')?;
+            Some((address.parse().ok()?, value.parse().ok()?))
+        })?;
+
+        Ok(Snapshot {
+            pc,
+            relative_base,
+            pending,
+            memory,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc::channel;
+
+    #[test]
+    fn test_snapshot() {
+        // counts [20] up from 0 forever
+        let mut program = vec![1001, 20, 1, 20, 1105, 1, 0];
+        program.resize(21, 0);
+        let (_send_in, recv_in) = channel();
+        let (send_out, _recv_out) = channel();
+        let mut computer = Intcode::new(&program, recv_in, send_out);
+
+        for _ in 0..4 {
+            computer.step().unwrap();
+        }
+        computer.push_input(7);
+        let snapshot = computer.snapshot();
+        assert_eq!(snapshot.to_string().parse(), Ok(snapshot.clone()));
+
+        for _ in 0..10 {
+            computer.step().unwrap();
+        }
+        assert_eq!(computer.memory(20), 7);
+
+        computer.restore(&snapshot);
+        assert_eq!(computer.memory(20), 2);
+        assert_eq!(computer.pc(), 0);
+        assert_eq!(computer.status().pending_input, 1);
+        assert_eq!(computer.snapshot(), snapshot);
+
+        let packed = snapshot.pack();
+        assert!(packed.len() < snapshot.to_string().len() / 2);
+        assert_eq!(Snapshot::unpack(&packed), Ok(snapshot));
+        assert_eq!(
+            Snapshot::unpack(&packed[..packed.len() - 1]),
+            Err(UnpackError::Truncated)
+        );
+        // a region of two cells starting at i64::MAX
+        assert_eq!(
+            Snapshot::unpack(
+                b"ICS\x01\x00\x00\x00\x01\xfe\xff\xff\xff\xff\xff\xff\xff\xff\x01\x02\x00\x00"
+            ),
+            Err(UnpackError::Region {
+                start: i64::MAX,
+                len: 2
+            })
+        );
+
+        assert!("pc 0\nrb 0\npending\n".parse::<Snapshot>().is_err());
+        assert!("pc 0\nrb 0\npending\nmemory 0=x"
+            .parse::<Snapshot>()
+            .is_err());
+    }
+}
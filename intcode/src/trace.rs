@@ -0,0 +1,252 @@
+//! Recording execution traces and finding where two of them diverge, e.g. to
+//! see where a changed computer starts to behave differently.
+
+use crate::{Access, Instruction, Intcode, IntcodeError, State};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::mpsc::channel;
+
+/// One executed instruction: where it ran, its cells and the memory it
+/// accessed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub pc: i64,
+    pub relative_base: i64,
+    pub instruction: Vec<i64>,
+    pub accesses: Vec<Access>,
+}
+
+/// Formats as a single line, e.g. `pc=0 rb=0 ins=1001,20,1,20 r[20]=3
+/// w[20]=3->4`, which `from_str` reads back.
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cells: Vec<String> = self
+            .instruction
+            .iter()
+            .map(|cell| cell.to_string())
+            .collect();
+        write!(
+            f,
+            "pc={} rb={} ins={}",
+            self.pc,
+            self.relative_base,
+            cells.join(",")
+        )?;
+
+        for access in &self.accesses {
+            match access {
+                Access::Read { address, value } => write!(f, " r[{}]={}", address, value)?,
+                Access::Write { address, old, new } => {
+                    write!(f, " w[{}]={}->{}", address, old, new)?
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_field<T: FromStr>(field: Option<&str>, name: &str) -> Result<T, String> {
+    field
+        .and_then(|field| field.strip_prefix(name))
+        .and_then(|value| value.parse().ok())
+        .ok_or_else(|| format!("missing or bad {}", name))
+}
+
+fn parse_access(field: &str) -> Option<Access> {
+    if let Some(read) = field.strip_prefix("r[") {
+        let (address, value) = read.split_once("]=")?;
+        return Some(Access::Read {
+            address: address.parse().ok()?,
+            value: value.parse().ok()?,
+        });
+    }
+
+    let (address, values) = field.strip_prefix("w[")?.split_once("]=")?;
+    let (old, new) = values.split_once("->")?;
+    Some(Access::Write {
+        address: address.parse().ok()?,
+        old: old.parse().ok()?,
+        new: new.parse().ok()?,
+    })
+}
+
+impl FromStr for Step {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fields = s.split_whitespace();
+        let pc = parse_field(fields.next(), "pc=")?;
+        let relative_base = parse_field(fields.next(), "rb=")?;
+
+        let instruction = fields
+            .next()
+            .and_then(|field| field.strip_prefix("ins="))
+            .ok_or_else(|| String::from("missing ins="))?
+            .split(',')
+            .map(|cell| cell.parse().map_err(|_| format!("bad cell {:?}", cell)))
+            .collect::<Result<_, _>>()?;
+
+        let accesses = fields
+            .map(|field| parse_access(field).ok_or_else(|| format!("bad access {:?}", field)))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Step {
+            pc,
+            relative_base,
+            instruction,
+            accesses,
+        })
+    }
+}
+
+/// Runs the program with the given input, recording each step, until it halts,
+/// fails, runs out of input or has run `limit` instructions. A failing
+/// instruction isn't recorded.
+pub fn record(program: &[i64], input: &[i64], limit: usize) -> Vec<Step> {
+    let (_, recv_in) = channel();
+    let (send_out, _recv_out) = channel();
+    let mut computer = Intcode::new(program, recv_in, send_out);
+    for value in input {
+        computer.push_input(*value);
+    }
+
+    let mut steps = Vec::new();
+    // a failing step just ends the trace
+    let _ = record_from(&mut computer, limit, &mut steps);
+    steps
+}
+
+/// Steps the computer like `record`, appending to `steps`, and returns the
+/// error that stopped it if a step failed. Turns on the computer's access log
+/// if it's off.
+pub fn record_from(
+    computer: &mut Intcode,
+    limit: usize,
+    steps: &mut Vec<Step>,
+) -> Result<(), IntcodeError> {
+    if computer.accesses.is_none() {
+        computer.accesses = Some(Vec::new());
+    }
+
+    let mut recorded = 0;
+    while recorded < limit && computer.status().state == State::Ready {
+        let pc = computer.pc();
+        let relative_base = computer.relative_base();
+        let length =
+            Instruction::decode(computer.memory(pc)).map_or(1, |instr| instr.parameters() + 1);
+        let instruction = (pc..pc + length as i64)
+            .map(|address| computer.memory(address))
+            .collect();
+
+        computer.step()?;
+
+        steps.push(Step {
+            pc,
+            relative_base,
+            instruction,
+            accesses: computer.accesses().to_vec(),
+        });
+        recorded += 1;
+    }
+
+    Ok(())
+}
+
+/// Reads a trace written one step per line, skipping blank lines.
+pub fn parse(trace: &str) -> Result<Vec<Step>, String> {
+    trace
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            line.parse()
+                .map_err(|err| format!("line {}: {}", index + 1, err))
+        })
+        .collect()
+}
+
+/// Finds the first step where the traces differ and describes it, with up to
+/// `context` steps before it and after it from each side. Returns None if the
+/// traces are the same.
+pub fn diff(left: &[Step], right: &[Step], context: usize) -> Option<String> {
+    let first = match left.iter().zip(right).position(|(l, r)| l != r) {
+        Some(first) => first,
+        None if left.len() == right.len() => return None,
+        None => left.len().min(right.len()),
+    };
+
+    let mut report = format!("traces diverge at step {}\n", first);
+    let start = first.saturating_sub(context);
+    for (index, step) in left.iter().enumerate().take(first).skip(start) {
+        report += &format!("  {:>8}: {}\n", index, step);
+    }
+
+    let end = first + context + 1;
+    for (sign, trace) in [("-", left), ("+", right)].iter() {
+        if first >= trace.len() {
+            report += &format!("{} {:>8}: (trace ended)\n", sign, first);
+        }
+        for (index, step) in trace.iter().enumerate().take(end).skip(first) {
+            report += &format!("{} {:>8}: {}\n", sign, index, step);
+        }
+    }
+
+    Some(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record() {
+        // outputs 2, 1, 0 counting [20] down from 3
+        let mut program = vec![1001, 20, -1, 20, 4, 20, 1005, 20, 0, 99];
+        program.resize(21, 0);
+        program[20] = 3;
+
+        let trace = record(&program, &[], 100);
+        assert_eq!(trace.len(), 9);
+        assert_eq!(
+            trace[0].to_string(),
+            "pc=0 rb=0 ins=1001,20,-1,20 r[20]=3 w[20]=3->2"
+        );
+
+        let text: String = trace.iter().map(|step| format!("{}\n", step)).collect();
+        assert_eq!(parse(&text), Ok(trace.clone()));
+        assert!(parse("pc=0 rb=0 ins=1 x[1]=2").is_err());
+
+        assert_eq!(diff(&trace, &trace, 2), None);
+        assert_eq!(record(&program, &[], 4).len(), 4);
+    }
+
+    #[test]
+    fn test_diff() {
+        let mut program = vec![1001, 20, -1, 20, 4, 20, 1005, 20, 0, 99];
+        program.resize(21, 0);
+        program[20] = 3;
+        let left = record(&program, &[], 100);
+
+        // decrements by two instead, so ends a round early
+        program[2] = -2;
+        program[20] = 4;
+        let right = record(&program, &[], 100);
+
+        assert_eq!(
+            diff(&left, &right, 1).unwrap(),
+            concat!(
+                "traces diverge at step 0\n",
+                "-        0: pc=0 rb=0 ins=1001,20,-1,20 r[20]=3 w[20]=3->2\n",
+                "-        1: pc=4 rb=0 ins=4,20 r[20]=2\n",
+                "+        0: pc=0 rb=0 ins=1001,20,-2,20 r[20]=4 w[20]=4->2\n",
+                "+        1: pc=4 rb=0 ins=4,20 r[20]=2\n",
+            )
+        );
+
+        let report = diff(&left, &left[..5], 1).unwrap();
+        assert!(report.starts_with("traces diverge at step 5\n"));
+        assert!(report.contains("\n         4: pc=4 rb=0 ins=4,20 r[20]=1\n"));
+        assert!(report.contains("+        5: (trace ended)\n"));
+    }
+}
@@ -0,0 +1,97 @@
+//! Checks that stepping the computer doesn't touch the heap once a program has
+//! warmed up, i.e. grown its memory, by running day 9's BOOST program in sensor
+//! boost mode under an allocator that counts.
+
+use common::parse::parse_csv;
+use common::require_input;
+use intcode::Intcode;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::mpsc::channel;
+use std::time::Instant;
+
+/// Steps before counting starts, by which point BOOST has written every cell
+/// it's going to.
+const WARM_UP_STEPS: usize = 10_000;
+
+struct Counting;
+
+thread_local! {
+    // only count on the thread running the test, not the harness's
+    static COUNTING: Cell<bool> = const { Cell::new(false) };
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if COUNTING.with(Cell::get) {
+            ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        }
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        if COUNTING.with(Cell::get) {
+            ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        }
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+/// Runs the program to the end after warming up, returning how many steps
+/// were counted and how many allocations they made.
+fn steady_state(mut computer: Intcode) -> (usize, usize) {
+    for _ in 0..WARM_UP_STEPS {
+        computer.step().unwrap();
+    }
+
+    ALLOCATIONS.with(|count| count.set(0));
+    COUNTING.with(|counting| counting.set(true));
+    let mut steps = 0;
+    while !computer.is_halted() {
+        computer.step().unwrap();
+        steps += 1;
+    }
+    COUNTING.with(|counting| counting.set(false));
+
+    (steps, ALLOCATIONS.with(Cell::get))
+}
+
+#[test]
+fn test_steady_state_allocations() {
+    let program: Vec<i64> = parse_csv(&require_input!(9)).unwrap();
+
+    for &lenient in &[false, true] {
+        let (_send_in, recv_in) = channel();
+        let (send_out, recv_out) = channel();
+        // the channel allocates room for outputs on the first send, which isn't
+        // the computer's doing
+        send_out.send(0).unwrap();
+        recv_out.recv().unwrap();
+        let mut computer = Intcode::new(&program, recv_in, send_out);
+        if lenient {
+            computer = computer.with_lenient_decoding();
+        }
+        computer.push_input(2);
+
+        let start = Instant::now();
+        let (steps, allocations) = steady_state(computer);
+        println!(
+            "lenient: {}, {} steps in {:?}, {} allocations",
+            lenient,
+            steps,
+            start.elapsed(),
+            allocations
+        );
+
+        assert_eq!(recv_out.try_iter().count(), 1);
+        assert_eq!(allocations, 0, "lenient: {}", lenient);
+    }
+}
@@ -0,0 +1,72 @@
+//! Compares counting day 6's orbits on interned ids with counting them on a
+//! map of names, on a synthetic map far larger than a real one.
+
+use common::rng::Rng;
+use criterion::{criterion_group, criterion_main, Criterion};
+use day6::{solve_part1, solve_part2};
+use std::collections::HashMap;
+use std::hint::black_box;
+use std::iter::successors;
+
+const OBJECTS: u64 = 300_000;
+const SEED: u64 = 2019;
+
+/// Builds a random tree of orbits around COM, with YOU and SAN somewhere in it.
+fn synthetic_map(rng: &mut Rng) -> String {
+    let name = |id: u64| match id {
+        0 => String::from("COM"),
+        id => format!("O{}", id),
+    };
+
+    let mut orbits: Vec<String> = (1..OBJECTS)
+        .map(|id| format!("{}){}", name(rng.below(id)), name(id)))
+        .collect();
+    orbits.push(format!("{})YOU", name(rng.below(OBJECTS))));
+    orbits.push(format!("{})SAN", name(rng.below(OBJECTS))));
+    rng.shuffle(&mut orbits);
+
+    orbits.join("\n")
+}
+
+/// The answers the way they were found before interning, by hashing names.
+fn by_name(input: &str) -> (usize, usize) {
+    let graph: HashMap<&str, &str> = input
+        .lines()
+        .map(|line| {
+            let mut parts = line.split(')');
+            let parent = parts.next().unwrap();
+            (parts.next().unwrap(), parent)
+        })
+        .collect();
+    let ancestors = |object: &str| -> Vec<&str> {
+        successors(graph.get(object), |n| graph.get(*n))
+            .cloned()
+            .collect()
+    };
+
+    let orbits = graph.values().map(|n| ancestors(n).len() + 1).sum();
+
+    let (yours, santas) = (ancestors("YOU"), ancestors("SAN"));
+    let transfers = yours
+        .iter()
+        .enumerate()
+        .find_map(|(i, v)| santas.iter().position(|w| v == w).map(|j| i + j))
+        .unwrap();
+
+    (orbits, transfers)
+}
+
+fn interning(c: &mut Criterion) {
+    let input = synthetic_map(&mut Rng::seeded(SEED));
+
+    let mut group = c.benchmark_group("day6/orbits");
+    group.sample_size(10);
+    group.bench_function("by name", |b| b.iter(|| by_name(black_box(&input))));
+    group.bench_function("interned", |b| {
+        b.iter(|| (solve_part1(black_box(&input)), solve_part2(&input)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, interning);
+criterion_main!(benches);
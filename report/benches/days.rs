@@ -0,0 +1,30 @@
+//! A benchmark for each part of every day on its personal input, so a change
+//! that slows a day down shows up against the last run. Days without their
+//! input are left out.
+
+use common::inputs::personal_in;
+use criterion::{criterion_group, criterion_main, Criterion};
+use report::{Solver, YEARS};
+use std::hint::black_box;
+
+fn days(c: &mut Criterion) {
+    for year in &YEARS {
+        for day in year.days {
+            let input = match personal_in(year.number, day.number) {
+                Some(input) => input,
+                None => continue,
+            };
+
+            let mut group = c.benchmark_group(format!("{}/day{}", year.number, day.number));
+            for part in 1..=2 {
+                group.bench_function(format!("part{}", part), |b| {
+                    b.iter(|| day.part(part, black_box(&input)))
+                });
+            }
+            group.finish();
+        }
+    }
+}
+
+criterion_group!(benches, days);
+criterion_main!(benches);
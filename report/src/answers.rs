@@ -0,0 +1,224 @@
+//! Known-correct answers for the personal inputs, kept in `answers.toml` at the
+//! top of the repository, and checking every solver still gives them. Anything
+//! shared, like the Intcode computer, can then be refactored and checked with
+//! one command.
+//!
+//! The file is plain TOML, one table per day holding its parts' answers:
+//!
+//! ```toml
+//! [2019.day8]
+//! part1 = 2500
+//! part2 = "CYUAH"
+//! ```
+//!
+//! Only that much of TOML is read, so it's parsed here rather than with a crate.
+
+use crate::{panic_message, year, Solver};
+use common::answer::Answer;
+use common::inputs::personal_in;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+
+/// Returns where the answers file is.
+pub fn answers_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("answers.toml")
+}
+
+/// A part's known answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expected {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub answer: Answer,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/// Reads a `[<year>.day<N>]` header.
+fn header(line: &str) -> Option<(u32, u32)> {
+    let inside = line.strip_prefix('[')?.strip_suffix(']')?;
+    let (year, day) = inside.trim().split_once('.')?;
+    Some((year.parse().ok()?, day.strip_prefix("day")?.parse().ok()?))
+}
+
+/// Reads a number or a double-quoted string, without escapes.
+fn value(text: &str) -> Option<Answer> {
+    if let Some(text) = text.strip_prefix('"') {
+        let text = text.strip_suffix('"')?;
+        return Some(Answer::Text(text.to_string())).filter(|_| !text.contains('"'));
+    }
+    text.replace('_', "").parse().ok().map(Answer::Int)
+}
+
+/// Parses the answers file, in the order the answers appear.
+pub fn parse(text: &str) -> Result<Vec<Expected>, ParseError> {
+    let mut answers = Vec::new();
+    let mut day = None;
+    for (index, line) in text.lines().enumerate() {
+        let error = |message: &str| ParseError {
+            line: index + 1,
+            message: message.to_string(),
+        };
+
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if line.starts_with('[') {
+            day = Some(header(line).ok_or_else(|| error("expected [<year>.day<N>]"))?);
+            continue;
+        }
+
+        let (year, day) = day.ok_or_else(|| error("answer outside a day's table"))?;
+        let (key, answer) = line
+            .split_once('=')
+            .ok_or_else(|| error("expected part<N> = <answer>"))?;
+        let part = key
+            .trim()
+            .strip_prefix("part")
+            .and_then(|part| part.parse().ok())
+            .filter(|part| *part == 1 || *part == 2)
+            .ok_or_else(|| error("expected part1 or part2"))?;
+        let answer = value(answer.trim())
+            .ok_or_else(|| error("expected a number or a double-quoted string"))?;
+        answers.push(Expected {
+            year,
+            day,
+            part,
+            answer,
+        });
+    }
+    Ok(answers)
+}
+
+/// How a part did against its known answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Check {
+    Same,
+    Changed(Answer),
+    Panicked(String),
+    NoSolution,
+    /// There's no personal input to check it on, which isn't a failure since
+    /// inputs aren't checked in.
+    NoInput,
+}
+
+impl Check {
+    pub fn failed(&self) -> bool {
+        !matches!(self, Check::Same | Check::NoInput)
+    }
+}
+
+/// Solves the part on its personal input and compares the answer with the
+/// known one. Answers compare as text, so a number or letters read from
+/// pixels can be written down as either.
+pub fn check(expected: &Expected) -> Check {
+    let day = match year(expected.year).and_then(|year| year.day(expected.day)) {
+        Some(day) => day,
+        None => return Check::NoSolution,
+    };
+    let input = match personal_in(expected.year, expected.day) {
+        Some(input) => input,
+        None => return Check::NoInput,
+    };
+
+    match panic::catch_unwind(AssertUnwindSafe(|| day.part(expected.part, &input))) {
+        Ok(Some(answer)) if answer.to_string() == expected.answer.to_string() => Check::Same,
+        Ok(Some(answer)) => Check::Changed(answer),
+        Ok(None) => Check::NoSolution,
+        Err(payload) => Check::Panicked(panic_message(payload)),
+    }
+}
+
+/// Describes a check as a line of `aoc verify`'s output.
+pub fn describe(expected: &Expected, check: &Check) -> String {
+    let part = format!(
+        "{} day {} part {}",
+        expected.year, expected.day, expected.part
+    );
+    match check {
+        Check::Same => format!("{}: ok", part),
+        Check::Changed(answer) => format!(
+            "{}: changed, expected {} but got {}",
+            part, expected.answer, answer
+        ),
+        Check::Panicked(message) => format!("{}: panicked: {}", part, message),
+        Check::NoSolution => format!("{}: no solution", part),
+        Check::NoInput => format!("{}: no personal input, skipped", part),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let text = "# known answers\n\n[2019.day1]\npart1 = 34_241\n\n\
+                    [2019.day8]\npart2 = \"CYUAH\"\n";
+        assert_eq!(
+            parse(text),
+            Ok(vec![
+                Expected {
+                    year: 2019,
+                    day: 1,
+                    part: 1,
+                    answer: Answer::Int(34241),
+                },
+                Expected {
+                    year: 2019,
+                    day: 8,
+                    part: 2,
+                    answer: Answer::from("CYUAH"),
+                },
+            ])
+        );
+
+        assert_eq!(parse("part1 = 1").unwrap_err().line, 1);
+        assert_eq!(
+            parse("[2019.day1]\npart3 = 1").unwrap_err().to_string(),
+            "line 2: expected part1 or part2"
+        );
+        assert!(parse("[2019.day1]\npart1 = \"unterminated").is_err());
+        assert!(parse("[2019]\n").is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        let expected = |day, answer: i64| Expected {
+            year: 2019,
+            day,
+            part: 1,
+            answer: Answer::Int(answer),
+        };
+        assert_eq!(check(&expected(25, 1)), Check::NoSolution);
+        assert!(!Check::NoInput.failed());
+        assert!(Check::Changed(Answer::Int(2)).failed());
+        assert_eq!(
+            describe(&expected(1, 1), &Check::Changed(Answer::Int(2))),
+            "2019 day 1 part 1: changed, expected 1 but got 2"
+        );
+    }
+
+    #[test]
+    fn test_answers_file() {
+        let text = std::fs::read_to_string(answers_path()).unwrap();
+        let answers = parse(&text).unwrap();
+        // both parts of every day there's a solution for
+        assert_eq!(answers.len(), 2 * year(2019).unwrap().days.len());
+    }
+}
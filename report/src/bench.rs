@@ -0,0 +1,250 @@
+//! Times parts that can be solved more than one way in this repository against
+//! each other on the personal inputs: the days' own interpreters against the
+//! shared Intcode computer, its strict decoding against its lenient decoding,
+//! and day 7's serial search sharing prefixes against trying every order in
+//! parallel.
+
+use common::answer::Answer;
+use common::parse::parse_csv;
+use intcode::Intcode;
+use std::fmt::Write;
+use std::sync::mpsc::channel;
+use std::time::{Duration, Instant};
+
+/// One way of solving a part. The first variant listed for a part is the one
+/// the others are compared against.
+pub struct Variant {
+    pub day: u32,
+    pub part: u32,
+    pub name: &'static str,
+    pub solve: fn(&str) -> Answer,
+}
+
+fn program(input: &str) -> Vec<i64> {
+    parse_csv(input).unwrap_or_else(|err| panic!("{}", err))
+}
+
+/// Runs the program on the shared computer and returns its last output.
+fn last_output(input: &str, inputs: &[i64], lenient: bool) -> Answer {
+    let (_send_in, recv_in) = channel();
+    let (send_out, recv_out) = channel();
+    let mut computer = Intcode::new(&program(input), recv_in, send_out);
+    if lenient {
+        computer = computer.with_lenient_decoding();
+    }
+    for value in inputs {
+        computer.push_input(*value);
+    }
+    computer.evaluate().unwrap_or_else(|err| panic!("{}", err));
+
+    let last = recv_out.try_iter().last().expect("no output");
+    last.into()
+}
+
+/// Day 2's first part on the shared computer.
+fn day2_shared(input: &str) -> Answer {
+    let mut program = program(input);
+    program[1] = 12;
+    program[2] = 2;
+
+    let (_send_in, recv_in) = channel();
+    let (send_out, _recv_out) = channel();
+    let mut computer = Intcode::new(&program, recv_in, send_out);
+    computer.evaluate().unwrap_or_else(|err| panic!("{}", err));
+    computer.memory(0).into()
+}
+
+/// Day 7's first part trying every order of phase settings in parallel.
+fn day7_parallel(input: &str) -> Answer {
+    let phases = day7::SERIAL_PHASES;
+    match day7::max_thruster_signal(&program(input), phases.len(), &phases) {
+        Ok((signal, _)) => signal.into(),
+        Err(err) => panic!("{}", err),
+    }
+}
+
+pub const VARIANTS: [Variant; 12] = [
+    Variant {
+        day: 2,
+        part: 1,
+        name: "day 2 interpreter",
+        solve: |input| day2::solve_part1(input).into(),
+    },
+    Variant {
+        day: 2,
+        part: 1,
+        name: "shared computer",
+        solve: day2_shared,
+    },
+    Variant {
+        day: 5,
+        part: 1,
+        name: "day 5 interpreter",
+        solve: |input| day5::solve_part1(input).into(),
+    },
+    Variant {
+        day: 5,
+        part: 1,
+        name: "shared computer",
+        solve: |input| last_output(input, &[1], false),
+    },
+    Variant {
+        day: 5,
+        part: 2,
+        name: "day 5 interpreter",
+        solve: |input| day5::solve_part2(input).into(),
+    },
+    Variant {
+        day: 5,
+        part: 2,
+        name: "shared computer",
+        solve: |input| last_output(input, &[5], false),
+    },
+    Variant {
+        day: 7,
+        part: 1,
+        name: "serial, sharing prefixes",
+        solve: |input| day7::solve_part1(input).into(),
+    },
+    Variant {
+        day: 7,
+        part: 1,
+        name: "every order in parallel",
+        solve: day7_parallel,
+    },
+    Variant {
+        day: 9,
+        part: 1,
+        name: "strict decoding",
+        solve: |input| day9::solve_part1(input).into(),
+    },
+    Variant {
+        day: 9,
+        part: 1,
+        name: "lenient decoding",
+        solve: |input| last_output(input, &[day9::TEST_MODE], true),
+    },
+    Variant {
+        day: 9,
+        part: 2,
+        name: "strict decoding",
+        solve: |input| day9::solve_part2(input).into(),
+    },
+    Variant {
+        day: 9,
+        part: 2,
+        name: "lenient decoding",
+        solve: |input| last_output(input, &[day9::SENSOR_BOOST_MODE], true),
+    },
+];
+
+/// How a variant did on an input.
+pub struct Timing {
+    pub variant: &'static Variant,
+    pub answer: Answer,
+    /// The median of the runs.
+    pub elapsed: Duration,
+}
+
+/// Solves the input with the variant `runs` times.
+pub fn time(variant: &'static Variant, input: &str, runs: usize) -> Timing {
+    let mut answer = None;
+    let mut times: Vec<Duration> = (0..runs.max(1))
+        .map(|_| {
+            let start = Instant::now();
+            answer = Some((variant.solve)(input));
+            start.elapsed()
+        })
+        .collect();
+    times.sort();
+
+    Timing {
+        variant,
+        answer: answer.unwrap(),
+        elapsed: times[times.len() / 2],
+    }
+}
+
+/// Lays the timings out with a row per variant under a heading per part,
+/// each with its speedup over the part's first variant. Answers that differ
+/// from the first variant's are flagged.
+pub fn render(timings: &[Timing]) -> String {
+    let width = timings
+        .iter()
+        .map(|timing| timing.variant.name.len())
+        .max()
+        .unwrap_or(0);
+
+    let mut out = String::new();
+    let mut baseline: Option<&Timing> = None;
+    for timing in timings {
+        let variant = timing.variant;
+        let same_part =
+            |base: &&Timing| (base.variant.day, base.variant.part) == (variant.day, variant.part);
+        if !baseline.as_ref().is_some_and(same_part) {
+            writeln!(out, "day {} part {}", variant.day, variant.part).unwrap();
+            baseline = Some(timing);
+        }
+
+        let base = baseline.unwrap();
+        let speedup = base.elapsed.as_secs_f64() / timing.elapsed.as_secs_f64().max(1e-9);
+        write!(
+            out,
+            "  {:<width$}  {:>10.3?}  {:>6.2}x",
+            variant.name,
+            timing.elapsed,
+            speedup,
+            width = width
+        )
+        .unwrap();
+        if timing.answer != base.answer {
+            write!(out, "  answered {}, not {}", timing.answer, base.answer).unwrap();
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_variants() {
+        // the same day 9 program either way
+        let input = "104,1125899906842624,99";
+        let strict = time(&VARIANTS[10], input, 3);
+        let lenient = time(&VARIANTS[11], input, 3);
+        assert_eq!(strict.answer, lenient.answer);
+
+        let rendered = render(&[strict, lenient]);
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "day 9 part 2");
+        assert!(lines[1].starts_with("  strict decoding "), "{}", rendered);
+        assert!(lines[1].ends_with("  1.00x"), "{}", rendered);
+        assert!(lines[2].starts_with("  lenient decoding "), "{}", rendered);
+    }
+
+    #[test]
+    fn test_mismatch() {
+        let timings = [
+            Timing {
+                variant: &VARIANTS[0],
+                answer: Answer::Int(1),
+                elapsed: Duration::from_millis(2),
+            },
+            Timing {
+                variant: &VARIANTS[1],
+                answer: Answer::Int(2),
+                elapsed: Duration::from_millis(1),
+            },
+        ];
+        let rendered = render(&timings);
+        assert!(
+            rendered.ends_with("  2.00x  answered 2, not 1\n"),
+            "{}",
+            rendered
+        );
+    }
+}
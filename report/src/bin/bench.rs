@@ -0,0 +1,57 @@
+use clap::{Parser, Subcommand};
+use common::inputs::{personal_in, personal_path, YEAR};
+use report::bench::{render, time, VARIANTS};
+use std::process;
+
+const DEFAULT_RUNS: usize = 5;
+
+/// Times the parts that can be solved more than one way against each other.
+#[derive(Parser)]
+#[command(name = "bench")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Time every variant of the chosen days' parts and print how they compare
+    Compare {
+        /// Only compare this day's variants, can be repeated; all days by default
+        #[arg(long = "day", value_name = "DAY")]
+        days: Vec<u32>,
+        /// How many times to run each variant, keeping the median
+        #[arg(long, default_value_t = DEFAULT_RUNS)]
+        runs: usize,
+    },
+}
+
+fn main() {
+    let Command::Compare { days, runs } = Cli::parse().command;
+
+    let mut timings = Vec::new();
+    let mut skipped = Vec::new();
+    for variant in VARIANTS.iter() {
+        if !days.is_empty() && !days.contains(&variant.day) {
+            continue;
+        }
+        match personal_in(YEAR, variant.day) {
+            Some(input) => timings.push(time(variant, &input, runs)),
+            None if !skipped.contains(&variant.day) => skipped.push(variant.day),
+            None => {}
+        }
+    }
+
+    for day in skipped {
+        eprintln!(
+            "skipping day {}, no input at {}",
+            day,
+            personal_path(day).display()
+        );
+    }
+    if timings.is_empty() {
+        eprintln!("nothing to compare");
+        process::exit(1);
+    }
+    print!("{}", render(&timings));
+}
@@ -0,0 +1,178 @@
+use common::color::Color;
+use common::grid::Grid;
+use common::inputs::personal_in;
+use ratatui::crossterm::event::{self, Event as Input, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color as Rgb, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph, Row, Table, TableState};
+use ratatui::{DefaultTerminal, Frame};
+use report::dashboard::{spawn_runs, Dashboard, Event};
+use std::env;
+use std::io;
+use std::panic;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::time::Duration;
+
+const KEYS: &str = "↑/↓ select  r re-run  a re-run all  v picture  q quit";
+
+/// The dashboard along with what the terminal is showing.
+struct App {
+    dashboard: Dashboard,
+    table: TableState,
+    /// The selected day's picture, while it's open.
+    picture: Option<Grid<Color>>,
+    parallel: bool,
+    events: Sender<Event>,
+}
+
+impl App {
+    fn rerun(&mut self, indices: &[usize]) {
+        let runs = self.dashboard.rerun(indices);
+        spawn_runs(runs, self.parallel, self.events.clone());
+    }
+
+    /// Opens the selected day's picture, or closes the one that's open.
+    fn toggle_picture(&mut self) {
+        if self.picture.take().is_some() {
+            return;
+        }
+
+        let entry = &self.dashboard.entries[self.dashboard.selected];
+        if let (Some(draw), Some(input)) =
+            (entry.day.picture, personal_in(entry.year, entry.day.number))
+        {
+            self.picture = Some(draw(&input));
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [table_area, keys_area] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+
+        let rows = self.dashboard.rows().into_iter().map(|cells| {
+            let style = match cells[2].as_str() {
+                "done" => Style::default().fg(Rgb::Green),
+                "running" => Style::default().fg(Rgb::Yellow),
+                "panicked" => Style::default().fg(Rgb::Red),
+                _ => Style::default().fg(Rgb::DarkGray),
+            };
+            Row::new(cells).style(style)
+        });
+        let widths = [
+            Constraint::Length(4),
+            Constraint::Length(3),
+            Constraint::Length(8),
+            Constraint::Min(20),
+            Constraint::Length(12),
+        ];
+        let table = Table::new(rows, widths)
+            .header(
+                Row::new(["year", "day", "status", "answers", "time"])
+                    .style(Style::default().add_modifier(Modifier::BOLD)),
+            )
+            .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+            .block(Block::bordered().title(if self.dashboard.finished() {
+                "Advent of Code: done"
+            } else {
+                "Advent of Code: running"
+            }));
+
+        self.table.select(Some(self.dashboard.selected));
+        frame.render_stateful_widget(table, table_area, &mut self.table);
+        frame.render_widget(Paragraph::new(KEYS), keys_area);
+
+        if let Some(picture) = &self.picture {
+            let lines: Vec<Line> = picture
+                .rows()
+                .map(|row| {
+                    Line::from(
+                        row.iter()
+                            .map(|color| {
+                                Span::styled(
+                                    "██",
+                                    Style::default().fg(Rgb::Rgb(color.r, color.g, color.b)),
+                                )
+                            })
+                            .collect::<Vec<_>>(),
+                    )
+                })
+                .collect();
+            let width = (picture.width() * 2 + 2).min(table_area.width as usize) as u16;
+            let height = (picture.height() + 2).min(table_area.height as usize) as u16;
+            let [area] = Layout::horizontal([Constraint::Length(width)])
+                .flex(ratatui::layout::Flex::Center)
+                .areas(table_area);
+            let [area] = Layout::vertical([Constraint::Length(height)])
+                .flex(ratatui::layout::Flex::Center)
+                .areas(area);
+
+            frame.render_widget(ratatui::widgets::Clear, area);
+            frame.render_widget(
+                Paragraph::new(lines).block(Block::bordered().title("picture")),
+                area,
+            );
+        }
+    }
+}
+
+fn run(terminal: &mut DefaultTerminal, app: &mut App, updates: Receiver<Event>) -> io::Result<()> {
+    loop {
+        while let Ok(event) = updates.try_recv() {
+            app.dashboard.apply(event);
+        }
+        terminal.draw(|frame| app.draw(frame))?;
+
+        // wake up now and then to show runs finishing
+        if !event::poll(Duration::from_millis(100))? {
+            continue;
+        }
+        if let Input::Key(key) = event::read()? {
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc if app.picture.is_none() => return Ok(()),
+                KeyCode::Char('q') | KeyCode::Esc | KeyCode::Char('v') => app.toggle_picture(),
+                KeyCode::Down | KeyCode::Char('j') => app.dashboard.select_next(),
+                KeyCode::Up | KeyCode::Char('k') => app.dashboard.select_previous(),
+                KeyCode::Char('r') => app.rerun(&[app.dashboard.selected]),
+                KeyCode::Char('a') => {
+                    let all: Vec<usize> = (0..app.dashboard.entries.len()).collect();
+                    app.rerun(&all);
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+fn main() -> io::Result<()> {
+    // `--parallel` runs every day at once instead of one after another
+    let mut parallel = false;
+    for arg in env::args().skip(1) {
+        match arg.as_str() {
+            "--parallel" => parallel = true,
+            _ => panic!("unknown argument: {}", arg),
+        }
+    }
+
+    // a day that panics shows as such, so don't print over the dashboard too
+    panic::set_hook(Box::new(|_| {}));
+
+    let (events, updates) = channel();
+    let mut app = App {
+        dashboard: Dashboard::new(),
+        table: TableState::default(),
+        picture: None,
+        parallel,
+        events,
+    };
+    let all: Vec<usize> = (0..app.dashboard.entries.len()).collect();
+    app.rerun(&all);
+
+    let mut terminal = ratatui::init();
+    let result = run(&mut terminal, &mut app, updates);
+    ratatui::restore();
+    result
+}
@@ -0,0 +1,42 @@
+use common::inputs::inputs_dir;
+use report::doctor::{check_day, check_inputs_dir};
+use report::YEARS;
+use std::panic;
+use std::process;
+
+fn main() {
+    // a panicking example is reported as a problem, so don't print it as well
+    panic::set_hook(Box::new(|_| {}));
+
+    let mut problems = Vec::new();
+    match check_inputs_dir() {
+        Ok(()) => println!("ok    inputs directory {}", inputs_dir().display()),
+        Err(problem) => {
+            println!("FAIL  {}", problem);
+            problems.push(problem);
+        }
+    }
+
+    for year in &YEARS {
+        for day in year.days {
+            let found = check_day(year.number, day);
+            if found.is_empty() {
+                println!(
+                    "ok    {} day {}: input present, examples pass",
+                    year.number, day.number
+                );
+            }
+            for problem in &found {
+                println!("FAIL  {} {}", year.number, problem);
+            }
+            problems.extend(found);
+        }
+    }
+
+    if problems.is_empty() {
+        println!("ready");
+    } else {
+        println!("{} problems", problems.len());
+        process::exit(1);
+    }
+}
@@ -0,0 +1,72 @@
+use common::inputs::{example_path_in, YEAR};
+use report::client::Client;
+use report::puzzle::{articles, examples, to_text};
+use std::env;
+use std::fs;
+use std::process;
+
+const USAGE: &str = "usage: puzzle <day> [--year <year>] [--examples]";
+
+fn usage() -> ! {
+    eprintln!("{}", USAGE);
+    process::exit(2);
+}
+
+/// Prints a day's puzzle as text, and with `--examples` saves the examples in
+/// it where the tests look for them, keeping any that are already there.
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (day, flags) = match args.split_first() {
+        Some((day, flags)) => (day.parse().unwrap_or_else(|_| usage()), flags),
+        None => usage(),
+    };
+
+    let (mut year, mut save_examples) = (YEAR, false);
+    let mut flags = flags.iter();
+    while let Some(flag) = flags.next() {
+        match flag.as_str() {
+            "--year" => {
+                year = flags
+                    .next()
+                    .and_then(|year| year.parse().ok())
+                    .unwrap_or_else(|| usage())
+            }
+            "--examples" => save_examples = true,
+            _ => usage(),
+        }
+    }
+
+    let client = Client::from_env();
+    let page = client.puzzle_page(year, day).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        process::exit(1);
+    });
+    let puzzle = articles(&page);
+    print!("{}", to_text(&puzzle));
+    if !client.is_logged_in() {
+        eprintln!("(set AOC_SESSION to see part 2 once part 1 is solved)");
+    }
+
+    if !save_examples {
+        return;
+    }
+    for (index, example) in examples(&puzzle).iter().enumerate() {
+        let path = example_path_in(year, day, index);
+        if path.exists() {
+            eprintln!("kept {}", path.display());
+            continue;
+        }
+
+        let written = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(&path, example));
+        match written {
+            Ok(()) => eprintln!("wrote {}", path.display()),
+            Err(err) => {
+                eprintln!("can't write {}: {}", path.display(), err);
+                process::exit(1);
+            }
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use report::serve::serve;
+use std::env;
+use std::process;
+
+/// Default address, only reachable from this machine; pass `--addr 0.0.0.0:8021`
+/// to share it.
+const DEFAULT_ADDR: &str = "127.0.0.1:8021";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let addr = match args.as_slice() {
+        [] => DEFAULT_ADDR,
+        [flag, addr] if flag == "--addr" => addr,
+        _ => {
+            eprintln!("usage: serve [--addr <host:port>]");
+            process::exit(2);
+        }
+    };
+
+    let addr = addr.parse().unwrap_or_else(|err| {
+        eprintln!("bad address {:?}: {}", addr, err);
+        process::exit(2);
+    });
+    if let Err(err) = serve(addr) {
+        eprintln!("can't serve on {}: {}", addr, err);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,31 @@
+use report::stats::{collect, summary};
+use report::timeline::{load, solves_path};
+use report::{run, YEARS};
+use std::process;
+
+fn main() {
+    let path = solves_path();
+    let solves = match load(&path) {
+        Ok(solves) => solves,
+        Err(err) => {
+            eprintln!("can't read {}: {}", path.display(), err);
+            process::exit(1);
+        }
+    };
+
+    for (index, year) in YEARS.iter().enumerate() {
+        let runs: Vec<_> = year
+            .days
+            .iter()
+            .map(|day| {
+                eprintln!("running {} day {}", year.number, day.number);
+                run(year.number, day)
+            })
+            .collect();
+
+        if index > 0 {
+            println!();
+        }
+        print!("{}", summary(year.number, &collect(year, &runs, &solves)));
+    }
+}
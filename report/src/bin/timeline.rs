@@ -0,0 +1,63 @@
+use common::inputs::YEAR;
+use report::timeline::{load, record, render, solves_path, Solve};
+use std::env;
+use std::process;
+
+const USAGE: &str = "usage: timeline [record <day> <part> [--year <year>]]
+
+Lists when each part was first solved. aoc submit and aoc verify record
+solves as they happen; record is only for backfilling a part solved some
+other way, and marks it solved now.";
+
+fn number(arg: Option<String>) -> u32 {
+    arg.and_then(|arg| arg.parse().ok()).unwrap_or_else(|| {
+        eprintln!("{}", USAGE);
+        process::exit(2);
+    })
+}
+
+fn main() {
+    let path = solves_path();
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    if args.iter().any(|arg| arg == "--help") {
+        println!("{}", USAGE);
+        return;
+    }
+    if args.is_empty() {
+        match load(&path) {
+            Ok(solves) if solves.is_empty() => println!("nothing solved yet"),
+            Ok(solves) => print!("{}", render(&solves)),
+            Err(err) => {
+                eprintln!("can't read {}: {}", path.display(), err);
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    // `record <day> <part>` backfills a part solved outside aoc, as solved now
+    let mut args = args.into_iter();
+    if args.next().as_deref() != Some("record") {
+        eprintln!("{}", USAGE);
+        process::exit(2);
+    }
+    let day = number(args.next());
+    let part = number(args.next());
+    let year = match args.next().as_deref() {
+        Some("--year") => number(args.next()),
+        Some(_) => number(None),
+        None => YEAR,
+    };
+
+    let solve = Solve::now(year, day, part);
+
+    match record(&path, solve) {
+        Ok(true) => print!("{}", render(&[solve])),
+        Ok(false) => println!("{} day {} part {} was already solved", year, day, part),
+        Err(err) => {
+            eprintln!("can't write {}: {}", path.display(), err);
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,254 @@
+//! A small client for adventofcode.com. Puzzle pages are public, but the
+//! second part only shows up for someone logged in who's solved the first, and
+//! inputs only for someone logged in, so requests carry the session cookie
+//! from `AOC_SESSION`, or failing that the session file, when there is one.
+
+use std::env;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Environment variable holding the value of the site's `session` cookie.
+pub const SESSION_ENV: &str = "AOC_SESSION";
+pub const BASE_URL: &str = "https://adventofcode.com";
+/// Identifies the tool to the site's operators, as they ask.
+const USER_AGENT: &str = "github.com/fharding1/adventofcode-2019 by franklinharding0.0@gmail.com";
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FetchError {
+    /// The site answered with an error, e.g. 404 for a day that isn't out yet.
+    Status { url: String, status: u16 },
+    /// The request never got an answer.
+    Transport { url: String, error: String },
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::Status { url, status } => write!(f, "{} answered {}", url, status),
+            FetchError::Transport { url, error } => write!(f, "can't fetch {}: {}", url, error),
+        }
+    }
+}
+
+pub struct Client {
+    base: String,
+    session: Option<String>,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(base: &str, session: Option<String>) -> Self {
+        Client {
+            base: base.trim_end_matches('/').to_string(),
+            session,
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(TIMEOUT)
+                .build(),
+        }
+    }
+
+    /// A client for the site, logged in if `AOC_SESSION` is set or the
+    /// session file holds a cookie.
+    pub fn from_env() -> Self {
+        let session = env::var(SESSION_ENV)
+            .ok()
+            .and_then(|session| read_session(&session))
+            .or_else(|| {
+                session_file()
+                    .and_then(|path| fs::read_to_string(path).ok())
+                    .and_then(|session| read_session(&session))
+            });
+        Client::new(BASE_URL, session)
+    }
+
+    pub fn is_logged_in(&self) -> bool {
+        self.session.is_some()
+    }
+
+    /// Starts a request to `path` on the site, carrying the session cookie,
+    /// and returns it with its full URL.
+    fn request(&self, method: &str, path: &str) -> (String, ureq::Request) {
+        let url = format!("{}{}", self.base, path);
+        let mut request = self.agent.request(method, &url);
+        if let Some(session) = &self.session {
+            request = request.set("Cookie", &format!("session={}", session));
+        }
+        (url, request)
+    }
+
+    fn get(&self, path: &str) -> Result<String, FetchError> {
+        let (url, request) = self.request("GET", path);
+        body(url, request.call())
+    }
+
+    /// Returns the HTML of the puzzle's page.
+    pub fn puzzle_page(&self, year: u32, day: u32) -> Result<String, FetchError> {
+        self.get(&format!("/{}/day/{}", year, day))
+    }
+
+    /// Submits an answer to part 1 or 2 of the puzzle, returning the page the
+    /// site answers with, which `puzzle::Verdict` reads.
+    pub fn submit(
+        &self,
+        year: u32,
+        day: u32,
+        part: u32,
+        answer: &str,
+    ) -> Result<String, FetchError> {
+        let (url, request) = self.request("POST", &format!("/{}/day/{}/answer", year, day));
+        let level = part.to_string();
+        body(
+            url,
+            request.send_form(&[("level", &level), ("answer", answer)]),
+        )
+    }
+
+    /// Returns the logged in user's input for the puzzle. The site answers 400
+    /// to anyone not logged in.
+    pub fn input(&self, year: u32, day: u32) -> Result<String, FetchError> {
+        self.get(&format!("/{}/day/{}/input", year, day))
+    }
+}
+
+/// Returns the body of a response, or what went wrong getting it.
+fn body(url: String, response: Result<ureq::Response, ureq::Error>) -> Result<String, FetchError> {
+    match response {
+        Ok(response) => response.into_string().map_err(|err| FetchError::Transport {
+            url,
+            error: err.to_string(),
+        }),
+        Err(ureq::Error::Status(status, _)) => Err(FetchError::Status { url, status }),
+        Err(err) => Err(FetchError::Transport {
+            url,
+            error: err.to_string(),
+        }),
+    }
+}
+
+/// Returns where the session cookie is kept when `AOC_SESSION` isn't set:
+/// `aoc/session` in `$XDG_CONFIG_HOME`, or in `~/.config` without it.
+pub fn session_file() -> Option<PathBuf> {
+    let config = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))?;
+    Some(config.join("aoc").join("session"))
+}
+
+/// Returns the cookie in `text`, ignoring surrounding whitespace and a
+/// `session=` copied along with it, or None if there's nothing there.
+fn read_session(text: &str) -> Option<String> {
+    let text = text.trim();
+    let session = text.strip_prefix("session=").unwrap_or(text);
+    Some(session.to_string()).filter(|session| !session.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// Answers one request with `status` and `body`, returning the request's
+    /// headers.
+    fn answer_once(
+        status: &'static str,
+        body: &'static str,
+    ) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut headers = Vec::new();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                headers.push(line.trim().to_string());
+            }
+            write!(
+                &stream,
+                "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            )
+            .unwrap();
+            headers
+        });
+        (base, server)
+    }
+
+    #[test]
+    fn test_puzzle_page() {
+        let (base, server) = answer_once("200 OK", "<article>hi</article>");
+        let client = Client::new(&base, Some(String::from("abc")));
+        assert_eq!(
+            client.puzzle_page(2019, 3),
+            Ok(String::from("<article>hi</article>"))
+        );
+
+        let headers = server.join().unwrap();
+        assert_eq!(headers[0], "GET /2019/day/3 HTTP/1.1");
+        assert!(headers.contains(&String::from("Cookie: session=abc")));
+        assert!(headers
+            .iter()
+            .any(|header| header.starts_with("User-Agent: github.com/")));
+    }
+
+    #[test]
+    fn test_input() {
+        let (base, server) = answer_once("200 OK", "1\n2\n");
+        let client = Client::new(&base, Some(String::from("abc")));
+        assert_eq!(client.input(2019, 1), Ok(String::from("1\n2\n")));
+        assert_eq!(server.join().unwrap()[0], "GET /2019/day/1/input HTTP/1.1");
+    }
+
+    #[test]
+    fn test_submit() {
+        let (base, server) = answer_once("200 OK", "<article>ok</article>");
+        let client = Client::new(&base, Some(String::from("abc")));
+        assert_eq!(
+            client.submit(2019, 4, 2, "1253"),
+            Ok(String::from("<article>ok</article>"))
+        );
+
+        let headers = server.join().unwrap();
+        assert_eq!(headers[0], "POST /2019/day/4/answer HTTP/1.1");
+        assert!(headers.contains(&String::from(
+            "Content-Type: application/x-www-form-urlencoded"
+        )));
+    }
+
+    #[test]
+    fn test_read_session() {
+        assert_eq!(read_session("abc\n"), Some(String::from("abc")));
+        assert_eq!(read_session(" session=abc"), Some(String::from("abc")));
+        assert_eq!(read_session("\n"), None);
+    }
+
+    #[test]
+    fn test_status() {
+        let (base, server) = answer_once("404 Not Found", "not yet");
+        let client = Client::new(&base, None);
+        assert_eq!(
+            client.puzzle_page(2019, 26),
+            Err(FetchError::Status {
+                url: format!("{}/2019/day/26", base),
+                status: 404,
+            })
+        );
+        assert!(!server
+            .join()
+            .unwrap()
+            .iter()
+            .any(|header| header.starts_with("Cookie")));
+    }
+}
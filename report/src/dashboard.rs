@@ -0,0 +1,230 @@
+//! What the dashboard shows: every day of every year, each waiting, running or
+//! done, updated as runs on other threads report back.
+
+use crate::{panic_message, run, Day, Run, YEARS};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::Sender;
+use std::thread;
+use std::time::Duration;
+
+/// Where a day's run has got to.
+pub enum Status {
+    Waiting,
+    Running,
+    Done(Run),
+    Panicked(String),
+}
+
+/// A day on the dashboard.
+pub struct Entry {
+    pub year: u32,
+    pub day: &'static Day,
+    pub status: Status,
+}
+
+/// What a run reports back, by the index of its entry.
+pub enum Event {
+    Started(usize),
+    Finished(usize, Run),
+    Panicked(usize, String),
+}
+
+/// Runs the day, reporting when it starts and how it went.
+pub fn run_entry(index: usize, year: u32, day: &'static Day, events: &Sender<Event>) {
+    // the dashboard may have quit, so nobody hearing back is fine
+    let _ = events.send(Event::Started(index));
+    let event = match panic::catch_unwind(AssertUnwindSafe(|| run(year, day))) {
+        Ok(run) => Event::Finished(index, run),
+        Err(payload) => Event::Panicked(index, panic_message(payload)),
+    };
+    let _ = events.send(event);
+}
+
+/// Runs the entries on a thread of their own each if `parallel`, otherwise one
+/// after another on a single thread.
+pub fn spawn_runs(entries: Vec<(usize, u32, &'static Day)>, parallel: bool, events: Sender<Event>) {
+    if parallel {
+        for (index, year, day) in entries {
+            let events = events.clone();
+            thread::spawn(move || run_entry(index, year, day, &events));
+        }
+    } else {
+        thread::spawn(move || {
+            for (index, year, day) in entries {
+                run_entry(index, year, day, &events);
+            }
+        });
+    }
+}
+
+/// The dashboard's state.
+pub struct Dashboard {
+    pub entries: Vec<Entry>,
+    pub selected: usize,
+}
+
+impl Default for Dashboard {
+    fn default() -> Self {
+        Dashboard::new()
+    }
+}
+
+impl Dashboard {
+    /// Lists every day of every year, all waiting to run.
+    pub fn new() -> Self {
+        let entries = YEARS
+            .iter()
+            .flat_map(|year| {
+                year.days.iter().map(move |day| Entry {
+                    year: year.number,
+                    day,
+                    status: Status::Waiting,
+                })
+            })
+            .collect();
+
+        Dashboard {
+            entries,
+            selected: 0,
+        }
+    }
+
+    /// Updates the entry the event is about.
+    pub fn apply(&mut self, event: Event) {
+        let (index, status) = match event {
+            Event::Started(index) => (index, Status::Running),
+            Event::Finished(index, run) => (index, Status::Done(run)),
+            Event::Panicked(index, message) => (index, Status::Panicked(message)),
+        };
+        if let Some(entry) = self.entries.get_mut(index) {
+            entry.status = status;
+        }
+    }
+
+    /// Marks the entries as waiting again and returns what to run for them.
+    pub fn rerun(&mut self, indices: &[usize]) -> Vec<(usize, u32, &'static Day)> {
+        indices
+            .iter()
+            .filter_map(|&index| {
+                let entry = self.entries.get_mut(index)?;
+                entry.status = Status::Waiting;
+                Some((index, entry.year, entry.day))
+            })
+            .collect()
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.entries.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Returns whether every entry has finished running.
+    pub fn finished(&self) -> bool {
+        self.entries
+            .iter()
+            .all(|entry| matches!(entry.status, Status::Done(_) | Status::Panicked(_)))
+    }
+
+    /// Returns the table's cells for each entry: year, day, status, answers and
+    /// how long the parts took.
+    pub fn rows(&self) -> Vec<[String; 5]> {
+        self.entries
+            .iter()
+            .map(|entry| {
+                let (status, answers, time) = match &entry.status {
+                    Status::Waiting => ("waiting", String::new(), String::new()),
+                    Status::Running => ("running", String::new(), String::new()),
+                    Status::Done(run) if run.parts.is_empty() => {
+                        ("no input", String::new(), String::new())
+                    }
+                    Status::Done(run) => {
+                        let answers: Vec<String> = run
+                            .parts
+                            .iter()
+                            .map(|part| part.answer.to_string())
+                            .collect();
+                        let time: Duration = run.parts.iter().map(|part| part.elapsed).sum();
+                        ("done", answers.join(" / "), format!("{:.3?}", time))
+                    }
+                    Status::Panicked(message) => ("panicked", message.clone(), String::new()),
+                };
+
+                [
+                    entry.year.to_string(),
+                    entry.day.number.to_string(),
+                    status.to_string(),
+                    answers,
+                    time,
+                ]
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Part;
+    use common::answer::Answer;
+    use std::sync::mpsc::channel;
+
+    #[test]
+    fn test_dashboard() {
+        let mut dashboard = Dashboard::new();
+        assert_eq!(dashboard.entries.len(), 9);
+        assert!(!dashboard.finished());
+
+        dashboard.apply(Event::Started(0));
+        dashboard.apply(Event::Finished(
+            1,
+            Run {
+                day: 2,
+                parts: vec![
+                    Part {
+                        answer: Answer::Int(12),
+                        elapsed: Duration::from_millis(1),
+                    },
+                    Part {
+                        answer: Answer::from("AB"),
+                        elapsed: Duration::from_millis(2),
+                    },
+                ],
+                picture: None,
+            },
+        ));
+        dashboard.apply(Event::Panicked(2, String::from("oops")));
+
+        let rows = dashboard.rows();
+        assert_eq!(rows[0][2], "running");
+        assert_eq!(rows[1][2..], ["done", "12 / AB", "3.000ms"]);
+        assert_eq!(rows[2][2..4], ["panicked", "oops"]);
+        assert_eq!(rows[3][..3], ["2019", "4", "waiting"]);
+
+        dashboard.select_previous();
+        assert_eq!(dashboard.selected, 0);
+        for _ in 0..20 {
+            dashboard.select_next();
+        }
+        assert_eq!(dashboard.selected, 8);
+
+        let runs = dashboard.rerun(&[1]);
+        assert_eq!(runs.len(), 1);
+        assert_eq!((runs[0].0, runs[0].1, runs[0].2.number), (1, 2019, 2));
+        assert_eq!(dashboard.rows()[1][2], "waiting");
+    }
+
+    #[test]
+    fn test_run_entry() {
+        let day = &YEARS[0].days[0];
+        let (events, received) = channel();
+        run_entry(4, 2019, day, &events);
+
+        assert!(matches!(received.recv().unwrap(), Event::Started(4)));
+        assert!(matches!(received.recv().unwrap(), Event::Finished(4, _)));
+    }
+}
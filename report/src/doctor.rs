@@ -0,0 +1,177 @@
+//! Checks that everything's ready to solve puzzles: the inputs directory can be
+//! written to, every day has its personal input, and every day still gets its
+//! examples right.
+
+use crate::{Day, Example, Solver};
+use common::inputs::{inputs_dir, personal_path_in};
+use std::fmt;
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+
+/// Something that needs fixing before solving.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+    InputsDir(String),
+    MissingInput {
+        day: u32,
+        path: PathBuf,
+    },
+    WrongAnswer {
+        day: u32,
+        example: usize,
+        part: usize,
+        expected: &'static str,
+        found: String,
+    },
+    Panicked {
+        day: u32,
+        example: usize,
+        part: usize,
+        message: String,
+    },
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Problem::InputsDir(err) => write!(f, "inputs directory: {}", err),
+            Problem::MissingInput { day, path } => {
+                write!(f, "day {}: no personal input at {}", day, path.display())
+            }
+            Problem::WrongAnswer {
+                day,
+                example,
+                part,
+                expected,
+                found,
+            } => write!(
+                f,
+                "day {}: part {} of example {} gave {}, expected {}",
+                day, part, example, found, expected
+            ),
+            Problem::Panicked {
+                day,
+                example,
+                part,
+                message,
+            } => write!(
+                f,
+                "day {}: part {} of example {} panicked: {}",
+                day, part, example, message
+            ),
+        }
+    }
+}
+
+/// Checks that a file can be created in the inputs directory, which is made if
+/// it doesn't exist yet.
+pub fn check_inputs_dir() -> Result<(), Problem> {
+    let dir = inputs_dir();
+    let probe = dir.join(".doctor");
+
+    fs::create_dir_all(&dir)
+        .and_then(|_| fs::write(&probe, ""))
+        .and_then(|_| fs::remove_file(&probe))
+        .map_err(|err| Problem::InputsDir(format!("{}: {}", dir.display(), err)))
+}
+
+/// Runs the part on the example, turning a panic into a problem.
+fn check_part(day: &Day, index: usize, example: &Example, part: usize) -> Option<Problem> {
+    let expected = example.answers[part - 1]?;
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        day.part(part as u32, example.input).unwrap().to_string()
+    }));
+
+    match result {
+        Ok(found) if found == expected => None,
+        Ok(found) => Some(Problem::WrongAnswer {
+            day: day.number,
+            example: index + 1,
+            part,
+            expected,
+            found,
+        }),
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|message| message.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| String::from("unknown panic"));
+            Some(Problem::Panicked {
+                day: day.number,
+                example: index + 1,
+                part,
+                message,
+            })
+        }
+    }
+}
+
+/// Checks that the year's day has its personal input and that it gets its
+/// examples right.
+pub fn check_day(year: u32, day: &Day) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    let path = personal_path_in(year, day.number);
+    if !path.is_file() {
+        problems.push(Problem::MissingInput {
+            day: day.number,
+            path,
+        });
+    }
+
+    for (index, example) in day.examples.iter().enumerate() {
+        for part in 1..=2 {
+            problems.extend(check_part(day, index, example, part));
+        }
+    }
+
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::YEARS;
+
+    #[test]
+    fn test_examples() {
+        for year in &YEARS {
+            for day in year.days {
+                let problems: Vec<Problem> = check_day(year.number, day)
+                    .into_iter()
+                    .filter(|problem| !matches!(problem, Problem::MissingInput { .. }))
+                    .collect();
+                assert_eq!(problems, Vec::new());
+            }
+        }
+    }
+
+    #[test]
+    fn test_problems() {
+        let day = Day {
+            number: 4,
+            parts: [|input| input.len().into(), |_| panic!("not yet")],
+            timed: |_| unreachable!(),
+            picture: None,
+            examples: &[Example {
+                input: "abc",
+                answers: [Some("4"), Some("0")],
+            }],
+        };
+
+        let problems: Vec<String> = check_day(2019, &day)
+            .iter()
+            .filter(|problem| !matches!(problem, Problem::MissingInput { .. }))
+            .map(|problem| problem.to_string())
+            .collect();
+        assert_eq!(
+            problems,
+            vec![
+                "day 4: part 1 of example 1 gave 3, expected 4",
+                "day 4: part 2 of example 1 panicked: not yet",
+            ]
+        );
+    }
+}
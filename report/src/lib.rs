@@ -0,0 +1,362 @@
+//! Runs every day on its personal input and writes the answers, how long they
+//! took and any pictures the day draws as a single static HTML page.
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use common::answer::Answer;
+use common::color::Color;
+use common::grid::Grid;
+use common::inputs::personal_in;
+use common::raster::encode_png;
+use std::any::Any;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+pub mod answers;
+pub mod bench;
+#[cfg(feature = "fetch")]
+pub mod client;
+pub mod dashboard;
+pub mod doctor;
+pub mod puzzle;
+#[cfg(feature = "serve")]
+pub mod serve;
+pub mod stats;
+pub mod timeline;
+pub mod y2019;
+
+/// Pixels per cell in a day's picture.
+const PICTURE_SCALE: usize = 8;
+
+/// A small input with known answers, for checking a day still works without
+/// its personal input. Parts the example doesn't apply to have no answer.
+pub struct Example {
+    pub input: &'static str,
+    pub answers: [Option<&'static str>; 2],
+}
+
+/// A day's solvers, with answers typed so every day looks the same.
+pub struct Day {
+    pub number: u32,
+    pub parts: [fn(&str) -> Answer; 2],
+    /// Solves both parts parsing the input only once, timing the parsing and
+    /// each part on its own, usually with `time_stages`.
+    pub timed: fn(&str) -> Timings,
+    /// Draws the day's picture of its input, for the days that have one.
+    pub picture: Option<fn(&str) -> Grid<Color>>,
+    pub examples: &'static [Example],
+}
+
+/// Solves a day's two parts. Everything that runs days goes through this, so
+/// it doesn't matter how a day is put together, only that it's registered in
+/// its year.
+pub trait Solver {
+    fn part1(&self, input: &str) -> Answer;
+    fn part2(&self, input: &str) -> Answer;
+
+    /// Solves both parts, timing each. Solvers that can't parse the input
+    /// apart from solving count parsing in each part and have no parse time.
+    fn timed(&self, input: &str) -> Timings {
+        let parts = (1..=2)
+            .map(|part| {
+                let start = Instant::now();
+                let answer = self.part(part, input).unwrap();
+                Part {
+                    answer,
+                    elapsed: start.elapsed(),
+                }
+            })
+            .collect();
+        Timings { parse: None, parts }
+    }
+
+    /// Solves part 1 or 2, or returns None for any other part.
+    fn part(&self, part: u32, input: &str) -> Option<Answer> {
+        match part {
+            1 => Some(self.part1(input)),
+            2 => Some(self.part2(input)),
+            _ => None,
+        }
+    }
+}
+
+impl Solver for Day {
+    fn part1(&self, input: &str) -> Answer {
+        (self.parts[0])(input)
+    }
+
+    fn part2(&self, input: &str) -> Answer {
+        (self.parts[1])(input)
+    }
+
+    fn timed(&self, input: &str) -> Timings {
+        (self.timed)(input)
+    }
+}
+
+/// A year's puzzles and the days there are solutions for.
+pub struct Year {
+    pub number: u32,
+    pub days: &'static [Day],
+}
+
+impl Year {
+    /// Returns the day with the given number, if there's a solution for it.
+    pub fn day(&self, number: u32) -> Option<&'static Day> {
+        self.days.iter().find(|day| day.number == number)
+    }
+
+    /// Returns the numbers of the days there are solutions for, in order.
+    pub fn implemented(&self) -> Vec<u32> {
+        self.days.iter().map(|day| day.number).collect()
+    }
+}
+
+/// Every year there are solutions for, in order.
+pub const YEARS: [Year; 1] = [Year {
+    number: 2019,
+    days: &y2019::DAYS,
+}];
+
+/// Returns the year with the given number, if there are solutions for it.
+pub fn year(number: u32) -> Option<&'static Year> {
+    YEARS.iter().find(|year| year.number == number)
+}
+
+/// A part's answer and how long it took to find.
+#[derive(Debug)]
+pub struct Part {
+    pub answer: Answer,
+    pub elapsed: Duration,
+}
+
+/// What running a day produced. A day without a personal input has no parts.
+pub struct Run {
+    pub day: u32,
+    pub parts: Vec<Part>,
+    /// The day's picture, as a PNG.
+    pub picture: Option<Vec<u8>>,
+}
+
+/// Returns the message a solver panicked with.
+pub fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| String::from("unknown panic"))
+}
+
+/// How long a solver took to parse an input, if it was timed on its own, and
+/// to solve each part of it.
+#[derive(Debug)]
+pub struct Timings {
+    pub parse: Option<Duration>,
+    pub parts: Vec<Part>,
+}
+
+/// Parses the input once and solves both parts on what was parsed, timing
+/// the parsing and each part separately.
+pub fn time_stages<'a, T>(
+    input: &'a str,
+    parse: fn(&'a str) -> T,
+    parts: [fn(&T) -> Answer; 2],
+) -> Timings {
+    let start = Instant::now();
+    let parsed = parse(black_box(input));
+    let parse = start.elapsed();
+
+    let parts = parts
+        .iter()
+        .map(|part| {
+            let start = Instant::now();
+            let answer = part(&parsed);
+            Part {
+                answer,
+                elapsed: start.elapsed(),
+            }
+        })
+        .collect();
+    Timings {
+        parse: Some(parse),
+        parts,
+    }
+}
+
+/// Runs both parts of the year's day on its personal input, if there is one.
+pub fn run(year: u32, day: &Day) -> Run {
+    let input = match personal_in(year, day.number) {
+        Some(input) => input,
+        None => {
+            return Run {
+                day: day.number,
+                parts: Vec::new(),
+                picture: None,
+            }
+        }
+    };
+
+    let parts = day.timed(&input).parts;
+    let picture = day.picture.map(|draw| {
+        let mut png = Vec::new();
+        encode_png(&draw(&input), PICTURE_SCALE, &mut png).unwrap();
+        png
+    });
+
+    Run {
+        day: day.number,
+        parts,
+        picture,
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+const STYLE: &str = "body { font-family: sans-serif; margin: 2em; }
+table { border-collapse: collapse; }
+th, td { padding: 0.3em 1em; text-align: left; border-bottom: 1px solid #ccc; }
+td.answer { font-family: monospace; }
+td.time { text-align: right; }
+img { image-rendering: pixelated; }";
+
+/// Writes the runs as an HTML page with one row per part, and the pictures
+/// inlined so the page stands on its own.
+pub fn html(title: &str, runs: &[Run]) -> String {
+    let mut page = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{0}</title>\n\
+         <style>\n{1}\n</style>\n</head>\n<body>\n<h1>{0}</h1>\n<table>\n\
+         <tr><th>Day</th><th>Part</th><th>Answer</th><th>Time</th></tr>\n",
+        escape(title),
+        STYLE
+    );
+
+    let mut total = Duration::default();
+    for run in runs {
+        if run.parts.is_empty() {
+            page += &format!(
+                "<tr><td>{}</td><td></td><td>no input</td><td></td></tr>\n",
+                run.day
+            );
+        }
+        for (part, result) in run.parts.iter().enumerate() {
+            total += result.elapsed;
+            page += &format!(
+                "<tr><td>{}</td><td>{}</td><td class=\"answer\">{}</td>\
+                 <td class=\"time\">{:.3?}</td></tr>\n",
+                run.day,
+                part + 1,
+                escape(&result.answer.to_string()),
+                result.elapsed
+            );
+        }
+    }
+    page += &format!(
+        "<tr><th colspan=\"3\">Total</th><th class=\"time\">{:.3?}</th></tr>\n</table>\n",
+        total
+    );
+
+    for run in runs {
+        if let Some(png) = &run.picture {
+            page += &format!(
+                "<h2>Day {0}</h2>\n<img alt=\"day {0}\" src=\"data:image/png;base64,{1}\">\n",
+                run.day,
+                STANDARD.encode(png)
+            );
+        }
+    }
+
+    page + "</body>\n</html>\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_html() {
+        let runs = [
+            Run {
+                day: 1,
+                parts: vec![
+                    Part {
+                        answer: Answer::from("<42>"),
+                        elapsed: Duration::from_millis(2),
+                    },
+                    Part {
+                        answer: Answer::Int(7),
+                        elapsed: Duration::from_millis(1),
+                    },
+                ],
+                picture: None,
+            },
+            Run {
+                day: 2,
+                parts: Vec::new(),
+                picture: Some(vec![1, 2, 3]),
+            },
+        ];
+
+        let page = html("Advent & Co", &runs);
+        assert!(page.contains("<title>Advent &amp; Co</title>"));
+        assert!(page.contains(
+            "<tr><td>1</td><td>1</td><td class=\"answer\">&lt;42&gt;</td>\
+             <td class=\"time\">2.000ms</td></tr>"
+        ));
+        assert!(page.contains("<tr><td>2</td><td></td><td>no input</td><td></td></tr>"));
+        assert!(page.contains("<th class=\"time\">3.000ms</th>"));
+        assert!(page.contains("src=\"data:image/png;base64,AQID\""));
+    }
+
+    #[test]
+    fn test_days() {
+        let numbers: Vec<u32> = y2019::DAYS.iter().map(|day| day.number).collect();
+        assert_eq!(numbers, (1..=9).collect::<Vec<u32>>());
+    }
+
+    #[test]
+    fn test_year() {
+        assert_eq!(year(2019).map(|year| year.days.len()), Some(9));
+        assert!(year(2018).is_none());
+    }
+
+    #[test]
+    fn test_solver() {
+        let year = year(2019).unwrap();
+        assert_eq!(year.implemented(), (1..=9).collect::<Vec<u32>>());
+        assert!(year.day(10).is_none());
+
+        let day: &dyn Solver = year.day(1).unwrap();
+        assert_eq!(day.part1("12"), Answer::Int(2));
+        assert_eq!(day.part(2, "14"), Some(Answer::Int(2)));
+        assert_eq!(day.part(3, "14"), None);
+    }
+
+    #[test]
+    fn test_timed() {
+        let answers = |timings: Timings| -> Vec<Answer> {
+            timings.parts.into_iter().map(|part| part.answer).collect()
+        };
+        let day = year(2019).unwrap().day(1).unwrap();
+        let timings = day.timed("12\n14\n");
+        assert!(timings.parse.is_some());
+        assert_eq!(answers(timings), vec![Answer::Int(4), Answer::Int(4)]);
+
+        struct Whole;
+        impl Solver for Whole {
+            fn part1(&self, input: &str) -> Answer {
+                input.len().into()
+            }
+            fn part2(&self, _input: &str) -> Answer {
+                Answer::Int(0)
+            }
+        }
+        let timings = Whole.timed("abc");
+        assert!(timings.parse.is_none());
+        assert_eq!(answers(timings), vec![Answer::Int(3), Answer::Int(0)]);
+    }
+}
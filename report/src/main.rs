@@ -0,0 +1,49 @@
+use clap::Parser;
+use report::{html, run, year, YEARS};
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+
+/// Runs every day of a year on its personal input and writes the answers,
+/// times and pictures as one HTML page.
+#[derive(Parser)]
+#[command(name = "report")]
+struct Cli {
+    /// Where to write the page
+    #[arg(long, value_name = "PATH", default_value = "report.html")]
+    output: PathBuf,
+    /// Which year's puzzles the page is about
+    #[arg(long, default_value_t = YEARS[YEARS.len() - 1].number)]
+    year: u32,
+}
+
+fn main() {
+    let Cli {
+        output,
+        year: number,
+    } = Cli::parse();
+
+    let year = match year(number) {
+        Some(year) => year,
+        None => {
+            eprintln!("no solutions for {}", number);
+            process::exit(1);
+        }
+    };
+
+    let runs: Vec<_> = year
+        .days
+        .iter()
+        .map(|day| {
+            eprintln!("running {} day {}", year.number, day.number);
+            run(year.number, day)
+        })
+        .collect();
+
+    let title = format!("Advent of Code {}", year.number);
+    if let Err(err) = fs::write(&output, html(&title, &runs)) {
+        eprintln!("can't write {}: {}", output.display(), err);
+        process::exit(1);
+    }
+    println!("{}", output.display());
+}
@@ -0,0 +1,363 @@
+//! Turns a puzzle's page into text that reads well in a terminal, and picks out
+//! the examples in it so they can be checked in under `inputs/examples`, and
+//! reads what the site said to a submitted answer.
+//!
+//! The page is only ever AoC's own, simple markup, so this scans tags rather
+//! than parsing HTML properly.
+
+use std::fmt;
+
+/// Words in the paragraph before a `<pre>` block that say it's an example.
+const EXAMPLE_HINTS: [&str; 3] = ["example", "for instance", "consider"];
+
+/// A tag, as the name in lower case and whether it closes.
+fn tag(inside: &str) -> (String, bool) {
+    let closing = inside.starts_with('/');
+    let name = inside
+        .trim_start_matches('/')
+        .split(|c: char| c.is_whitespace() || c == '/')
+        .next()
+        .unwrap_or("")
+        .to_lowercase();
+    (name, closing)
+}
+
+/// A piece of the page: text, with entities still encoded, or a tag.
+enum Token<'a> {
+    Text(&'a str),
+    Tag(String, bool),
+}
+
+fn tokens(html: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut rest = html;
+    while let Some(open) = rest.find('<') {
+        if open > 0 {
+            tokens.push(Token::Text(&rest[..open]));
+        }
+        let close = match rest[open..].find('>') {
+            Some(close) => open + close,
+            None => break,
+        };
+        let (name, closing) = tag(&rest[open + 1..close]);
+        tokens.push(Token::Tag(name, closing));
+        rest = &rest[close + 1..];
+    }
+    if !rest.is_empty() && !rest.contains('<') {
+        tokens.push(Token::Text(rest));
+    }
+    tokens
+}
+
+/// Decodes the entities AoC uses, and numeric ones.
+pub fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        decoded.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+
+        let entity = rest.find(';').map(|semi| &rest[1..semi]);
+        let ch = entity.and_then(|entity| match entity {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some(' '),
+            _ => {
+                let number = entity.strip_prefix('#')?;
+                let code = match number.strip_prefix('x') {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                    None => number.parse().ok()?,
+                };
+                std::char::from_u32(code)
+            }
+        });
+
+        match (ch, entity) {
+            (Some(ch), Some(entity)) => {
+                decoded.push(ch);
+                rest = &rest[entity.len() + 2..];
+            }
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+/// Returns the contents of the page's `<article>`s, which hold the puzzle's
+/// parts, or the whole page if it has none.
+pub fn articles(page: &str) -> String {
+    // only ASCII tag names are searched for, and ASCII lowercasing keeps every
+    // byte where it was, so offsets in `lower` are offsets in `page`
+    let lower = page.to_ascii_lowercase();
+    let mut articles = String::new();
+    let mut from = 0;
+    while let Some(start) = lower[from..].find("<article").map(|start| from + start) {
+        let end = lower[start..]
+            .find("</article>")
+            .map_or(page.len(), |end| start + end + "</article>".len());
+        articles.push_str(&page[start..end]);
+        from = end;
+    }
+
+    if articles.is_empty() {
+        page.to_string()
+    } else {
+        articles
+    }
+}
+
+/// Strips the markup, keeping paragraphs apart, list items marked and
+/// preformatted blocks as they are.
+pub fn to_text(html: &str) -> String {
+    let mut text = String::new();
+    for token in tokens(html) {
+        match token {
+            Token::Text(chunk) => text.push_str(&decode_entities(chunk)),
+            Token::Tag(name, closing) => match (name.as_str(), closing) {
+                ("p", true) | ("pre", true) | ("h2", true) | ("ul", true) | ("article", true) => {
+                    text.push_str("\n\n")
+                }
+                ("li", false) => text.push_str("- "),
+                ("li", true) | ("br", _) => text.push('\n'),
+                _ => {}
+            },
+        }
+    }
+
+    // collapse the blank lines blocks left between each other
+    let mut collapsed = String::new();
+    let mut blank = 0;
+    for line in text.trim().lines() {
+        let line = line.trim_end();
+        blank = if line.is_empty() { blank + 1 } else { 0 };
+        if blank < 2 {
+            collapsed.push_str(line);
+            collapsed.push('\n');
+        }
+    }
+    collapsed
+}
+
+/// Returns the text of every `<pre>` block whose paragraph before it calls it
+/// an example, or every block if none do.
+pub fn examples(html: &str) -> Vec<String> {
+    let mut blocks = Vec::new();
+    let mut before = String::new();
+    let mut block: Option<String> = None;
+
+    for token in tokens(html) {
+        match (token, &mut block) {
+            (Token::Tag(name, false), None) if name == "pre" => block = Some(String::new()),
+            (Token::Tag(name, true), Some(text)) if name == "pre" => {
+                let lead = before.to_lowercase();
+                let hinted = EXAMPLE_HINTS.iter().any(|hint| lead.contains(hint));
+                let mut text = text.trim_start_matches('\n').to_string();
+                if !text.ends_with('\n') {
+                    text.push('\n');
+                }
+                blocks.push((hinted, text));
+                block = None;
+                before.clear();
+            }
+            (Token::Text(chunk), Some(text)) => text.push_str(&decode_entities(chunk)),
+            (Token::Text(chunk), None) => before.push_str(chunk),
+            (Token::Tag(name, false), None) if name == "p" => before.clear(),
+            _ => {}
+        }
+    }
+
+    let any_hinted = blocks.iter().any(|(hinted, _)| *hinted);
+    blocks
+        .into_iter()
+        .filter(|(hinted, _)| *hinted || !any_hinted)
+        .map(|(_, text)| text)
+        .collect()
+}
+
+/// What the site made of a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without saying which way.
+    Wrong,
+    /// Answered too soon after the last try, with how long is left to wait.
+    TooSoon(Option<String>),
+    /// The part is already solved, or it's part 2 and part 1 isn't yet.
+    WrongLevel,
+    /// Anything else, as the page's text.
+    Unknown(String),
+}
+
+impl Verdict {
+    /// Reads the verdict from the page the site answers a submission with.
+    pub fn from_page(page: &str) -> Self {
+        let text = to_text(&articles(page));
+        let flat = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        if flat.contains("That's the right answer") {
+            Verdict::Correct
+        } else if flat.contains("That's not the right answer") {
+            if flat.contains("too high") {
+                Verdict::TooHigh
+            } else if flat.contains("too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Wrong
+            }
+        } else if flat.contains("You gave an answer too recently") {
+            let wait = flat.find("You have ").and_then(|start| {
+                let rest = &flat[start + "You have ".len()..];
+                rest.find(" left to wait")
+                    .map(|end| rest[..end].to_string())
+            });
+            Verdict::TooSoon(wait)
+        } else if flat.contains("You don't seem to be solving the right level") {
+            Verdict::WrongLevel
+        } else {
+            Verdict::Unknown(text.trim().to_string())
+        }
+    }
+
+    pub fn is_correct(&self) -> bool {
+        *self == Verdict::Correct
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "That's the right answer, have a star!"),
+            Verdict::TooHigh => write!(f, "Wrong: the answer is too high."),
+            Verdict::TooLow => write!(f, "Wrong: the answer is too low."),
+            Verdict::Wrong => write!(f, "Wrong answer."),
+            Verdict::TooSoon(Some(wait)) => {
+                write!(f, "Answered too recently, try again in {}.", wait)
+            }
+            Verdict::TooSoon(None) => write!(f, "Answered too recently, try again later."),
+            Verdict::WrongLevel => write!(
+                f,
+                "That part isn't open: it's already solved, or the one before isn't yet."
+            ),
+            Verdict::Unknown(text) => write!(f, "The site said:\n{}", text),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 3: Crossed Wires ---</h2>
+<p>The wires twist and turn &amp; cross.</p>
+<p>For example, if the first wire's path is <code>R8,U5,L5,D3</code>, then:</p>
+<pre><code>...........
+.+-----+...
+.o-------X-
+</code></pre>
+<p>The grid itself:</p>
+<pre><code>&lt;grid&gt;</code></pre>
+<p>Here are a few more examples:</p>
+<ul>
+<li><code>R75,D30</code> = distance <code>159</code></li>
+</ul>
+</article>
+<p>Answer: <input type="text" name="answer"></p>
+</main></body></html>"#;
+
+    #[test]
+    fn test_decode_entities() {
+        assert_eq!(decode_entities("a &lt;b&gt; &amp;&#65;&#x42;"), "a <b> &AB");
+        assert_eq!(decode_entities("AT&T &bogus; &"), "AT&T &bogus; &");
+    }
+
+    #[test]
+    fn test_to_text() {
+        let text = to_text(&articles(PAGE));
+        assert!(
+            text.starts_with(
+                "--- Day 3: Crossed Wires ---\n\nThe wires twist and turn & cross.\n\n\
+                 For example, if the first wire's path is R8,U5,L5,D3, then:\n\n\
+                 ...........\n.+-----+...\n.o-------X-\n\n"
+            ),
+            "{}",
+            text
+        );
+        assert!(text.ends_with("- R75,D30 = distance 159\n"), "{}", text);
+        assert!(!text.contains("Answer"));
+
+        // the Kelvin sign lowercases to a one-byte k
+        assert_eq!(
+            articles("\u{212a}<ARTICLE>Day 1</ARTICLE>"),
+            "<ARTICLE>Day 1</ARTICLE>"
+        );
+    }
+
+    #[test]
+    fn test_examples() {
+        assert_eq!(
+            examples(PAGE),
+            vec![String::from("...........\n.+-----+...\n.o-------X-\n")]
+        );
+        // without hints, every block
+        assert_eq!(
+            examples("<p>Like so:</p><pre><code>1\n2</code></pre><pre>3</pre>"),
+            vec![String::from("1\n2\n"), String::from("3\n")]
+        );
+    }
+
+    #[test]
+    fn test_verdict() {
+        let page = |text: &str| format!("<main><article><p>{}</p></article></main>", text);
+        assert_eq!(
+            Verdict::from_page(&page(
+                "That's the right answer!  You are <em>one gold star</em> closer."
+            )),
+            Verdict::Correct
+        );
+        assert_eq!(
+            Verdict::from_page(&page(
+                "That's not the right answer; your answer is too high.  \
+                 Please wait one minute before trying again."
+            )),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            Verdict::from_page(&page(
+                "That's not the right answer; your answer is too low."
+            )),
+            Verdict::TooLow
+        );
+        assert_eq!(
+            Verdict::from_page(&page("That's not the right answer.  If you're stuck...")),
+            Verdict::Wrong
+        );
+        let too_soon = Verdict::from_page(&page(
+            "You gave an answer too recently; you have to wait after submitting an answer \
+             before trying again.  You have 35s left to wait.",
+        ));
+        assert_eq!(too_soon, Verdict::TooSoon(Some(String::from("35s"))));
+        assert_eq!(
+            too_soon.to_string(),
+            "Answered too recently, try again in 35s."
+        );
+        assert_eq!(
+            Verdict::from_page(&page(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            )),
+            Verdict::WrongLevel
+        );
+        assert_eq!(
+            Verdict::from_page(&page("Something new")),
+            Verdict::Unknown(String::from("Something new"))
+        );
+    }
+}
@@ -0,0 +1,247 @@
+//! An HTTP API that solves uploaded inputs, so friends can check their answers
+//! against these solutions:
+//!
+//! - `GET` or `POST /{year}/{day}/{part}` with the input as the body returns
+//!   the [`Solved`] answer, or an [`ErrorBody`] if there's no such part, the
+//!   solver gave up on the input or it took too long.
+//!
+//! Solvers have no step limit, so an input can keep one running forever, like
+//! an Intcode program that jumps to itself. A request gives up on its solver
+//! after `SOLVE_TIMEOUT`, but the thread it runs on stays busy, so only
+//! `MAX_SOLVING` solvers may run at once and requests past that are turned
+//! away rather than using up every thread.
+
+use crate::{panic_message, year, Part, Solver};
+use axum::extract::{DefaultBodyLimit, Path, State};
+use axum::http::StatusCode;
+use axum::routing::get;
+use axum::{Json, Router};
+use common::answer::Answer;
+use serde::Serialize;
+use std::fmt;
+use std::io;
+use std::net::SocketAddr;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+
+/// How long a request waits for its solver.
+pub const SOLVE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Most solvers running at once, counting ones whose requests timed out.
+pub const MAX_SOLVING: usize = 8;
+
+/// Largest input accepted, far more than any puzzle's.
+pub const MAX_INPUT_BYTES: usize = 1 << 20;
+
+/// Why an input couldn't be solved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    NoSuchDay {
+        year: u32,
+        day: u32,
+    },
+    NoSuchPart(u32),
+    Panicked(String),
+    TimedOut(Duration),
+    /// `MAX_SOLVING` solvers are already running.
+    Busy,
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::NoSuchDay { year, day } => {
+                write!(f, "no solution for {} day {}", year, day)
+            }
+            SolveError::NoSuchPart(part) => write!(f, "no part {}, only 1 and 2", part),
+            SolveError::Panicked(message) => write!(f, "solver panicked: {}", message),
+            SolveError::TimedOut(limit) => write!(f, "no answer within {:?}", limit),
+            SolveError::Busy => write!(f, "too many inputs being solved, try again later"),
+        }
+    }
+}
+
+/// Solves one part of the year's day for the input, timing it.
+pub fn solve(year_number: u32, day: u32, part: u32, input: &str) -> Result<Part, SolveError> {
+    let day = year(year_number)
+        .and_then(|year| year.day(day))
+        .ok_or(SolveError::NoSuchDay {
+            year: year_number,
+            day,
+        })?;
+    if !(1..=2).contains(&part) {
+        return Err(SolveError::NoSuchPart(part));
+    }
+
+    let start = Instant::now();
+    let answer = panic::catch_unwind(AssertUnwindSafe(|| day.part(part, input).unwrap()))
+        .map_err(|payload| SolveError::Panicked(panic_message(payload)))?;
+    Ok(Part {
+        answer,
+        elapsed: start.elapsed(),
+    })
+}
+
+/// An answer as JSON: numbers stay numbers, everything else is text.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum AnswerBody {
+    Int(i64),
+    Text(String),
+}
+
+impl From<&Answer> for AnswerBody {
+    fn from(answer: &Answer) -> Self {
+        match answer {
+            Answer::Int(value) => AnswerBody::Int(*value),
+            answer => AnswerBody::Text(answer.to_string()),
+        }
+    }
+}
+
+/// A solved part.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Solved {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub answer: AnswerBody,
+    pub elapsed_ms: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ErrorBody {
+    pub error: String,
+}
+
+/// Like `solve`, on a blocking thread holding one of `solving`'s permits until
+/// the solver finishes, giving up on it after `timeout`.
+pub async fn solve_limited(
+    solving: Arc<Semaphore>,
+    timeout: Duration,
+    year: u32,
+    day: u32,
+    part: u32,
+    input: String,
+) -> Result<Part, SolveError> {
+    let permit = solving.try_acquire_owned().map_err(|_| SolveError::Busy)?;
+    // solvers can take seconds, so keep them off the threads serving requests
+    let solver = tokio::task::spawn_blocking(move || {
+        let _permit = permit;
+        solve(year, day, part, &input)
+    });
+
+    match tokio::time::timeout(timeout, solver).await {
+        Ok(solved) => solved.unwrap_or_else(|err| Err(SolveError::Panicked(err.to_string()))),
+        Err(_) => Err(SolveError::TimedOut(timeout)),
+    }
+}
+
+async fn solve_upload(
+    State(solving): State<Arc<Semaphore>>,
+    Path((year, day, part)): Path<(u32, u32, u32)>,
+    input: String,
+) -> Result<Json<Solved>, (StatusCode, Json<ErrorBody>)> {
+    let result = solve_limited(solving, SOLVE_TIMEOUT, year, day, part, input).await;
+
+    match result {
+        Ok(solved) => Ok(Json(Solved {
+            year,
+            day,
+            part,
+            answer: AnswerBody::from(&solved.answer),
+            elapsed_ms: solved.elapsed.as_secs_f64() * 1000.0,
+        })),
+        Err(err) => {
+            let status = match err {
+                SolveError::Panicked(_) => StatusCode::UNPROCESSABLE_ENTITY,
+                SolveError::TimedOut(_) | SolveError::Busy => StatusCode::SERVICE_UNAVAILABLE,
+                SolveError::NoSuchDay { .. } | SolveError::NoSuchPart(_) => StatusCode::NOT_FOUND,
+            };
+            let body = ErrorBody {
+                error: err.to_string(),
+            };
+            Err((status, Json(body)))
+        }
+    }
+}
+
+/// Returns the API's routes.
+pub fn router() -> Router {
+    Router::new()
+        .route("/{year}/{day}/{part}", get(solve_upload).post(solve_upload))
+        .layer(DefaultBodyLimit::max(MAX_INPUT_BYTES))
+        .with_state(Arc::new(Semaphore::new(MAX_SOLVING)))
+}
+
+/// Serves the API on `addr` until the process is killed.
+pub fn serve(addr: SocketAddr) -> io::Result<()> {
+    let runtime = tokio::runtime::Runtime::new()?;
+
+    runtime.block_on(async move {
+        let listener = tokio::net::TcpListener::bind(addr).await?;
+        println!("serving on http://{}", listener.local_addr()?);
+        axum::serve(listener, router()).await
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve() {
+        let part = solve(2019, 1, 2, "14\n1969").unwrap();
+        assert_eq!(part.answer, Answer::Int(2 + 966));
+
+        assert_eq!(
+            solve(2018, 1, 1, "12").unwrap_err().to_string(),
+            "no solution for 2018 day 1"
+        );
+        assert_eq!(
+            solve(2019, 1, 3, "12").unwrap_err(),
+            SolveError::NoSuchPart(3)
+        );
+        assert!(matches!(
+            solve(2019, 1, 1, "twelve"),
+            Err(SolveError::Panicked(_))
+        ));
+    }
+
+    #[test]
+    fn test_solve_limited() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let limited = |permits, program: &str| {
+            runtime.block_on(solve_limited(
+                Arc::new(Semaphore::new(permits)),
+                Duration::from_millis(100),
+                2019,
+                9,
+                1,
+                program.to_string(),
+            ))
+        };
+
+        assert_eq!(limited(1, "104,7,99").unwrap().answer, Answer::Int(7));
+        assert_eq!(limited(0, "104,7,99").unwrap_err(), SolveError::Busy);
+        // jumps to itself forever
+        assert_eq!(
+            limited(1, "1105,1,0").unwrap_err(),
+            SolveError::TimedOut(Duration::from_millis(100))
+        );
+
+        // the looping solver never gives its thread back
+        runtime.shutdown_background();
+    }
+
+    #[test]
+    fn test_answer_body() {
+        assert_eq!(AnswerBody::from(&Answer::Int(5)), AnswerBody::Int(5));
+        assert_eq!(
+            AnswerBody::from(&Answer::from("CYUAH")),
+            AnswerBody::Text(String::from("CYUAH"))
+        );
+    }
+}
@@ -0,0 +1,174 @@
+//! A summary of the season: which days are done, the stars earned, how long the
+//! solutions take and how much code they are.
+
+use crate::timeline::Solve;
+use crate::{Run, Year};
+use common::inputs::YEAR;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// What's known about one day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayStats {
+    pub day: u32,
+    pub stars: usize,
+    /// How long both parts took on the personal input, if there is one.
+    pub runtime: Option<Duration>,
+    pub lines: usize,
+}
+
+/// Returns the directory of the crate solving `day` of `year`.
+pub fn crate_dir(year: u32, day: u32) -> PathBuf {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    if year == YEAR {
+        root.join(format!("day{}", day))
+    } else {
+        root.join(format!("y{}", year)).join(format!("day{}", day))
+    }
+}
+
+/// Counts the lines of Rust under `dir` that aren't blank or comments.
+pub fn lines_of_code(dir: &Path) -> io::Result<usize> {
+    let mut lines = 0;
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            lines += lines_of_code(&path)?;
+        } else if path.extension().is_some_and(|extension| extension == "rs") {
+            lines += fs::read_to_string(&path)?
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with("//"))
+                .count();
+        }
+    }
+    Ok(lines)
+}
+
+/// Gathers the stats for each of the year's days from their runs and the
+/// recorded solves.
+pub fn collect(year: &Year, runs: &[Run], solves: &[Solve]) -> Vec<DayStats> {
+    year.days
+        .iter()
+        .map(|day| {
+            let run = runs.iter().find(|run| run.day == day.number);
+            let stars = solves
+                .iter()
+                .filter(|solve| solve.year == year.number && solve.day == day.number)
+                .count();
+            let runtime = run
+                .filter(|run| !run.parts.is_empty())
+                .map(|run| run.parts.iter().map(|part| part.elapsed).sum());
+            let lines = lines_of_code(&crate_dir(year.number, day.number).join("src")).unwrap_or(0);
+
+            DayStats {
+                day: day.number,
+                stars,
+                runtime,
+                lines,
+            }
+        })
+        .collect()
+}
+
+/// Writes the summary for the year, followed by a line per day.
+pub fn summary(year: u32, days: &[DayStats]) -> String {
+    let timed = || days.iter().filter_map(|day| Some((day.day, day.runtime?)));
+    let total: Duration = timed().map(|(_, runtime)| runtime).sum();
+    let stars: usize = days.iter().map(|day| day.stars).sum();
+    let lines: usize = days.iter().map(|day| day.lines).sum();
+
+    let mut text = format!("{}\n", year);
+    text += &format!("days implemented  {}\n", days.len());
+    text += &format!("stars             {} of {}\n", stars, 2 * days.len());
+    text += &format!("total runtime     {:.3?}\n", total);
+    if let Some((day, runtime)) = timed().min_by_key(|&(_, runtime)| runtime) {
+        text += &format!("fastest           day {} ({:.3?})\n", day, runtime);
+    }
+    if let Some((day, runtime)) = timed().max_by_key(|&(_, runtime)| runtime) {
+        text += &format!("slowest           day {} ({:.3?})\n", day, runtime);
+    }
+    text += &format!("lines of code     {}\n\n", lines);
+
+    text += "day  stars  runtime     lines\n";
+    for day in days {
+        let runtime = match day.runtime {
+            Some(runtime) => format!("{:.3?}", runtime),
+            None => String::from("no input"),
+        };
+        text += &format!(
+            "{:>3}  {:<5}  {:<10}  {:>5}\n",
+            day.day,
+            "*".repeat(day.stars),
+            runtime,
+            day.lines
+        );
+    }
+
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::testing::scratch_dir;
+
+    #[test]
+    fn test_lines_of_code() {
+        let dir = scratch_dir();
+        fs::create_dir(dir.join("bin")).unwrap();
+        fs::write(
+            dir.join("lib.rs"),
+            "//! Docs.\n\nfn main() {\n    // hi\n}\n",
+        )
+        .unwrap();
+        fs::write(dir.join("bin").join("a.rs"), "fn a() {}\n").unwrap();
+        fs::write(dir.join("notes.txt"), "not code\n").unwrap();
+
+        assert_eq!(lines_of_code(&dir).unwrap(), 3);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_summary() {
+        let days = [
+            DayStats {
+                day: 1,
+                stars: 2,
+                runtime: Some(Duration::from_millis(3)),
+                lines: 40,
+            },
+            DayStats {
+                day: 2,
+                stars: 1,
+                runtime: None,
+                lines: 60,
+            },
+            DayStats {
+                day: 3,
+                stars: 0,
+                runtime: Some(Duration::from_millis(1)),
+                lines: 0,
+            },
+        ];
+
+        assert_eq!(
+            summary(2019, &days),
+            "2019\n\
+             days implemented  3\n\
+             stars             3 of 6\n\
+             total runtime     4.000ms\n\
+             fastest           day 3 (1.000ms)\n\
+             slowest           day 1 (3.000ms)\n\
+             lines of code     100\n\
+             \n\
+             day  stars  runtime     lines\n  \
+             1  **     3.000ms        40\n  \
+             2  *      no input       60\n  \
+             3         1.000ms         0\n"
+        );
+    }
+}
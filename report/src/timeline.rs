@@ -0,0 +1,221 @@
+//! A log of when each part was first solved, kept in `solves.csv` in the inputs
+//! directory, and the personal completion timeline drawn from it. Parts are
+//! recorded as solved when `aoc submit` gets an answer accepted or `aoc verify`
+//! first sees it match `answers.toml`.
+
+use common::inputs::inputs_dir;
+use common::timings::datetime;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The first line of a new solves file.
+pub const HEADER: &str = "year,day,part,solved_at";
+
+/// Returns where the solves are kept.
+pub fn solves_path() -> PathBuf {
+    inputs_dir().join("solves.csv")
+}
+
+/// When a part was first solved, in seconds since the Unix epoch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Solve {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub solved_at: u64,
+}
+
+/// A line of the solves file that isn't a solve.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseSolveError(pub String);
+
+impl fmt::Display for ParseSolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "not a solve: {:?}", self.0)
+    }
+}
+
+impl Solve {
+    fn parse(line: &str) -> Result<Self, ParseSolveError> {
+        let fields: Vec<u64> = line
+            .split(',')
+            .map(|field| field.trim().parse())
+            .collect::<Result<_, _>>()
+            .map_err(|_| ParseSolveError(line.to_string()))?;
+
+        match fields[..] {
+            [year, day, part, solved_at] => Ok(Solve {
+                year: year as u32,
+                day: day as u32,
+                part: part as u32,
+                solved_at,
+            }),
+            _ => Err(ParseSolveError(line.to_string())),
+        }
+    }
+
+    /// A solve of the part now.
+    pub fn now(year: u32, day: u32, part: u32) -> Self {
+        let solved_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        Solve {
+            year,
+            day,
+            part,
+            solved_at,
+        }
+    }
+
+    /// Returns when the day's puzzle unlocked: midnight US Eastern, which is
+    /// 05:00 UTC, on that day of December.
+    pub fn unlocked_at(&self) -> u64 {
+        // Howard Hinnant's days_from_civil, for December only
+        let year = self.year as u64;
+        let day_of_year = (153 * 9 + 2) / 5 + self.day as u64 - 1;
+        let year_of_era = year % 400;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        let days = (year / 400) * 146_097 + day_of_era - 719_468;
+
+        days * 86_400 + 5 * 3600
+    }
+}
+
+/// Reads the solves at `path`, none if the file doesn't exist yet.
+pub fn load(path: &Path) -> io::Result<Vec<Solve>> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+
+    text.lines()
+        .skip(1)
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            Solve::parse(line)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))
+        })
+        .collect()
+}
+
+/// Records the solve at `path` unless the part was already solved, returning
+/// whether it was new.
+pub fn record(path: &Path, solve: Solve) -> io::Result<bool> {
+    let solves = load(path)?;
+    let solved = solves.iter().any(|earlier| {
+        (earlier.year, earlier.day, earlier.part) == (solve.year, solve.day, solve.part)
+    });
+    if solved {
+        return Ok(false);
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    let mut row = String::new();
+    if file.metadata()?.len() == 0 {
+        row += HEADER;
+        row += "\n";
+    }
+    row += &format!(
+        "{},{},{},{}\n",
+        solve.year, solve.day, solve.part, solve.solved_at
+    );
+    file.write_all(row.as_bytes())?;
+
+    Ok(true)
+}
+
+/// Records the part as solved now in the solves file, unless it already was,
+/// returning whether it was new.
+pub fn record_now(year: u32, day: u32, part: u32) -> io::Result<bool> {
+    record(&solves_path(), Solve::now(year, day, part))
+}
+
+fn since_unlock(solve: &Solve) -> String {
+    let secs = solve.solved_at.saturating_sub(solve.unlocked_at());
+    let (hours, minutes, secs) = (secs / 3600, secs / 60 % 60, secs % 60);
+    if hours >= 24 {
+        format!("+{}d{:02}h{:02}m", hours / 24, hours % 24, minutes)
+    } else {
+        format!("+{}h{:02}m{:02}s", hours, minutes, secs)
+    }
+}
+
+/// Lays the solves out in the order they happened, each with how long after
+/// the puzzle unlocked it came.
+pub fn render(solves: &[Solve]) -> String {
+    let mut solves = solves.to_vec();
+    solves.sort_by_key(|solve| solve.solved_at);
+
+    solves
+        .iter()
+        .map(|solve| {
+            format!(
+                "{} day {:>2} part {}  {}  {}\n",
+                solve.year,
+                solve.day,
+                solve.part,
+                datetime(solve.solved_at),
+                since_unlock(solve)
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::testing::scratch_dir;
+
+    fn solve(day: u32, part: u32, solved_at: u64) -> Solve {
+        Solve {
+            year: 2019,
+            day,
+            part,
+            solved_at,
+        }
+    }
+
+    #[test]
+    fn test_unlocked_at() {
+        // 2019-12-01 05:00:00 UTC
+        assert_eq!(solve(1, 1, 0).unlocked_at(), 1_575_176_400);
+        assert_eq!(solve(25, 1, 0).unlocked_at(), 1_575_176_400 + 24 * 86_400);
+    }
+
+    #[test]
+    fn test_record() {
+        let path = scratch_dir().join("solves.csv");
+        assert_eq!(load(&path).unwrap(), Vec::new());
+
+        assert!(record(&path, solve(1, 1, 1_575_176_653)).unwrap());
+        assert!(!record(&path, solve(1, 1, 1_575_200_000)).unwrap());
+        assert!(record(&path, solve(1, 2, 1_575_177_000)).unwrap());
+
+        let solves = load(&path).unwrap();
+        assert_eq!(
+            solves,
+            vec![solve(1, 1, 1_575_176_653), solve(1, 2, 1_575_177_000)]
+        );
+        assert!(fs::read_to_string(&path).unwrap().starts_with(HEADER));
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_render() {
+        let solves = [
+            solve(2, 1, 1_575_176_400 + 86_400 * 3),
+            solve(1, 1, 1_575_176_653),
+        ];
+        assert_eq!(
+            render(&solves),
+            "2019 day  1 part 1  2019-12-01 05:04:13  +0h04m13s\n\
+             2019 day  2 part 1  2019-12-04 05:00:00  +2d00h00m\n"
+        );
+    }
+}
@@ -0,0 +1,198 @@
+//! The 2019 puzzles, each solved by its own `dayN` crate.
+
+use crate::{time_stages, Day, Example};
+use common::color::Color;
+
+/// Every day of 2019 there's a solution for, in order.
+pub const DAYS: [Day; 9] = [
+    Day {
+        number: 1,
+        parts: [
+            |input| day1::solve_part1(input).into(),
+            |input| day1::solve_part2(input).into(),
+        ],
+        timed: |input| {
+            time_stages(
+                input,
+                day1::masses,
+                [|parsed| day1::part1(parsed).into(), |parsed| day1::part2(parsed).into()],
+            )
+        },
+        picture: None,
+        examples: &[Example {
+            input: include_str!("../../inputs/examples/day1.txt"),
+            answers: [Some("34241"), Some("51316")],
+        }],
+    },
+    Day {
+        number: 2,
+        parts: [
+            |input| day2::solve_part1(input).into(),
+            |input| day2::solve_part2(input).into(),
+        ],
+        timed: |input| {
+            time_stages(
+                input,
+                day2::program,
+                [|parsed| day2::part1(parsed).into(), |parsed| day2::part2(parsed).into()],
+            )
+        },
+        picture: None,
+        examples: &[Example {
+            // adds [12] and the verb, 2, into [0]
+            input: "1,0,0,0,99,0,0,0,0,0,0,0,7",
+            answers: [Some("9"), None],
+        }],
+    },
+    Day {
+        number: 3,
+        parts: [
+            |input| day3::solve_part1(input).into(),
+            |input| day3::solve_part2(input).into(),
+        ],
+        timed: |input| {
+            time_stages(
+                input,
+                day3::wires,
+                [|parsed| day3::part1(parsed).into(), |parsed| day3::part2(parsed).into()],
+            )
+        },
+        picture: None,
+        examples: &[Example {
+            input: include_str!("../../inputs/examples/day3.txt"),
+            answers: [Some("6"), Some("30")],
+        }],
+    },
+    Day {
+        number: 4,
+        parts: [
+            |input| day4::solve_part1(input).into(),
+            |input| day4::solve_part2(input).into(),
+        ],
+        timed: |input| {
+            time_stages(
+                input,
+                day4::ranges,
+                [|parsed| day4::part1(parsed).into(), |parsed| day4::part2(parsed).into()],
+            )
+        },
+        picture: None,
+        examples: &[Example {
+            input: "111122-111123",
+            answers: [Some("2"), Some("1")],
+        }],
+    },
+    Day {
+        number: 5,
+        parts: [
+            |input| day5::solve_part1(input).into(),
+            |input| day5::solve_part2(input).into(),
+        ],
+        timed: |input| {
+            time_stages(
+                input,
+                day5::program,
+                [|parsed| day5::part1(parsed).into(), |parsed| day5::part2(parsed).into()],
+            )
+        },
+        picture: None,
+        examples: &[Example {
+            // echoes its input, the system ID
+            input: "3,0,4,0,99",
+            answers: [Some("1"), Some("5")],
+        }],
+    },
+    Day {
+        number: 6,
+        parts: [
+            |input| day6::solve_part1(input).into(),
+            |input| day6::solve_part2(input).into(),
+        ],
+        timed: |input| {
+            time_stages(
+                input,
+                day6::OrbitMap::parse,
+                [|parsed| day6::part1(parsed).into(), |parsed| day6::part2(parsed).into()],
+            )
+        },
+        picture: None,
+        examples: &[Example {
+            input: include_str!("../../inputs/examples/day6.txt"),
+            answers: [Some("54"), Some("4")],
+        }],
+    },
+    Day {
+        number: 7,
+        parts: [
+            |input| day7::solve_part1(input).into(),
+            |input| day7::solve_part2(input).into(),
+        ],
+        timed: |input| {
+            time_stages(
+                input,
+                day7::program,
+                [|parsed| day7::part1(parsed).into(), |parsed| day7::part2(parsed).into()],
+            )
+        },
+        picture: None,
+        examples: &[
+            Example {
+                input: "3,15,3,16,1002,16,10,16,1,16,15,15,4,15,99,0,0",
+                answers: [Some("43210"), None],
+            },
+            Example {
+                input: "3,26,1001,26,-4,26,3,27,1002,27,2,27,1,27,26,27,4,27,1001,28,-1,28,1005,28,6,99,0,0,5",
+                answers: [None, Some("139629729")],
+            },
+        ],
+    },
+    Day {
+        number: 8,
+        parts: [
+            |input| day8::solve_part1(input).into(),
+            |input| day8::image(input, day8::WIDTH, day8::HEIGHT).into(),
+        ],
+        timed: |input| {
+            time_stages::<Vec<Vec<u32>>>(
+                input,
+                |input| day8::layers(input, day8::WIDTH, day8::HEIGHT),
+                [
+                    |parsed| day8::part1(parsed).into(),
+                    |parsed| day8::stack(parsed, day8::WIDTH, day8::HEIGHT).into(),
+                ],
+            )
+        },
+        picture: Some(|input| {
+            day8::image(input, day8::WIDTH, day8::HEIGHT).map(|&lit| {
+                if lit {
+                    Color::WHITE
+                } else {
+                    Color::BLACK
+                }
+            })
+        }),
+        examples: &[Example {
+            input: "011001000110010011001001010010100011001010010100101000001010100101001011110100000010010010111101001010010001001001010010100100110000100011001001010010",
+            answers: [Some("0"), Some("CYUAH")],
+        }],
+    },
+    Day {
+        number: 9,
+        parts: [
+            |input| day9::solve_part1(input).into(),
+            |input| day9::solve_part2(input).into(),
+        ],
+        timed: |input| {
+            time_stages(
+                input,
+                day9::program,
+                [|parsed| day9::part1(parsed).into(), |parsed| day9::part2(parsed).into()],
+            )
+        },
+        picture: None,
+        examples: &[Example {
+            input: "104,1125899906842624,99",
+            answers: [Some("1125899906842624"), Some("1125899906842624")],
+        }],
+    },
+];
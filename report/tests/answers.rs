@@ -0,0 +1,14 @@
+use report::answers::{answers_path, check, describe, parse};
+use std::fs;
+
+#[test]
+fn test_answers() {
+    let answers = parse(&fs::read_to_string(answers_path()).unwrap()).unwrap();
+    let failures: Vec<String> = answers
+        .iter()
+        .map(|expected| (expected, check(expected)))
+        .filter(|(_, check)| check.failed())
+        .map(|(expected, check)| describe(expected, &check))
+        .collect();
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
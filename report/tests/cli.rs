@@ -0,0 +1,45 @@
+use common::testing::{run_with_input, stderr, stdout};
+
+const EXE: &str = env!("CARGO_BIN_EXE_report");
+const BENCH: &str = env!("CARGO_BIN_EXE_bench");
+
+#[test]
+fn test_bad_flags() {
+    for args in [&["--colour"][..], &["--year"], &["--year", "twenty"]] {
+        let output = run_with_input(EXE, 1, None, args);
+        let err = stderr(&output);
+        assert_eq!(output.status.code(), Some(2), "{}", err);
+        assert!(!err.contains("panicked"), "{}", err);
+    }
+}
+
+#[test]
+fn test_unknown_year() {
+    let output = run_with_input(EXE, 1, None, &["--year", "2015"]);
+    assert_eq!(stderr(&output), "no solutions for 2015\n");
+}
+
+#[test]
+fn test_help() {
+    let output = run_with_input(EXE, 1, None, &["--help"]);
+    assert!(stdout(&output).contains("--output <PATH>"));
+}
+
+#[test]
+fn test_bench_flags() {
+    for args in [
+        &[][..],
+        &["compare", "--runs"],
+        &["compare", "--day", "one"],
+    ] {
+        let output = run_with_input(BENCH, 1, None, args);
+        let err = stderr(&output);
+        assert_eq!(output.status.code(), Some(2), "{}", err);
+        assert!(!err.contains("panicked"), "{}", err);
+    }
+
+    let output = run_with_input(BENCH, 1, None, &["compare", "--help"]);
+    let out = stdout(&output);
+    assert!(out.contains("--day <DAY>"), "{}", out);
+    assert!(out.contains("--runs <RUNS>"), "{}", out);
+}